@@ -1,228 +1,1661 @@
-use crate::ast::{FfmpegCommand, OptionNode, OutputSpec};
-use crate::codec_db::CodecDatabase;
+use crate::ast::{parse_time_seconds, Bitrate, FfmpegCommand, FilterSpec, OptionNode, OutputSpec};
+use crate::rules::{RuleContext, RuleRegistry};
+use crate::paths::{classify_path, normalize_path, PathKind};
+use crate::codec_db::{AudioEncoderConstraints, FfmpegVersion, BITMAP_SUBTITLE_CODECS, CodecDatabase, FormatInfo, OptionValues, GENERIC_ENCODER_OPTIONS, KNOWN_ENCODER_OPTIONS, STREAM_COPY};
 use crate::stream_tracker::StreamTracker;
-use crate::rich_content::{build_rich_content, generate_pipeline_diagram};
-use shared_types::{AnalyzerDiagnostics, DiagnosticKind, DiagnosticMessage, Severity, SourceCodeSpan, StreamType, DiagnosticRich, RichBlock, DiagnosticSpan, SpanRole};
+use crate::suggest::{did_you_mean, filter_suggestions, format_suggestions};
+use crate::rich_content::{build_rich_content, explain_subtitle_modes, generate_pipeline_diagram};
+use crate::messages::{Arg, Locale, Message};
+use std::collections::HashMap;
+use shared_types::{AnalyzerDiagnostics, DiagnosticKind, DiagnosticMessage, LintConfig, MediaFile, Severity, SourceCodeSpan, StreamType, DiagnosticRich, RichBlock, DiagnosticSpan, SpanRole};
+
+/// What is known about the environment a command runs in, beyond the command itself
+#[derive(Debug, Clone, Default)]
+pub struct AnalysisOptions {
+    /// Known stream information for input files; other inputs are guessed from their extension
+    pub media: Vec<MediaFile>,
+    /// ffmpeg release the command will run with; None assumes the latest release
+    pub ffmpeg_version: Option<FfmpegVersion>,
+    /// Codes and categories to drop or report at another severity
+    pub lint: Option<LintConfig>,
+    /// Language of the diagnostic messages
+    pub locale: Locale,
+}
 
 /// Analyze FFmpeg command and return diagnostics
 pub fn analyze_command(command: FfmpegCommand) -> AnalyzerDiagnostics {
-    let mut diagnostics = Vec::new();
-    let mut tracker = StreamTracker::new();
-    let db = CodecDatabase::new();
+    analyze_command_with_options(command, AnalysisOptions::default())
+}
+
+/// Analyze FFmpeg command using known input streams and the target ffmpeg release
+pub fn analyze_command_with_options(command: FfmpegCommand, options: AnalysisOptions) -> AnalyzerDiagnostics {
+    analyze_command_with_database(command, options, &CodecDatabase::new())
+}
+
+/// Analyze FFmpeg command against a codec database, e.g. one imported from the listings
+/// of the ffmpeg build the command will run with
+pub fn analyze_command_with_database(command: FfmpegCommand, options: AnalysisOptions, db: &CodecDatabase) -> AnalyzerDiagnostics {
+    analyze_command_with_rules(command, options, db, &RuleRegistry::builtin())
+}
+
+/// Resolve the command's streams, then run every enabled rule of `rules` over it
+pub fn analyze_command_with_rules(
+    command: FfmpegCommand,
+    options: AnalysisOptions,
+    db: &CodecDatabase,
+    rules: &RuleRegistry,
+) -> AnalyzerDiagnostics {
+    let mut tracker = StreamTracker::with_database(db.clone(), options.media);
     
-    // Phase 1: Discover streams from inputs
-    let input_diagnostics = tracker.analyze_inputs(&command.inputs);
-    diagnostics.extend(input_diagnostics);
+    // Discover streams from inputs and the outputs of -filter_complex
+    tracker.analyze_inputs(&command.inputs);
+    for option in &command.global_options {
+        if let OptionNode::FilterComplex { filter, .. } = option {
+            tracker.register_filter_graph(filter);
+        }
+    }
     
-    // Phase 2: Validate outputs
-    for output in &command.outputs {
-        let output_diagnostics = analyze_output(output, &tracker, &db);
-        diagnostics.extend(output_diagnostics);
+    let context = RuleContext { command: &command, tracker: &tracker, db, ffmpeg_version: options.ffmpeg_version, locale: options.locale };
+    AnalyzerDiagnostics { messages: rules.run(&context, options.lint.as_ref()) }
+}
+
+/// Inputs whose streams could be neither probed nor guessed from the extension. Network streams and
+/// pipes never have a file to probe, so they are not reported
+pub(crate) fn check_input_streams(context: &RuleContext) -> Vec<DiagnosticMessage> {
+    let tracker = context.tracker;
+    context
+        .command
+        .inputs
+        .iter()
+        .enumerate()
+        .filter(|(input_idx, input)| tracker.unknown_inputs.contains(input_idx) && !is_stream_or_pipe(&input.file_path))
+        .map(|(_, input)| DiagnosticMessage {
+            code: "W200".to_string(),
+            severity: Severity::Warning,
+            kind: DiagnosticKind::ParseError {
+                message: "Could not determine stream types from input".to_string(),
+            },
+            message: Message::new("unknown-input-streams").arg("path", &input.file_path).render(context.locale),
+            spans: vec![DiagnosticSpan { span: input.file_path_span.clone(), role: SpanRole::Target, message: context.locale.text("span-unknown-streams") }],
+            rich: None,
+        })
+        .collect()
+}
+
+/// Protocol URLs (`rtmp://`, `https://`) other than `file://`, and `-` or `pipe:` for standard input
+fn is_stream_or_pipe(path: &str) -> bool {
+    path == "-"
+        || path.starts_with("pipe:")
+        || path.split_once("://").is_some_and(|(scheme, _)| scheme != "file" && scheme.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+/// Seek and trim options on every input and output
+pub(crate) fn check_trim_options(context: &RuleContext) -> Vec<DiagnosticMessage> {
+    let mut diagnostics = Vec::new();
+    for (input_idx, input) in context.command.inputs.iter().enumerate() {
+        diagnostics.extend(validate_trim_options(&input.options, true, context.locale));
+        let duration = context.tracker.input_durations.get(input_idx).copied().flatten();
+        diagnostics.extend(validate_input_seek(&input.options, duration, &input.file_path_span, context.locale));
+    }
+    for output in &context.command.outputs {
+        diagnostics.extend(validate_trim_options(&output.options, false, context.locale));
+    }
+    diagnostics
+}
+
+/// A codec before `-i` selects the decoder
+pub(crate) fn check_input_decoders(context: &RuleContext) -> Vec<DiagnosticMessage> {
+    context
+        .command
+        .inputs
+        .iter()
+        .flat_map(|input| input.options.iter())
+        .filter_map(|option| match option {
+            OptionNode::VideoCodec { codec, codec_span, .. }
+            | OptionNode::AudioCodec { codec, codec_span, .. }
+            | OptionNode::SubtitleCodec { codec, codec_span, .. }
+            | OptionNode::Codec { codec, codec_span, .. } => context.tracker.validate_decoder(codec, codec_span, context.locale),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Codecs and container an output selects; a later option overrides an earlier one
+struct OutputCodecs {
+    video: Option<(String, SourceCodeSpan)>,
+    audio: Option<(String, SourceCodeSpan)>,
+    subtitle: Option<(String, SourceCodeSpan)>,
+    /// Span of `-c copy`
+    copy_all: Option<SourceCodeSpan>,
+    /// `-f`, or the format the file name implies
+    format: Option<String>,
+    format_span: Option<SourceCodeSpan>,
+}
+
+impl OutputCodecs {
+    fn of(output: &OutputSpec, db: &CodecDatabase) -> Self {
+        let mut codecs = OutputCodecs {
+            video: None,
+            audio: None,
+            subtitle: None,
+            copy_all: None,
+            format: db.infer_format_from_filename(&output.file_path),
+            format_span: None,
+        };
+        for option in &output.options {
+            match option {
+                OptionNode::VideoCodec { codec, codec_span, .. } => codecs.video = Some((codec.clone(), codec_span.clone())),
+                OptionNode::AudioCodec { codec, codec_span, .. } => codecs.audio = Some((codec.clone(), codec_span.clone())),
+                OptionNode::SubtitleCodec { codec, codec_span, .. } => codecs.subtitle = Some((codec.clone(), codec_span.clone())),
+                OptionNode::Codec { codec, codec_span, .. } if codec == STREAM_COPY => codecs.copy_all = Some(codec_span.clone()),
+                OptionNode::Codec { codec, codec_span, .. } => {
+                    // A generic codec selects the stream type it encodes
+                    let selected = (codec.clone(), codec_span.clone());
+                    match db.get_codec(codec).map(|c| &c.stream_type) {
+                        Some(StreamType::Video) => codecs.video = Some(selected),
+                        Some(StreamType::Audio) => codecs.audio = Some(selected),
+                        Some(StreamType::Subtitle) => codecs.subtitle = Some(selected),
+                        _ => {}
+                    }
+                }
+                OptionNode::Format { format, format_span, .. } => {
+                    codecs.format = Some(format.clone());
+                    codecs.format_span = Some(format_span.clone());
+                }
+                _ => {}
+            }
+        }
+        codecs
     }
     
-    // Phase 3: Add informational diagnostic with pipeline visualization (if valid command)
-    if !command.inputs.is_empty() && !command.outputs.is_empty() {
-        let pipeline_diagram = generate_pipeline_diagram(&command, &tracker, &db);
+    fn video_codec(&self) -> Option<&str> {
+        self.video.as_ref().map(|(codec, _)| codec.as_str())
+    }
+}
+
+/// Run a check over every output with the codecs it selects
+fn for_each_output(
+    context: &RuleContext,
+    check: impl Fn(&OutputSpec, &OutputCodecs) -> Vec<DiagnosticMessage>,
+) -> Vec<DiagnosticMessage> {
+    context
+        .command
+        .outputs
+        .iter()
+        .flat_map(|output| check(output, &OutputCodecs::of(output, context.db)))
+        .collect()
+}
+
+/// Output codecs must be encoders of the stream type they are given for, and `-f` a known format
+pub(crate) fn check_output_codecs(context: &RuleContext) -> Vec<DiagnosticMessage> {
+    let (tracker, locale) = (context.tracker, context.locale);
+    let codec = |codec: &str, stream_type: StreamType, span: &SourceCodeSpan| {
+        tracker.validate_codec(codec, &stream_type, span, locale).or_else(|| tracker.validate_encoder(codec, span, locale))
+    };
+    context
+        .command
+        .outputs
+        .iter()
+        .flat_map(|output| output.options.iter())
+        .filter_map(|option| match option {
+            OptionNode::VideoCodec { codec: name, codec_span, .. } => codec(name, StreamType::Video, codec_span),
+            OptionNode::AudioCodec { codec: name, codec_span, .. } => codec(name, StreamType::Audio, codec_span),
+            OptionNode::SubtitleCodec { codec: name, codec_span, .. } => codec(name, StreamType::Subtitle, codec_span),
+            OptionNode::Codec { codec: name, codec_span, .. } if name != STREAM_COPY => codec(name, StreamType::Unknown, codec_span),
+            OptionNode::Format { format, format_span, .. } => validate_format_name(format, format_span, context.db, locale),
+            _ => None,
+        })
+        .collect()
+}
+
+/// A video, audio or subtitle codec needs a stream of that type to encode
+pub(crate) fn check_codec_streams(context: &RuleContext) -> Vec<DiagnosticMessage> {
+    let (tracker, locale) = (context.tracker, context.locale);
+    let options = context.command.outputs.iter().flat_map(|output| output.options.iter());
+    let mut diagnostics = Vec::new();
+    for option in options {
+        let (codec, codec_span, stream_type) = match option {
+            OptionNode::VideoCodec { codec, codec_span, .. } => (codec, codec_span, StreamType::Video),
+            OptionNode::AudioCodec { codec, codec_span, .. } => (codec, codec_span, StreamType::Audio),
+            OptionNode::SubtitleCodec { codec, codec_span, .. } => (codec, codec_span, StreamType::Subtitle),
+            _ => continue,
+        };
+        if codec == STREAM_COPY || tracker.has_stream_type(&stream_type) {
+            continue;
+        }
+        let (code, operation, message, span_message) = match stream_type {
+            StreamType::Video => ("E104", "video encoding", "video-codec-without-video", "span-codec-requires-video"),
+            StreamType::Audio => ("E105", "audio encoding", "audio-codec-without-audio", "span-codec-requires-audio"),
+            _ => ("E106", "subtitle encoding", "subtitle-codec-without-subtitles", "span-codec-requires-subtitles"),
+        };
+        // Subtitles are often meant to be burned in rather than encoded
+        let rich = match stream_type {
+            StreamType::Subtitle => build_rich_content(vec![RichBlock::MarkdownGfm { markdown: explain_subtitle_modes(locale) }]),
+            _ => None,
+        };
         diagnostics.push(DiagnosticMessage {
-            code: "I001".to_string(),
-            severity: Severity::Hint,
-            kind: DiagnosticKind::ParseError { 
-                message: "Pipeline overview".to_string() 
+            code: code.to_string(),
+            severity: Severity::Error,
+            kind: DiagnosticKind::MissingStream { stream_type, operation: operation.to_string() },
+            message: locale.text(message),
+            spans: vec![DiagnosticSpan { span: codec_span.clone(), role: SpanRole::Target, message: locale.text(span_message) }],
+            rich,
+        });
+    }
+    diagnostics
+}
+
+/// `-vf` and `-af` filters must exist and take the stream type they are applied to
+pub(crate) fn check_output_filters(context: &RuleContext) -> Vec<DiagnosticMessage> {
+    let (tracker, db, locale) = (context.tracker, context.db, context.locale);
+    let mut diagnostics = Vec::new();
+    for option in context.command.outputs.iter().flat_map(|output| output.options.iter()) {
+        match option {
+            OptionNode::VideoFilter { filter, span } => {
+                // Parse filter name from raw filter string
+                let filter_name = extract_filter_name(&filter.raw);
+                if let Some(mut diag) = tracker.validate_filter(&filter_name, &StreamType::Video, span, locale) {
+                    // Attach a sample Mermaid diagram for type mismatch errors
+                    if matches!(diag.kind, DiagnosticKind::StreamTypeMismatch{..}) {
+                        diag.rich = Some(DiagnosticRich { blocks: vec![
+                            RichBlock::MarkdownGfm { markdown: Message::new("rich-filter-expects-video").arg("filter", &filter_name).render(locale) },
+                            RichBlock::Mermaid { mermaid: "graph TD; in_audio([audio]) --x--> vf_scale[scale]; vf_scale --x--> out([video])".to_string() }
+                        ]});
+                    } else if !matches!(filter_name.as_str(), "subtitles" | "ass") {
+                        // Keep the burn-in vs soft subtitle explanation
+                        diag.rich = None;
+                    }
+                    diagnostics.push(suggest_filter(diag, filter, &filter_name, &StreamType::Video, db, locale));
+                }
+            }
+            OptionNode::AudioFilter { filter, span } => {
+                let filter_name = extract_filter_name(&filter.raw);
+                if let Some(diag) = tracker.validate_filter(&filter_name, &StreamType::Audio, span, locale) {
+                    diagnostics.push(suggest_filter(diag, filter, &filter_name, &StreamType::Audio, db, locale));
+                }
+            }
+            _ => {}
+        }
+    }
+    diagnostics
+}
+
+/// `-s` and `-r` values
+pub(crate) fn check_frame_parameters(context: &RuleContext) -> Vec<DiagnosticMessage> {
+    let locale = context.locale;
+    context
+        .command
+        .outputs
+        .iter()
+        .flat_map(|output| output.options.iter())
+        .filter_map(|option| match option {
+            OptionNode::Resolution { resolution, resolution_span, .. } => validate_resolution(resolution, resolution_span, locale),
+            OptionNode::FrameRate { rate, rate_span, .. } => validate_framerate(rate, rate_span, locale),
+            _ => None,
+        })
+        .collect()
+}
+
+/// `-map` must select streams the inputs or filter graphs provide
+pub(crate) fn check_output_mapping(context: &RuleContext) -> Vec<DiagnosticMessage> {
+    context
+        .command
+        .outputs
+        .iter()
+        .flat_map(|output| output.options.iter())
+        .filter_map(|option| match option {
+            OptionNode::Map { mapping, mapping_span, .. } => validate_mapping(mapping, mapping_span, context.tracker, context.locale),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The container must store the selected codecs, and the subtitle and copied audio streams
+/// that reach the output
+pub(crate) fn check_container_codecs(context: &RuleContext) -> Vec<DiagnosticMessage> {
+    let (tracker, db, locale) = (context.tracker, context.db, context.locale);
+    for_each_output(context, |output, codecs| {
+        let mut diagnostics = Vec::new();
+        let Some(format) = &codecs.format else { return diagnostics };
+        for (codec, codec_span) in [&codecs.video, &codecs.audio, &codecs.subtitle].into_iter().flatten() {
+            if let Some(mut diag) = tracker.validate_codec_format_compatibility(codec, format, codec_span, &output.file_path_span, locale) {
+                diag.spans.extend(codec_format_suggestions(codec, codec_span, format, codecs.format_span.as_ref(), output, db, locale));
+                diagnostics.push(diag);
+            }
+        }
+        
+        if !output.options.iter().any(|o| o.name() == "-sn") {
+            diagnostics.extend(validate_subtitle_streams(
+                output,
+                format,
+                codecs.subtitle.as_ref().map(|(codec, span)| (codec.as_str(), span)),
+                codecs.copy_all.as_ref(),
+                tracker,
+                db,
+                locale,
+            ));
+        }
+        if !output.options.iter().any(|o| o.name() == "-an") {
+            diagnostics.extend(validate_copied_audio(
+                format,
+                codecs.audio.as_ref().map(|(codec, span)| (codec.as_str(), span)),
+                codecs.copy_all.as_ref(),
+                tracker,
+                db,
+                locale,
+            ));
+        }
+        diagnostics
+    })
+}
+
+/// Private options of the selected video encoder
+pub(crate) fn check_encoder_options(context: &RuleContext) -> Vec<DiagnosticMessage> {
+    for_each_output(context, |output, codecs| match &codecs.video {
+        Some((codec, codec_span)) => validate_encoder_options(output, codec, codec_span, context.db, context.locale),
+        None => Vec::new(),
+    })
+}
+
+/// `-pix_fmt` and `format=` against the video encoder and container
+pub(crate) fn check_pixel_formats(context: &RuleContext) -> Vec<DiagnosticMessage> {
+    for_each_output(context, |output, codecs| {
+        validate_pixel_formats(output, codecs.video_codec(), codecs.format.as_deref(), context.db, context.locale)
+    })
+}
+
+/// Bitrate values, and rate-control options against each other and the output frame size
+pub(crate) fn check_rate_control(context: &RuleContext) -> Vec<DiagnosticMessage> {
+    let locale = context.locale;
+    for_each_output(context, |output, codecs| {
+        let mut diagnostics: Vec<DiagnosticMessage> = output
+            .options
+            .iter()
+            .filter_map(|option| match option {
+                OptionNode::VideoBitrate { bitrate, bitrate_span, .. } => validate_bitrate(bitrate, bitrate_span, true, locale),
+                OptionNode::AudioBitrate { bitrate, bitrate_span, .. } => validate_bitrate(bitrate, bitrate_span, false, locale),
+                _ => None,
+            })
+            .collect();
+        diagnostics.extend(validate_rate_control(output, codecs.video_codec(), context.tracker, context.db, locale));
+        diagnostics
+    })
+}
+
+/// Output frame sizes larger than the input
+pub(crate) fn check_upscaling(context: &RuleContext) -> Vec<DiagnosticMessage> {
+    context
+        .command
+        .outputs
+        .iter()
+        .filter_map(|output| validate_upscaling(output, context.tracker, context.locale))
+        .collect()
+}
+
+/// Sample rates and channel layouts against the selected audio encoder
+pub(crate) fn check_audio_parameters(context: &RuleContext) -> Vec<DiagnosticMessage> {
+    for_each_output(context, |output, codecs| match &codecs.audio {
+        Some((codec, codec_span)) => validate_audio_parameters(output, codec, codec_span, context.db, context.locale),
+        None => Vec::new(),
+    })
+}
+
+pub(crate) fn lossy_transcoding(context: &RuleContext) -> Vec<DiagnosticMessage> {
+    let has_filter_complex = context.command.global_options.iter().any(|o| matches!(o, OptionNode::FilterComplex { .. }));
+    context
+        .command
+        .outputs
+        .iter()
+        .flat_map(|output| lossy_transcoding_hints(output, has_filter_complex, context.tracker, context.db, context.locale))
+        .collect()
+}
+
+/// Informational diagnostic with the pipeline visualization
+pub(crate) fn check_pipeline_overview(context: &RuleContext) -> Vec<DiagnosticMessage> {
+    let command = context.command;
+    if command.inputs.is_empty() || command.outputs.is_empty() {
+        return Vec::new();
+    }
+    let pipeline_diagram = generate_pipeline_diagram(command, context.tracker, context.db);
+    vec![DiagnosticMessage {
+        code: "I001".to_string(),
+        severity: Severity::Hint,
+        kind: DiagnosticKind::ParseError { 
+            message: "Pipeline overview".to_string() 
+        },
+        message: context.locale.text("pipeline-overview"),
+        spans: vec![DiagnosticSpan {
+            span: command.span.clone(),
+            role: SpanRole::Target,
+            message: context.locale.text("span-entire-command"),
+        }],
+        rich: build_rich_content(vec![
+            RichBlock::MarkdownGfm {
+                markdown: context.locale.text("rich-pipeline-overview"),
+            },
+            RichBlock::Mermaid {
+                mermaid: pipeline_diagram,
+            },
+        ]),
+    }]
+}
+
+/// Fixes for a codec the output container cannot store: an encoder of the same stream type
+/// the container takes, or a container that takes the codec
+fn codec_format_suggestions(
+    codec: &str,
+    codec_span: &SourceCodeSpan,
+    format: &str,
+    format_span: Option<&SourceCodeSpan>,
+    output: &OutputSpec,
+    db: &CodecDatabase,
+    locale: Locale,
+) -> Vec<DiagnosticSpan> {
+    let mut spans = Vec::new();
+    if let Some(codec_info) = db.get_codec(codec) {
+        spans.extend(db.encoders_for_format(format, &codec_info.stream_type).into_iter().take(2).map(|encoder| DiagnosticSpan {
+            span: codec_span.clone(),
+            role: SpanRole::Suggestion { replacement: encoder.to_string() },
+            message: Message::new("fix-encode-with").arg("encoder", encoder).render(locale),
+        }));
+    }
+    
+    // The most specific containers first (webm before matroska for vp9)
+    let mut containers: Vec<&FormatInfo> = db
+        .get_compatible_formats(codec)
+        .iter()
+        .filter_map(|ext| db.get_format_by_extension(ext))
+        .collect();
+    containers.sort_by_key(|f| {
+        let codec_count = f.supported_video_codecs.len() + f.supported_audio_codecs.len() + f.supported_subtitle_codecs.len();
+        (codec_count, f.name.clone())
+    });
+    for container in containers.into_iter().take(2) {
+        let Some(extension) = container.extensions.first() else { continue };
+        // Change `-f`, or the extension of an unquoted output path
+        let (span, replacement) = match format_span {
+            Some(span) => (span.clone(), container.name.clone()),
+            None => {
+                let path_span = &output.file_path_span;
+                let unquoted = path_span.start_line == path_span.end_line
+                    && path_span.end_column - path_span.start_column == output.file_path.len();
+                let Some((_, current)) = output.file_path.rsplit_once('.').filter(|_| unquoted) else { continue };
+                let span = SourceCodeSpan { start_column: path_span.end_column - current.len(), ..path_span.clone() };
+                (span, extension.clone())
+            }
+        };
+        spans.push(DiagnosticSpan {
+            span,
+            role: SpanRole::Suggestion { replacement },
+            message: Message::new("fix-write-container").arg("format", &container.name).render(locale),
+        });
+    }
+    spans
+}
+
+/// Empty span just after a span, where a suggestion inserts text
+pub(crate) fn end_of(span: &SourceCodeSpan) -> SourceCodeSpan {
+    SourceCodeSpan { start_line: span.end_line, start_column: span.end_column, ..span.clone() }
+}
+
+/// Report input audio streams copied into a container that cannot store their codec (E213)
+fn validate_copied_audio(
+    format: &str,
+    audio_codec: Option<(&str, &SourceCodeSpan)>,
+    copy_all: Option<&SourceCodeSpan>,
+    tracker: &StreamTracker,
+    db: &CodecDatabase,
+    locale: Locale,
+) -> Vec<DiagnosticMessage> {
+    // `-c:a copy` (or `-c copy` without `-c:a`) keeps the input codec
+    let copy_span = match audio_codec {
+        Some((STREAM_COPY, span)) => span,
+        None => match copy_all {
+            Some(span) => span,
+            None => return Vec::new(),
+        },
+        _ => return Vec::new(),
+    };
+    let encoder = db.encoders_for_format(format, &StreamType::Audio).first().copied();
+    
+    let mut diagnostics = Vec::new();
+    for stream in tracker.get_streams_of_type(&StreamType::Audio) {
+        let Some(input_codec) = stream.codec.as_deref() else { continue };
+        if db.get_codec(input_codec).is_none() || db.is_codec_supported_in_format(input_codec, format) {
+            continue;
+        }
+        let mut spans = vec![
+            DiagnosticSpan { span: copy_span.clone(), role: SpanRole::Target, message: locale.text("span-stream-copy") },
+            DiagnosticSpan {
+                span: tracker.input_file_spans[stream.input_index].clone(),
+                role: SpanRole::Reference,
+                message: Message::new("span-codec-audio").arg("codec", input_codec).render(locale),
             },
-            message: "FFmpeg pipeline flow".to_string(),
-            spans: vec![DiagnosticSpan {
-                span: command.span.clone(),
-                role: SpanRole::Target,
-                message: "entire command".to_string(),
-            }],
-            rich: build_rich_content(vec![
-                RichBlock::MarkdownGfm {
-                    markdown: "## Pipeline Overview\n\nThis diagram shows how data flows through your FFmpeg command.".to_string(),
+        ];
+        // Replace `-c:a copy`, or encode only the audio after `-c copy`
+        if let Some(encoder) = encoder {
+            spans.push(match audio_codec {
+                Some((_, span)) => DiagnosticSpan {
+                    span: span.clone(),
+                    role: SpanRole::Suggestion { replacement: encoder.to_string() },
+                    message: Message::new("fix-encode-with").arg("encoder", encoder).render(locale),
                 },
-                RichBlock::Mermaid {
-                    mermaid: pipeline_diagram,
+                None => DiagnosticSpan {
+                    span: end_of(copy_span),
+                    role: SpanRole::Suggestion { replacement: format!(" -c:a {}", encoder) },
+                    message: Message::new("fix-encode-with").arg("encoder", encoder).render(locale),
                 },
-            ]),
+            });
+        }
+        diagnostics.push(DiagnosticMessage {
+            code: "E213".to_string(),
+            severity: Severity::Error,
+            kind: DiagnosticKind::CodecFormatIncompatible {
+                codec: input_codec.to_string(),
+                format: format.to_string(),
+                reason: format!("'{}' cannot store {} audio", format, input_codec),
+            },
+            message: match encoder {
+                Some(encoder) => Message::new("audio-copy-unsupported").arg("codec", input_codec).arg("format", format).arg("encoder", encoder),
+                None => Message::new("audio-copy-unsupported-drop").arg("codec", input_codec).arg("format", format),
+            }
+            .render(locale),
+            spans,
+            rich: None,
         });
     }
+    diagnostics
+}
+
+/// Report input subtitle streams that cannot reach the output: text/bitmap conversions and
+/// stream copies into a container that cannot store the subtitle codec
+fn validate_subtitle_streams(
+    output: &OutputSpec,
+    format: &str,
+    subtitle_codec: Option<(&str, &SourceCodeSpan)>,
+    copy_all: Option<&SourceCodeSpan>,
+    tracker: &StreamTracker,
+    db: &CodecDatabase,
+    locale: Locale,
+) -> Vec<DiagnosticMessage> {
+    let mut diagnostics = Vec::new();
+    let default_codec = db.default_subtitle_codec(format);
     
-    AnalyzerDiagnostics { messages: diagnostics }
+    for stream in tracker.get_streams_of_type(&StreamType::Subtitle) {
+        let Some(input_codec) = stream.codec.as_deref() else { continue };
+        let input_span = &tracker.input_file_spans[stream.input_index];
+        
+        // `-c:s copy` (or `-c copy` without `-c:s`) keeps the input codec
+        let copy_span = match subtitle_codec {
+            Some((STREAM_COPY, span)) => Some(span),
+            None => copy_all,
+            _ => None,
+        };
+        if let Some(copy_span) = copy_span {
+            if db.is_codec_supported_in_format(input_codec, format) {
+                continue;
+            }
+            let mut spans = vec![
+                DiagnosticSpan { span: copy_span.clone(), role: SpanRole::Target, message: locale.text("span-stream-copy") },
+                DiagnosticSpan { span: input_span.clone(), role: SpanRole::Reference, message: Message::new("span-codec-subtitles").arg("codec", input_codec).render(locale) },
+            ];
+            // Replace `-c:s copy`, or convert only the subtitles after `-c copy`
+            if let Some(replacement) = default_codec {
+                spans.push(match subtitle_codec {
+                    Some((_, span)) => DiagnosticSpan {
+                        span: span.clone(),
+                        role: SpanRole::Suggestion { replacement: replacement.to_string() },
+                        message: Message::new("fix-convert-to").arg("codec", replacement).render(locale),
+                    },
+                    None => DiagnosticSpan {
+                        span: end_of(copy_span),
+                        role: SpanRole::Suggestion { replacement: format!(" -c:s {}", replacement) },
+                        message: Message::new("fix-convert-to").arg("codec", replacement).render(locale),
+                    },
+                });
+            }
+            diagnostics.push(DiagnosticMessage {
+                code: "E207".to_string(),
+                severity: Severity::Error,
+                kind: DiagnosticKind::CodecFormatIncompatible {
+                    codec: input_codec.to_string(),
+                    format: format.to_string(),
+                    reason: format!("'{}' cannot store {} subtitles", format, input_codec),
+                },
+                message: match default_codec {
+                    Some(codec) => Message::new("subtitle-copy-unsupported").arg("codec", input_codec).arg("format", format).arg("encoder", codec),
+                    None => Message::new("subtitle-copy-unsupported-drop").arg("codec", input_codec).arg("format", format),
+                }
+                .render(locale),
+                spans,
+                rich: build_rich_content(vec![RichBlock::MarkdownGfm { markdown: explain_subtitle_modes(locale) }]),
+            });
+            continue;
+        }
+        
+        // Subtitle encoders only convert text to text and bitmap to bitmap
+        let (target_codec, target_span) = match subtitle_codec {
+            Some((codec, span)) => (codec, span),
+            None => match default_codec {
+                Some(codec) => (codec, &output.file_path_span),
+                None => continue,
+            },
+        };
+        let input_is_bitmap = BITMAP_SUBTITLE_CODECS.contains(&input_codec);
+        let output_is_bitmap = BITMAP_SUBTITLE_CODECS.contains(&target_codec);
+        if input_is_bitmap != output_is_bitmap && db.get_codec(target_codec).is_some() {
+            let kind_name = |bitmap: bool| Message::new(if bitmap { "subtitle-kind-bitmap" } else { "subtitle-kind-text" });
+            diagnostics.push(DiagnosticMessage {
+                code: "E208".to_string(),
+                severity: Severity::Error,
+                kind: DiagnosticKind::InvalidParameter {
+                    option: "-c:s".to_string(),
+                    value: target_codec.to_string(),
+                    reason: "subtitles can only be converted text to text or bitmap to bitmap".to_string(),
+                },
+                message: Message::new("subtitle-conversion")
+                    .arg("codec", input_codec)
+                    .arg("kind", kind_name(input_is_bitmap))
+                    .arg("target", target_codec)
+                    .arg("target_kind", kind_name(output_is_bitmap))
+                    .render(locale),
+                spans: vec![
+                    DiagnosticSpan { span: target_span.clone(), role: SpanRole::Target, message: Message::new("span-codec-encoder").arg("codec", target_codec).render(locale) },
+                    DiagnosticSpan { span: input_span.clone(), role: SpanRole::Reference, message: Message::new("span-codec-subtitles").arg("codec", input_codec).render(locale) },
+                ],
+                rich: None,
+            });
+        }
+    }
+    
+    diagnostics
 }
 
-fn analyze_output(
+/// Hint at avoidable quality loss: re-encoding a lossy stream into the same codec, chaining
+/// lossy audio codecs, and compressing a lossless source at low quality
+fn lossy_transcoding_hints(
     output: &OutputSpec,
+    has_filter_complex: bool,
     tracker: &StreamTracker,
     db: &CodecDatabase,
+    locale: Locale,
 ) -> Vec<DiagnosticMessage> {
     let mut diagnostics = Vec::new();
+    let format = output
+        .options
+        .iter()
+        .find_map(|o| match o {
+            OptionNode::Format { format, .. } => Some(format.clone()),
+            _ => None,
+        })
+        .or_else(|| db.infer_format_from_filename(&output.file_path));
     
-    // Infer output format from filename
-    let output_format = db.infer_format_from_filename(&output.file_path);
+    for stream_type in [StreamType::Video, StreamType::Audio] {
+        let is_video = stream_type == StreamType::Video;
+        let Some((codec, codec_span)) = output.options.iter().find_map(|o| match o {
+            OptionNode::VideoCodec { codec, codec_span, .. } if is_video => Some((codec, codec_span)),
+            OptionNode::AudioCodec { codec, codec_span, .. } if !is_video => Some((codec, codec_span)),
+            OptionNode::Codec { codec, codec_span, .. }
+                if db.get_codec(codec).is_some_and(|c| c.stream_type == stream_type) => Some((codec, codec_span)),
+            _ => None,
+        }) else { continue };
+        if codec == STREAM_COPY {
+            continue;
+        }
+        
+        // Only reason about the source when it is unambiguous
+        let sources = tracker.get_streams_of_type(&stream_type);
+        let [source] = sources.as_slice() else { continue };
+        let Some(source_codec) = source.codec.as_deref() else { continue };
+        let source_span = &tracker.input_file_spans[source.input_index];
+        
+        let has_filters = has_filter_complex
+            || output.options.iter().any(|o| match o {
+                OptionNode::VideoFilter { .. } | OptionNode::Resolution { .. } | OptionNode::FrameRate { .. }
+                | OptionNode::PixelFormat { .. } => is_video,
+                OptionNode::AudioFilter { .. } | OptionNode::SampleRate { .. } | OptionNode::AudioChannels { .. } => !is_video,
+                OptionNode::Generic { name, .. } => !is_video && name == "-channel_layout",
+                _ => false,
+            });
+        let audio_bitrate = output.options.iter().find_map(|o| match o {
+            OptionNode::AudioBitrate { bitrate, bitrate_span, .. } => Some((bitrate, bitrate_span)),
+            _ => None,
+        });
+        let crf = output.options.iter().find_map(|o| match o {
+            OptionNode::Generic { name, value: Some(value), value_span: Some(value_span), .. }
+                if video_option_name(name) == Some("crf") => Some((value, value_span)),
+            _ => None,
+        });
+        let source_lossless = db.is_lossless_codec(source_codec);
+        let target_lossless = db.is_lossless_codec(codec);
+        let same_codec = db.codec_identity(source_codec) == db.codec_identity(codec);
+        let hint = |code: &str, message: Message, fix: DiagnosticSpan| DiagnosticMessage {
+            code: code.to_string(),
+            severity: Severity::Hint,
+            kind: DiagnosticKind::LossyTranscoding { message: message.render(Locale::English) },
+            message: message.render(locale),
+            spans: vec![
+                DiagnosticSpan { span: codec_span.clone(), role: SpanRole::Target, message: locale.text("span-re-encode") },
+                DiagnosticSpan { span: source_span.clone(), role: SpanRole::Reference, message: Message::new("span-source").arg("source", source_codec).render(locale) },
+                fix,
+            ],
+            rich: None,
+        };
+        
+        if !source_lossless && same_codec && !has_filters {
+            diagnostics.push(hint(
+                "W103",
+                Message::new("same-codec-re-encode").arg("source", source_codec).arg("codec", codec),
+                DiagnosticSpan { span: codec_span.clone(), role: SpanRole::Suggestion { replacement: STREAM_COPY.to_string() }, message: locale.text("span-stream-copy") },
+            ));
+        } else if !is_video && !source_lossless && !target_lossless && !same_codec {
+            let can_copy = !has_filters && format.as_deref().is_some_and(|f| db.is_codec_supported_in_format(source_codec, f));
+            let fix = if can_copy {
+                DiagnosticSpan { span: codec_span.clone(), role: SpanRole::Suggestion { replacement: STREAM_COPY.to_string() }, message: locale.text("span-stream-copy") }
+            } else if audio_bitrate.is_none() {
+                DiagnosticSpan { span: end_of(codec_span), role: SpanRole::Suggestion { replacement: " -b:a 192k".to_string() }, message: locale.text("fix-generous-bitrate") }
+            } else {
+                continue;
+            };
+            diagnostics.push(hint(
+                "W104",
+                Message::new("lossy-audio-transcode").arg("source", source_codec).arg("codec", codec),
+                fix,
+            ));
+        } else if source_lossless && !target_lossless {
+            let low_bitrate = audio_bitrate
+                .filter(|_| !is_video)
+                .filter(|(bitrate, _)| Bitrate::parse(bitrate).is_some_and(|b| b.bits_per_second() < 128_000.0))
+                .map(|(_, span)| DiagnosticSpan { span: span.clone(), role: SpanRole::Suggestion { replacement: "192k".to_string() }, message: locale.text("fix-raise-bitrate") });
+            // A CRF in the top 40% of the encoder's range is low quality
+            let crf_range = db.get_encoder_options(codec).and_then(|e| e.get_option("crf")).and_then(|o| match o.values {
+                OptionValues::Range { min, max } => Some((min, max)),
+                _ => None,
+            });
+            let high_crf = crf
+                .filter(|_| is_video)
+                .zip(crf_range)
+                .filter(|((value, _), (min, max))| value.parse::<f64>().is_ok_and(|v| v > min + (max - min) * 0.6))
+                .map(|((_, span), (min, max))| DiagnosticSpan {
+                    span: span.clone(),
+                    role: SpanRole::Suggestion { replacement: ((min + max) / 2.0).floor().to_string() },
+                    message: locale.text("fix-lower-crf"),
+                });
+            if let Some(fix) = low_bitrate.or(high_crf) {
+                diagnostics.push(hint(
+                    "W105",
+                    Message::new("lossless-low-quality").arg("source", source_codec).arg("codec", codec),
+                    fix,
+                ));
+            }
+        }
+    }
     
-    let mut video_codec = None;
-    let mut audio_codec = None;
-    let mut explicit_format = output_format.clone();
+    diagnostics
+}
+
+/// Strip the leading dash and a video stream specifier (`-profile:v` -> `profile`).
+/// Returns None for options targeting non-video streams.
+fn video_option_name(name: &str) -> Option<&str> {
+    let name = name.trim_start_matches('-');
+    match name.split_once(':') {
+        Some((base, "v")) | Some((base, "v:0")) => Some(base),
+        Some(_) => None,
+        None => Some(name),
+    }
+}
+
+/// Validate encoder private options (crf, preset, tune, profile, ...) for a video encoder
+fn validate_encoder_options(
+    output: &OutputSpec,
+    codec: &str,
+    codec_span: &SourceCodeSpan,
+    db: &CodecDatabase,
+    locale: Locale,
+) -> Vec<DiagnosticMessage> {
+    let mut diagnostics = Vec::new();
+    let mut has_quality_setting = false;
+    let mut profile_value = None;
+    let mut pix_fmt_value = None;
+    let mut private_options = Vec::new();
     
-    // Collect codec and format information
     for option in &output.options {
         match option {
-            OptionNode::VideoCodec { codec, codec_span, .. } => {
-                video_codec = Some((codec.clone(), codec_span.clone()));
-                
-                // Validate that codec is actually a video codec
-                if let Some(diag) = tracker.validate_codec(codec, &StreamType::Video, codec_span) {
-                    diagnostics.push(diag);
+            OptionNode::VideoBitrate { .. } => has_quality_setting = true,
+            OptionNode::PixelFormat { format, format_span, .. } => {
+                pix_fmt_value = Some((format.clone(), format_span.clone()));
+            }
+            OptionNode::Generic { name, value, value_span, span } => {
+                let Some(base) = video_option_name(name) else { continue };
+                if matches!(base, "crf" | "qp" | "cq" | "q" | "qscale" | "global_quality") {
+                    has_quality_setting = true;
+                }
+                if KNOWN_ENCODER_OPTIONS.contains(&base) {
+                    let value_span = value_span.clone().unwrap_or_else(|| span.clone());
+                    private_options.push((base, name, value.clone(), value_span));
                 }
-                
-                // Check if we have video streams available
-                if !tracker.has_stream_type(&StreamType::Video) && codec != "copy" {
+            }
+            _ => {}
+        }
+    }
+    
+    if codec == STREAM_COPY {
+        for (_, name, _, value_span) in &private_options {
+            diagnostics.push(DiagnosticMessage {
+                code: "W401".to_string(),
+                severity: Severity::Warning,
+                kind: DiagnosticKind::InvalidParameter {
+                    option: name.to_string(),
+                    value: String::new(),
+                    reason: "stream copy does not encode".to_string(),
+                },
+                message: Message::new("option-ignored-with-copy").arg("option", *name).render(locale),
+                spans: vec![
+                    DiagnosticSpan { span: value_span.clone(), role: SpanRole::Target, message: locale.text("span-ignored-option") },
+                    DiagnosticSpan { span: codec_span.clone(), role: SpanRole::Reference, message: locale.text("span-stream-copy") },
+                ],
+                rich: None,
+            });
+        }
+        return diagnostics;
+    }
+    
+    let Some(encoder) = db.get_encoder_options(codec) else {
+        return diagnostics;
+    };
+    
+    for (base, name, value, value_span) in &private_options {
+        let Some(option_info) = encoder.get_option(base) else {
+            if GENERIC_ENCODER_OPTIONS.contains(base) {
+                continue;
+            }
+            diagnostics.push(DiagnosticMessage {
+                code: "W401".to_string(),
+                severity: Severity::Warning,
+                kind: DiagnosticKind::InvalidParameter {
+                    option: name.to_string(),
+                    value: value.clone().unwrap_or_default(),
+                    reason: format!("not an option of encoder '{}'", codec),
+                },
+                message: Message::new("option-unsupported-by-encoder").arg("encoder", codec).arg("option", *name).render(locale),
+                spans: vec![
+                    DiagnosticSpan { span: value_span.clone(), role: SpanRole::Target, message: locale.text("span-unsupported-option") },
+                    DiagnosticSpan { span: codec_span.clone(), role: SpanRole::Reference, message: Message::new("span-encoder").arg("encoder", codec).render(locale) },
+                ],
+                rich: None,
+            });
+            continue;
+        };
+        let Some(value) = value else { continue };
+        
+        match &option_info.values {
+            OptionValues::Range { min, max } => match value.parse::<f64>() {
+                Ok(number) if number < *min || number > *max => {
                     diagnostics.push(DiagnosticMessage {
-                        code: "E104".to_string(),
+                        code: "E404".to_string(),
                         severity: Severity::Error,
-                        kind: DiagnosticKind::MissingStream {
-                            stream_type: StreamType::Video,
-                            operation: "video encoding".to_string(),
+                        kind: DiagnosticKind::ParameterOutOfRange {
+                            option: name.to_string(),
+                            value: value.clone(),
+                            min: min.to_string(),
+                            max: max.to_string(),
                         },
-                        message: "Video codec specified but no video stream available in inputs".to_string(),
-                        spans: vec![DiagnosticSpan { span: codec_span.clone(), role: SpanRole::Target, message: "filter requires video".to_string() }],
+                        message: Message::new("option-out-of-range")
+                            .arg("value", value)
+                            .arg("option", *name)
+                            .arg("encoder", codec)
+                            .arg("min", *min)
+                            .arg("max", *max)
+                            .render(locale),
+                        spans: vec![DiagnosticSpan {
+                            span: value_span.clone(),
+                            role: SpanRole::Target,
+                            message: Message::new("span-expected-range").arg("min", *min).arg("max", *max).render(locale),
+                        }],
                         rich: None,
                     });
                 }
-            }
-            
-            OptionNode::AudioCodec { codec, codec_span, .. } => {
-                audio_codec = Some((codec.clone(), codec_span.clone()));
-                
-                // Validate that codec is actually an audio codec
-                if let Some(diag) = tracker.validate_codec(codec, &StreamType::Audio, codec_span) {
-                    diagnostics.push(diag);
+                Ok(_) => {}
+                Err(_) => {
+                    diagnostics.push(DiagnosticMessage {
+                        code: "E405".to_string(),
+                        severity: Severity::Error,
+                        kind: DiagnosticKind::InvalidParameter {
+                            option: name.to_string(),
+                            value: value.clone(),
+                            reason: "expected a number".to_string(),
+                        },
+                        message: Message::new("option-not-a-number").arg("value", value).arg("option", *name).render(locale),
+                        spans: vec![DiagnosticSpan { span: value_span.clone(), role: SpanRole::Target, message: locale.text("span-expected-number") }],
+                        rich: None,
+                    });
                 }
-                
-                // Check if we have audio streams available
-                if !tracker.has_stream_type(&StreamType::Audio) && codec != "copy" {
+            },
+            OptionValues::OneOf(accepted) => {
+                if !accepted.contains(value) {
                     diagnostics.push(DiagnosticMessage {
-                        code: "E105".to_string(),
+                        code: "E405".to_string(),
                         severity: Severity::Error,
-                        kind: DiagnosticKind::MissingStream {
-                            stream_type: StreamType::Audio,
-                            operation: "audio encoding".to_string(),
+                        kind: DiagnosticKind::InvalidParameter {
+                            option: name.to_string(),
+                            value: value.clone(),
+                            reason: format!("accepted values: {}", accepted.join(", ")),
                         },
-                        message: "Audio codec specified but no audio stream available in inputs".to_string(),
-                        spans: vec![DiagnosticSpan { span: codec_span.clone(), role: SpanRole::Target, message: "codec requires audio".to_string() }],
+                        message: Message::new("option-invalid-value")
+                            .arg("value", value)
+                            .arg("option", *name)
+                            .arg("encoder", codec)
+                            .arg("accepted", accepted.join(", "))
+                            .render(locale),
+                        spans: vec![DiagnosticSpan { span: value_span.clone(), role: SpanRole::Target, message: Message::new("span-invalid-option").arg("option", *base).render(locale) }],
                         rich: None,
                     });
+                } else if *base == "profile" {
+                    profile_value = Some((value.clone(), value_span.clone()));
                 }
             }
-            
-            OptionNode::Codec { codec, codec_span, .. } => {
-                // Generic codec - could be video or audio, check both
-                if let Some(codec_info) = db.get_codec(codec) {
-                    match codec_info.stream_type {
-                        StreamType::Video => {
-                            video_codec = Some((codec.clone(), codec_span.clone()));
-                        }
-                        StreamType::Audio => {
-                            audio_codec = Some((codec.clone(), codec_span.clone()));
-                        }
-                        _ => {}
+        }
+    }
+    
+    // Profile restricts bit depth and chroma subsampling
+    if let (Some((profile, profile_span)), Some((pix_fmt, pix_fmt_span))) = (&profile_value, &pix_fmt_value) {
+        if let (Some(profile_info), Some(pix_fmt_info)) =
+            (encoder.get_profile(profile), db.get_pixel_format(pix_fmt))
+        {
+            let bit_depth = pix_fmt_info.bit_depth;
+            let chroma = &pix_fmt_info.chroma;
+            if bit_depth > profile_info.max_bit_depth || !profile_info.chroma.contains(chroma) {
+                diagnostics.push(DiagnosticMessage {
+                    code: "E405".to_string(),
+                    severity: Severity::Error,
+                    kind: DiagnosticKind::InvalidParameter {
+                        option: "-profile:v".to_string(),
+                        value: profile.clone(),
+                        reason: format!("profile does not support pixel format '{}'", pix_fmt),
+                    },
+                    message: Message::new("profile-pixel-format")
+                        .arg("profile", profile)
+                        .arg("encoder", codec)
+                        .arg("format", pix_fmt)
+                        .arg("depth", bit_depth)
+                        .arg("chroma", chroma)
+                        .render(locale),
+                    spans: vec![
+                        DiagnosticSpan { span: profile_span.clone(), role: SpanRole::Target, message: locale.text("span-profile") },
+                        DiagnosticSpan { span: pix_fmt_span.clone(), role: SpanRole::Reference, message: locale.text("span-pixel-format") },
+                    ],
+                    rich: None,
+                });
+            }
+        }
+    }
+    
+    if encoder.is_lossy && !has_quality_setting {
+        diagnostics.push(DiagnosticMessage {
+            code: "W102".to_string(),
+            severity: Severity::Hint,
+            kind: DiagnosticKind::NoQualitySetting {
+                codec: codec.to_string(),
+            },
+            message: Message::new("no-quality-setting").arg("encoder", codec).render(locale),
+            spans: vec![DiagnosticSpan { span: codec_span.clone(), role: SpanRole::Target, message: locale.text("span-add-quality-setting") }],
+            rich: None,
+        });
+    }
+    
+    diagnostics
+}
+
+/// Validate `-pix_fmt` and `format=` filters against the selected video encoder
+fn validate_pixel_formats(
+    output: &OutputSpec,
+    codec: Option<&str>,
+    output_format: Option<&str>,
+    db: &CodecDatabase,
+    locale: Locale,
+) -> Vec<DiagnosticMessage> {
+    let mut diagnostics = Vec::new();
+    let codec = codec.filter(|c| *c != STREAM_COPY);
+    let accepted = codec.and_then(|c| db.get_encoder_pixel_formats(c));
+    // Pixel format reaching the encoder: -pix_fmt wins over the last format= filter
+    let mut effective: Option<(String, SourceCodeSpan)> = None;
+    let mut explicit = false;
+    
+    for option in &output.options {
+        match option {
+            OptionNode::PixelFormat { format, format_span, .. } => {
+                if db.get_pixel_format(format).is_none() {
+                    diagnostics.push(unknown_pixel_format(format, "-pix_fmt", format_span, locale));
+                    continue;
+                }
+                if let (Some(codec), Some(accepted)) = (codec, accepted) {
+                    if !accepted.contains(format) {
+                        diagnostics.push(unsupported_pixel_format(format, codec, accepted, format_span, locale));
                     }
                 }
+                effective = Some((format.clone(), format_span.clone()));
+                explicit = true;
             }
-            
-            OptionNode::Format { format, format_span: _format_span, .. } => {
-                explicit_format = Some(format.clone());
-            }
-            
-            OptionNode::VideoFilter { filter, span } => {
-                // Parse filter name from raw filter string
-                let filter_name = extract_filter_name(&filter.raw);
-                if let Some(mut diag) = tracker.validate_filter(&filter_name, &StreamType::Video, span) {
-                    // Attach a sample Mermaid diagram for type mismatch errors
-                    if matches!(diag.kind, DiagnosticKind::StreamTypeMismatch{..}) {
-                        diag.rich = Some(DiagnosticRich { blocks: vec![
-                            RichBlock::MarkdownGfm { markdown: format!("Filter '{}' expects video input.", filter_name) },
-                            RichBlock::Mermaid { mermaid: "graph TD; in_audio([audio]) --x--> vf_scale[scale]; vf_scale --x--> out([video])".to_string() }
-                        ]});
-                    } else {
-                        diag.rich = None;
+            OptionNode::VideoFilter { filter, .. } => {
+                for format_filter in filter.filters().filter(|f| f.name == "format") {
+                    let Some(param) = format_filter.param("pix_fmts", 0) else { continue };
+                    let formats: Vec<&str> = param.value.split('|').collect();
+                    if let Some(unknown) = formats.iter().find(|f| db.get_pixel_format(f).is_none()) {
+                        diagnostics.push(unknown_pixel_format(unknown, "format", &format_filter.span, locale));
+                        continue;
+                    }
+                    if let (Some(codec), Some(accepted)) = (codec, accepted) {
+                        if !formats.iter().any(|f| accepted.iter().any(|a| a == f)) {
+                            diagnostics.push(unsupported_pixel_format(&param.value, codec, accepted, &param.span, locale));
+                        }
+                    }
+                    if !explicit {
+                        effective = Some((formats[0].to_string(), param.span.clone()));
                     }
-                    diagnostics.push(diag);
                 }
             }
-            
-            OptionNode::AudioFilter { filter, span } => {
-                let filter_name = extract_filter_name(&filter.raw);
-                if let Some(diag) = tracker.validate_filter(&filter_name, &StreamType::Audio, span) {
-                    diagnostics.push(diag);
-                }
+            _ => {}
+        }
+    }
+    
+    // H.264/HEVC outside 8-bit 4:2:0 does not play in common browsers. Only outputs meant for
+    // the web are reported: webm, or mp4 with the index moved up front by `-movflags +faststart`
+    let faststart = output.options.iter().find_map(|o| match o {
+        OptionNode::Generic { name, value: Some(flags), span, .. } if name == "-movflags" && flags.contains("faststart") => Some(span),
+        _ => None,
+    });
+    let web_output = match output_format {
+        Some("webm") => Some(&output.file_path_span),
+        Some("mp4") => faststart,
+        _ => None,
+    };
+    let is_avc_or_hevc = matches!(codec, Some("libx264" | "libx265" | "h264" | "hevc"));
+    if let (Some(web_span), true, Some((pix_fmt, pix_fmt_span))) = (web_output, is_avc_or_hevc, &effective) {
+        if let Some(info) = db.get_pixel_format(pix_fmt) {
+            if info.bit_depth > 8 || info.chroma != "420" {
+                diagnostics.push(DiagnosticMessage {
+                    code: "W203".to_string(),
+                    severity: Severity::Warning,
+                    kind: DiagnosticKind::UnsupportedPixelFormat {
+                        format: pix_fmt.clone(),
+                        codec: codec.unwrap_or_default().to_string(),
+                    },
+                    message: Message::new("pixel-format-not-web")
+                        .arg("format", pix_fmt)
+                        .arg("depth", info.bit_depth)
+                        .arg("chroma", &info.chroma)
+                        .render(locale),
+                    spans: vec![
+                        DiagnosticSpan { span: pix_fmt_span.clone(), role: SpanRole::Target, message: locale.text("span-not-web-compatible") },
+                        DiagnosticSpan { span: web_span.clone(), role: SpanRole::Reference, message: locale.text("span-web-output") },
+                    ],
+                    rich: None,
+                });
             }
-            
-            OptionNode::Resolution { resolution, resolution_span, .. } => {
-                if let Some(diag) = validate_resolution(resolution, resolution_span) {
-                    diagnostics.push(diag);
-                }
-                
-                // Check for upscaling (would need input resolution info)
-                // TODO: Implement resolution tracking and upscaling detection
+        }
+    }
+    
+    diagnostics
+}
+
+/// W402 for `-pix_fmt` or a `format=` filter; `option` names which
+fn unknown_pixel_format(format: &str, option: &str, span: &SourceCodeSpan, locale: Locale) -> DiagnosticMessage {
+    DiagnosticMessage {
+        code: "W402".to_string(),
+        severity: Severity::Warning,
+        kind: DiagnosticKind::InvalidParameter {
+            option: option.to_string(),
+            value: format.to_string(),
+            reason: "unknown pixel format".to_string(),
+        },
+        message: Message::new("unknown-pixel-format").arg("format", format).render(locale),
+        spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-unknown-pixel-format") }],
+        rich: None,
+    }
+}
+
+fn unsupported_pixel_format(
+    format: &str,
+    codec: &str,
+    accepted: &[String],
+    span: &SourceCodeSpan,
+    locale: Locale,
+) -> DiagnosticMessage {
+    DiagnosticMessage {
+        code: "W202".to_string(),
+        severity: Severity::Warning,
+        kind: DiagnosticKind::UnsupportedPixelFormat {
+            format: format.to_string(),
+            codec: codec.to_string(),
+        },
+        message: Message::new("unsupported-pixel-format").arg("encoder", codec).arg("format", format).render(locale),
+        spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-unsupported-pixel-format") }],
+        rich: build_rich_content(vec![RichBlock::MarkdownGfm {
+            markdown: Message::new("rich-supported-pixel-formats")
+                .arg("encoder", codec)
+                .arg("formats", accepted.iter().map(|f| format!("- `{}`", f)).collect::<Vec<_>>().join("\n"))
+                .render(locale),
+        }]),
+    }
+}
+
+/// Validate `-ar`, `-ac`, `-channel_layout`, `aresample` and `pan` against an audio encoder
+fn validate_audio_parameters(
+    output: &OutputSpec,
+    codec: &str,
+    codec_span: &SourceCodeSpan,
+    db: &CodecDatabase,
+    locale: Locale,
+) -> Vec<DiagnosticMessage> {
+    let mut diagnostics = Vec::new();
+    let Some(constraints) = db.get_audio_constraints(codec) else {
+        return diagnostics;
+    };
+    
+    for option in &output.options {
+        match option {
+            OptionNode::SampleRate { rate, rate_span, .. } => {
+                diagnostics.extend(check_sample_rate(rate, rate_span, constraints, codec_span, locale));
             }
-            
-            OptionNode::VideoBitrate { bitrate, bitrate_span, .. } => {
-                if let Some(diag) = validate_bitrate(bitrate, bitrate_span, true) {
-                    diagnostics.push(diag);
+            OptionNode::AudioChannels { channels, channels_span, .. } => {
+                if let Ok(count) = channels.parse::<u32>() {
+                    diagnostics.extend(check_channel_count(count, "-ac", channels, channels_span, constraints, codec_span, locale));
                 }
             }
-            
-            OptionNode::AudioBitrate { bitrate, bitrate_span, .. } => {
-                if let Some(diag) = validate_bitrate(bitrate, bitrate_span, false) {
-                    diagnostics.push(diag);
+            OptionNode::Generic { name, value: Some(layout), value_span: Some(value_span), .. }
+                if name == "-channel_layout" || name == "-ch_layout" =>
+            {
+                if let Some(count) = db.channel_layout_channels(layout) {
+                    diagnostics.extend(check_channel_count(count, name, layout, value_span, constraints, codec_span, locale));
                 }
             }
-            
-            OptionNode::FrameRate { rate, rate_span, .. } => {
-                if let Some(diag) = validate_framerate(rate, rate_span) {
-                    diagnostics.push(diag);
+            OptionNode::AudioFilter { filter, .. } => {
+                for audio_filter in filter.filters() {
+                    match audio_filter.name.as_str() {
+                        "aresample" => {
+                            let rate = audio_filter
+                                .param("osr", 0)
+                                .or_else(|| audio_filter.param("out_sample_rate", 0));
+                            if let Some(rate) = rate {
+                                diagnostics.extend(check_sample_rate(&rate.value, &rate.span, constraints, codec_span, locale));
+                            }
+                        }
+                        "pan" => {
+                            let Some(args) = audio_filter.param("args", 0) else { continue };
+                            let layout = args.value.split('|').next().unwrap_or_default();
+                            if let Some(count) = db.channel_layout_channels(layout) {
+                                diagnostics.extend(check_channel_count(count, "pan", layout, &args.span, constraints, codec_span, locale));
+                            }
+                        }
+                        _ => {}
+                    }
                 }
             }
-            
-            OptionNode::Map { mapping, mapping_span, .. } => {
-                // Validate stream mapping
-                if let Some(diag) = validate_mapping(mapping, mapping_span, tracker) {
-                    diagnostics.push(diag);
+            _ => {}
+        }
+    }
+    
+    diagnostics
+}
+
+fn check_sample_rate(
+    rate: &str,
+    rate_span: &SourceCodeSpan,
+    constraints: &AudioEncoderConstraints,
+    codec_span: &SourceCodeSpan,
+    locale: Locale,
+) -> Option<DiagnosticMessage> {
+    let rate_hz = rate.parse::<u32>().ok()?;
+    if constraints.supports_sample_rate(rate_hz) {
+        return None;
+    }
+    
+    let mut spans = vec![
+        DiagnosticSpan { span: rate_span.clone(), role: SpanRole::Target, message: locale.text("span-unsupported-sample-rate") },
+        DiagnosticSpan {
+            span: codec_span.clone(),
+            role: SpanRole::Reference,
+            message: Message::new("span-encoder").arg("encoder", &constraints.encoder).render(locale),
+        },
+    ];
+    if let Some(nearest) = constraints.nearest_sample_rate(rate_hz) {
+        spans.push(DiagnosticSpan {
+            span: rate_span.clone(),
+            role: SpanRole::Suggestion { replacement: nearest.to_string() },
+            message: Message::new("fix-use-sample-rate").arg("rate", nearest.to_string()).render(locale),
+        });
+    }
+    
+    Some(DiagnosticMessage {
+        code: "E203".to_string(),
+        severity: Severity::Error,
+        kind: DiagnosticKind::UnsupportedSampleRate {
+            rate: rate.to_string(),
+            codec: constraints.encoder.clone(),
+        },
+        message: Message::new("unsupported-sample-rate").arg("encoder", &constraints.encoder).arg("rate", rate).render(locale),
+        spans,
+        rich: build_rich_content(vec![RichBlock::MarkdownGfm {
+            markdown: Message::new("rich-supported-sample-rates")
+                .arg("encoder", &constraints.encoder)
+                .arg(
+                    "rates",
+                    if constraints.sample_rates.is_empty() {
+                        Message::new("rich-any-sample-rate")
+                            .arg("max", constraints.max_sample_rate.unwrap_or_default().to_string())
+                            .render(locale)
+                    } else {
+                        constraints.sample_rates.iter().map(|r| format!("- {} Hz", r)).collect::<Vec<_>>().join("\n")
+                    },
+                )
+                .render(locale),
+        }]),
+    })
+}
+
+fn check_channel_count(
+    count: u32,
+    option: &str,
+    value: &str,
+    value_span: &SourceCodeSpan,
+    constraints: &AudioEncoderConstraints,
+    codec_span: &SourceCodeSpan,
+    locale: Locale,
+) -> Option<DiagnosticMessage> {
+    if count <= constraints.max_channels {
+        return None;
+    }
+    
+    Some(DiagnosticMessage {
+        code: "E206".to_string(),
+        severity: Severity::Error,
+        kind: DiagnosticKind::ParameterOutOfRange {
+            option: option.to_string(),
+            value: value.to_string(),
+            min: "1".to_string(),
+            max: constraints.max_channels.to_string(),
+        },
+        message: Message::new("too-many-channels")
+            .arg("encoder", &constraints.encoder)
+            .arg("max", constraints.max_channels)
+            .arg("value", value)
+            .arg("count", count)
+            .render(locale),
+        spans: vec![
+            DiagnosticSpan { span: value_span.clone(), role: SpanRole::Target, message: Message::new("span-channels").arg("count", count).render(locale) },
+            DiagnosticSpan {
+                span: codec_span.clone(),
+                role: SpanRole::Reference,
+                message: Message::new("span-encoder").arg("encoder", &constraints.encoder).render(locale),
+            },
+        ],
+        rich: None,
+    })
+}
+
+/// Check `-ss`, `-t`, `-to` and `-sseof` on a single input or output
+fn validate_trim_options(options: &[OptionNode], is_input: bool, locale: Locale) -> Vec<DiagnosticMessage> {
+    let mut diagnostics = Vec::new();
+    let mut start = None;
+    let mut end = None;
+    
+    for option in options {
+        match option {
+            OptionNode::SeekStart { time, time_span, .. } => start = Some((time, time_span)),
+            OptionNode::SeekEnd { time, time_span, .. } => end = Some((time, time_span)),
+            OptionNode::Duration { time, time_span, .. } if parse_time_seconds(time).is_some_and(|secs| secs <= 0.0) => {
+                diagnostics.push(DiagnosticMessage {
+                    code: "E411".to_string(),
+                    severity: Severity::Error,
+                    kind: DiagnosticKind::InvalidParameter {
+                        option: "-t".to_string(),
+                        value: time.clone(),
+                        reason: "duration must be positive".to_string(),
+                    },
+                    message: Message::new("duration-not-positive").arg("time", time).render(locale),
+                    spans: vec![DiagnosticSpan { span: time_span.clone(), role: SpanRole::Target, message: locale.text("span-non-positive-duration") }],
+                    rich: None,
+                });
+            }
+            OptionNode::SeekFromEnd { time, time_span, span } => {
+                if !is_input {
+                    diagnostics.push(DiagnosticMessage {
+                        code: "E412".to_string(),
+                        severity: Severity::Error,
+                        kind: DiagnosticKind::MissingRequiredOption {
+                            option: "-i".to_string(),
+                            context: "-sseof".to_string(),
+                        },
+                        message: locale.text("sseof-on-output"),
+                        spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-not-followed-by-input") }],
+                        rich: None,
+                    });
+                } else if parse_time_seconds(time).is_some_and(|secs| secs > 0.0) {
+                    diagnostics.push(DiagnosticMessage {
+                        code: "E405".to_string(),
+                        severity: Severity::Error,
+                        kind: DiagnosticKind::InvalidParameter {
+                            option: "-sseof".to_string(),
+                            value: time.clone(),
+                            reason: "must be negative (relative to end of file)".to_string(),
+                        },
+                        message: Message::new("sseof-not-negative").arg("time", time).render(locale),
+                        spans: vec![DiagnosticSpan { span: time_span.clone(), role: SpanRole::Target, message: locale.text("span-must-be-negative") }],
+                        rich: None,
+                    });
                 }
             }
-            
             _ => {}
         }
     }
     
-    // Phase 3: Check codec/format compatibility
-    if let Some(format) = &explicit_format {
-        if let Some((codec, codec_span)) = &video_codec {
-            if let Some(diag) = tracker.validate_codec_format_compatibility(
-                codec,
-                format,
-                codec_span,
-                &output.file_path_span,
-            ) {
-                diagnostics.push(diag);
-            }
+    if let (Some((start_time, start_span)), Some((end_time, end_span))) = (start, end) {
+        if let (Some(start_secs), Some(end_secs)) = (parse_time_seconds(start_time), parse_time_seconds(end_time)) {
+            if start_secs >= end_secs {
+                diagnostics.push(DiagnosticMessage {
+                    code: "E410".to_string(),
+                    severity: Severity::Error,
+                    kind: DiagnosticKind::InvalidParameter {
+                        option: "-ss".to_string(),
+                        value: start_time.clone(),
+                        reason: format!("start is not before end ({})", end_time),
+                    },
+                    message: Message::new("start-after-end").arg("start", start_time).arg("end", end_time).render(locale),
+                    spans: vec![
+                        DiagnosticSpan { span: start_span.clone(), role: SpanRole::Target, message: locale.text("span-start") },
+                        DiagnosticSpan { span: end_span.clone(), role: SpanRole::Reference, message: locale.text("span-end") },
+                    ],
+                    rich: None,
+                });
+            }
+        }
+    }
+    
+    diagnostics
+}
+
+/// Warn when an input-side `-ss` seeks past the known end of the input
+fn validate_input_seek(
+    options: &[OptionNode],
+    input_duration: Option<f64>,
+    file_path_span: &SourceCodeSpan,
+    locale: Locale,
+) -> Option<DiagnosticMessage> {
+    let input_duration = input_duration?;
+    options.iter().find_map(|option| {
+        let OptionNode::SeekStart { time, time_span, .. } = option else { return None };
+        let start = parse_time_seconds(time)?;
+        (start >= input_duration).then(|| DiagnosticMessage {
+            code: "W411".to_string(),
+            severity: Severity::Warning,
+            kind: DiagnosticKind::ParameterOutOfRange {
+                option: "-ss".to_string(),
+                value: time.clone(),
+                min: "0".to_string(),
+                max: input_duration.to_string(),
+            },
+            message: Message::new("seek-past-end").arg("time", time).arg("duration", input_duration).render(locale),
+            spans: vec![
+                DiagnosticSpan { span: time_span.clone(), role: SpanRole::Target, message: locale.text("span-past-end-of-input") },
+                DiagnosticSpan {
+                    span: file_path_span.clone(),
+                    role: SpanRole::Reference,
+                    message: Message::new("span-seconds-long").arg("duration", input_duration).render(locale),
+                },
+            ],
+            rich: None,
+        })
+    })
+}
+
+/// Explain keyframe snapping when seeking is combined with stream copy
+pub(crate) fn seek_precision_hints(command: &FfmpegCommand, locale: Locale) -> Vec<DiagnosticMessage> {
+    let mut diagnostics = Vec::new();
+    let input_seeks: Vec<(&String, &SourceCodeSpan)> = command
+        .inputs
+        .iter()
+        .flat_map(|input| input.options.iter())
+        .filter_map(|option| match option {
+            OptionNode::SeekStart { time, time_span, .. } => Some((time, time_span)),
+            _ => None,
+        })
+        .collect();
+    
+    for output in &command.outputs {
+        let copy = output.options.iter().find_map(|option| match option {
+            OptionNode::Codec { codec, codec_span, .. } if codec == STREAM_COPY => Some(("-c", codec_span)),
+            OptionNode::VideoCodec { codec, codec_span, .. } if codec == STREAM_COPY => Some(("-c:v", codec_span)),
+            _ => None,
+        });
+        let Some(copy) = copy else { continue };
+        
+        for &seek in &input_seeks {
+            diagnostics.push(seek_precision_hint(seek, copy, "input", locale));
+        }
+        for option in &output.options {
+            if let OptionNode::SeekStart { time, time_span, .. } = option {
+                diagnostics.push(seek_precision_hint((time, time_span), copy, "output", locale));
+            }
+        }
+    }
+    
+    diagnostics
+}
+
+/// I002 for a `-ss` on the `position` ("input" or "output") side of a stream-copied output
+fn seek_precision_hint(
+    (seek, seek_span): (&String, &SourceCodeSpan),
+    (copy_option, copy_span): (&str, &SourceCodeSpan),
+    position: &str,
+    locale: Locale,
+) -> DiagnosticMessage {
+    DiagnosticMessage {
+        code: "I002".to_string(),
+        severity: Severity::Info,
+        kind: DiagnosticKind::SeekWithStreamCopy {
+            seek: seek.clone(),
+            position: position.to_string(),
+            copy_option: copy_option.to_string(),
+        },
+        message: locale.text(if position == "input" { "input-seek-with-copy" } else { "output-seek-with-copy" }),
+        spans: vec![
+            DiagnosticSpan { span: seek_span.clone(), role: SpanRole::Target, message: locale.text("span-seek") },
+            DiagnosticSpan { span: copy_span.clone(), role: SpanRole::Reference, message: locale.text("span-copy-snaps-to-keyframes") },
+        ],
+        rich: build_rich_content(vec![RichBlock::MarkdownGfm { markdown: locale.text("rich-seek-with-copy") }]),
+    }
+}
+
+/// Report outputs that write to an input file or to the same path as another output
+pub(crate) fn validate_output_paths(command: &FfmpegCommand, locale: Locale) -> Vec<DiagnosticMessage> {
+    let mut diagnostics = Vec::new();
+    let never_overwrite = command
+        .global_options
+        .iter()
+        .any(|o| matches!(o, OptionNode::Generic { name, .. } if name == "-n"));
+    // With -n ffmpeg exits instead of overwriting, so nothing is destroyed
+    let severity = || if never_overwrite { Severity::Warning } else { Severity::Error };
+    
+    let inputs: Vec<_> = command
+        .inputs
+        .iter()
+        .filter_map(|input| Some((normalize_path(&input.file_path)?, input)))
+        .collect();
+    let mut seen_outputs: Vec<(String, &OutputSpec)> = Vec::new();
+    
+    for output in &command.outputs {
+        let Some(path) = normalize_path(&output.file_path) else { continue };
+        
+        if let Some((_, input)) = inputs.iter().find(|(input_path, _)| *input_path == path) {
+            diagnostics.push(DiagnosticMessage {
+                code: "E601".to_string(),
+                severity: severity(),
+                kind: DiagnosticKind::OutputOverwritesInput { path: path.clone() },
+                message: Message::new("output-overwrites-input").arg("output", &output.file_path).arg("input", &input.file_path).render(locale),
+                spans: vec![
+                    DiagnosticSpan { span: output.file_path_span.clone(), role: SpanRole::Target, message: locale.text("span-output") },
+                    DiagnosticSpan { span: input.file_path_span.clone(), role: SpanRole::Reference, message: locale.text("span-same-file-as-input") },
+                ],
+                rich: None,
+            });
+        } else if let Some((_, earlier)) = seen_outputs.iter().find(|(earlier_path, _)| *earlier_path == path) {
+            diagnostics.push(DiagnosticMessage {
+                code: "E602".to_string(),
+                severity: severity(),
+                kind: DiagnosticKind::DuplicateOutput { path: path.clone() },
+                message: Message::new("duplicate-output").arg("output", &output.file_path).render(locale),
+                spans: vec![
+                    DiagnosticSpan { span: output.file_path_span.clone(), role: SpanRole::Target, message: locale.text("span-duplicate-output") },
+                    DiagnosticSpan { span: earlier.file_path_span.clone(), role: SpanRole::Reference, message: locale.text("span-first-written-here") },
+                ],
+                rich: None,
+            });
+        }
+        
+        seen_outputs.push((path, output));
+    }
+    
+    diagnostics
+}
+
+/// Check frame rate and pattern options on image sequence inputs, and numbered patterns on image outputs
+pub(crate) fn validate_image_sequences(command: &FfmpegCommand, locale: Locale) -> Vec<DiagnosticMessage> {
+    let mut diagnostics = Vec::new();
+    let generic = |options: &[OptionNode], names: &[&str]| {
+        options.iter().find_map(|option| match option {
+            OptionNode::Generic { name, value, .. } if names.contains(&name.as_str()) => Some(value.clone().unwrap_or_default()),
+            _ => None,
+        })
+    };
+    
+    for input in &command.inputs {
+        let kind = classify_path(&input.file_path);
+        if !matches!(kind, PathKind::ImageSequence | PathKind::Glob) {
+            continue;
         }
         
-        if let Some((codec, codec_span)) = &audio_codec {
-            if let Some(diag) = tracker.validate_codec_format_compatibility(
-                codec,
-                format,
-                codec_span,
-                &output.file_path_span,
-            ) {
-                diagnostics.push(diag);
-            }
+        if kind == PathKind::Glob && generic(&input.options, &["-pattern_type"]).as_deref() != Some("glob") {
+            diagnostics.push(DiagnosticMessage {
+                code: "W604".to_string(),
+                severity: Severity::Warning,
+                kind: DiagnosticKind::MissingRequiredOption {
+                    option: "-pattern_type glob".to_string(),
+                    context: "wildcard input".to_string(),
+                },
+                message: Message::new("glob-without-pattern-type").arg("input", &input.file_path).render(locale),
+                spans: vec![
+                    DiagnosticSpan { span: input.file_path_span.clone(), role: SpanRole::Target, message: locale.text("span-wildcard-pattern") },
+                ],
+                rich: None,
+            });
+        }
+        
+        if generic(&input.options, &["-framerate"]).is_some() {
+            continue;
+        }
+        let input_rate = input.options.iter().find_map(|option| match option {
+            OptionNode::FrameRate { rate, span, .. } => Some((rate, span)),
+            _ => None,
+        });
+        diagnostics.push(match input_rate {
+            Some((rate, span)) => DiagnosticMessage {
+                code: "W602".to_string(),
+                severity: Severity::Warning,
+                kind: DiagnosticKind::MissingRequiredOption {
+                    option: "-framerate".to_string(),
+                    context: "image sequence input".to_string(),
+                },
+                message: locale.text("image-sequence-input-rate"),
+                spans: vec![
+                    DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-input-frame-rate") },
+                    DiagnosticSpan {
+                        span: span.clone(),
+                        role: SpanRole::Suggestion { replacement: format!("-framerate {}", rate) },
+                        message: locale.text("fix-use-framerate"),
+                    },
+                ],
+                rich: None,
+            },
+            None => DiagnosticMessage {
+                code: "W603".to_string(),
+                severity: Severity::Hint,
+                kind: DiagnosticKind::MissingRequiredOption {
+                    option: "-framerate".to_string(),
+                    context: "image sequence input".to_string(),
+                },
+                message: Message::new("image-sequence-default-rate").arg("input", &input.file_path).render(locale),
+                spans: vec![
+                    DiagnosticSpan { span: input.file_path_span.clone(), role: SpanRole::Target, message: locale.text("span-add-framerate") },
+                ],
+                rich: None,
+            },
+        });
+    }
+    
+    // A single-image output is fine when every input is a single image too
+    let single_frame_inputs = command.inputs.iter().all(|input| classify_path(&input.file_path) == PathKind::Image);
+    for output in &command.outputs {
+        if classify_path(&output.file_path) != PathKind::Image || single_frame_inputs {
+            continue;
+        }
+        let single_frame = generic(&output.options, &["-frames:v", "-vframes", "-frames"]).as_deref() == Some("1")
+            || generic(&output.options, &["-update"]).as_deref() == Some("1");
+        if single_frame {
+            continue;
         }
+        
+        diagnostics.push(DiagnosticMessage {
+            code: "W601".to_string(),
+            severity: Severity::Warning,
+            kind: DiagnosticKind::ImageSequenceWithoutPattern { path: output.file_path.clone() },
+            message: Message::new("image-output-without-pattern").arg("output", &output.file_path).render(locale),
+            spans: vec![
+                DiagnosticSpan { span: output.file_path_span.clone(), role: SpanRole::Target, message: locale.text("span-missing-pattern") },
+            ],
+            rich: None,
+        });
     }
     
     diagnostics
 }
 
+/// Add the filters of the stream type a misspelled filter may mean to its E502 diagnostic
+fn suggest_filter(
+    mut diag: DiagnosticMessage,
+    filter: &FilterSpec,
+    filter_name: &str,
+    stream_type: &StreamType,
+    db: &CodecDatabase,
+    locale: Locale,
+) -> DiagnosticMessage {
+    if !matches!(diag.kind, DiagnosticKind::UnknownFilter { .. }) {
+        return diag;
+    }
+    let Some(name_span) = filter.filters().find(|f| f.name == filter_name).map(|f| f.name_span.clone()) else { return diag };
+    let (spans, hint) = did_you_mean(&filter_suggestions(db, filter_name, stream_type), &name_span, locale);
+    diag.message.push_str(&hint);
+    diag.spans.extend(spans);
+    diag
+}
+
+/// Report a `-f` value that is not a known format but close to one (W206)
+fn validate_format_name(format: &str, span: &SourceCodeSpan, db: &CodecDatabase, locale: Locale) -> Option<DiagnosticMessage> {
+    let suggestions = format_suggestions(db, format);
+    if suggestions.is_empty() {
+        return None;
+    }
+    let (mut spans, hint) = did_you_mean(&suggestions, span, locale);
+    spans.insert(0, DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-unknown-format") });
+    Some(DiagnosticMessage {
+        code: "W206".to_string(),
+        severity: Severity::Warning,
+        kind: DiagnosticKind::UnknownFormat { format: format.to_string() },
+        message: Message::new("unknown-output-format").arg("format", format).arg("hint", hint).render(locale),
+        spans,
+        rich: None,
+    })
+}
+
 fn extract_filter_name(filter_str: &str) -> String {
     // Extract first filter name from filter string (before '=' or ',')
     filter_str
@@ -233,18 +1666,28 @@ fn extract_filter_name(filter_str: &str) -> String {
         .to_string()
 }
 
-fn validate_resolution(resolution: &str, span: &SourceCodeSpan) -> Option<DiagnosticMessage> {
+fn validate_resolution(resolution: &str, span: &SourceCodeSpan, locale: Locale) -> Option<DiagnosticMessage> {
     // Check format: NxM where N and M are numbers
     let parts: Vec<&str> = resolution.split('x').collect();
     if parts.len() != 2 {
+        let mut spans = vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-invalid-resolution-format") }];
+        // A lone width: complete it with the 16:9 height, rounded to an even number
+        if let Ok(width) = resolution.parse::<u32>() {
+            let height = (width as f64 * 9.0 / 32.0).round() as u32 * 2;
+            spans.push(DiagnosticSpan {
+                span: span.clone(),
+                role: SpanRole::Suggestion { replacement: format!("{}x{}", width, height) },
+                message: locale.text("fix-16-9-frame-size"),
+            });
+        }
         return Some(DiagnosticMessage {
             code: "E401".to_string(),
             severity: Severity::Error,
             kind: DiagnosticKind::InvalidResolution {
                 value: resolution.to_string(),
             },
-            message: format!("Invalid resolution format '{}' (expected format: WIDTHxHEIGHT)", resolution),
-            spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: "invalid resolution format".to_string() }],
+            message: Message::new("invalid-resolution-format").arg("value", resolution).render(locale),
+            spans,
             rich: None,
         });
     }
@@ -257,8 +1700,68 @@ fn validate_resolution(resolution: &str, span: &SourceCodeSpan) -> Option<Diagno
             kind: DiagnosticKind::InvalidResolution {
                 value: resolution.to_string(),
             },
-            message: format!("Invalid resolution '{}' (width and height must be numbers)", resolution),
-            spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: "width/height must be numbers".to_string() }],
+            message: Message::new("invalid-resolution-numbers").arg("value", resolution).render(locale),
+            spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-resolution-numbers") }],
+            rich: None,
+        });
+    }
+    
+    None
+}
+
+fn validate_bitrate(bitrate: &str, span: &SourceCodeSpan, is_video: bool, locale: Locale) -> Option<DiagnosticMessage> {
+    let Some(value) = Bitrate::parse(bitrate) else {
+        return Some(DiagnosticMessage {
+            code: "E402".to_string(),
+            severity: Severity::Error,
+            kind: DiagnosticKind::InvalidBitrate {
+                value: bitrate.to_string(),
+            },
+            message: Message::new("invalid-bitrate").arg("value", bitrate).render(locale),
+            spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-invalid-bitrate") }],
+            rich: None,
+        });
+    };
+    let bps = value.bits_per_second();
+    
+    // 50 Mbit/s for video, 500 kbit/s for audio
+    let high_threshold = if is_video { 50_000_000.0 } else { 500_000.0 };
+    if bps > high_threshold {
+        return Some(DiagnosticMessage {
+            code: "W101".to_string(),
+            severity: Severity::Warning,
+            kind: DiagnosticKind::HighBitrateWarning {
+                bitrate: bitrate.to_string(),
+            },
+            message: Message::new("high-bitrate").arg("value", bitrate).render(locale),
+            spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-high-bitrate") }],
+            rich: None,
+        });
+    }
+    
+    // Usually a missing unit: `-b:a 128` is 128 bit/s, not 128 kbit/s. A bitrate of 0 is not
+    // low but unset, as in `-crf 30 -b:v 0` for constant quality with libvpx-vp9 and libaom
+    let low_threshold = if is_video { 10_000.0 } else { 8_000.0 };
+    if bps > 0.0 && bps < low_threshold {
+        let mut spans = vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: Message::new("span-bits-per-second").arg("bps", bps).render(locale) }];
+        if bitrate.chars().all(|c| c.is_ascii_digit() || c == '.') {
+            spans.push(DiagnosticSpan {
+                span: span.clone(),
+                role: SpanRole::Suggestion { replacement: format!("{}k", bitrate) },
+                message: locale.text("fix-add-k-suffix"),
+            });
+        }
+        return Some(DiagnosticMessage {
+            code: "W106".to_string(),
+            severity: Severity::Warning,
+            kind: DiagnosticKind::ParameterOutOfRange {
+                option: if is_video { "-b:v" } else { "-b:a" }.to_string(),
+                value: bitrate.to_string(),
+                min: Bitrate(low_threshold).to_string(),
+                max: Bitrate(high_threshold).to_string(),
+            },
+            message: Message::new("low-bitrate").arg("value", bitrate).arg("bps", bps).render(locale),
+            spans,
             rich: None,
         });
     }
@@ -266,43 +1769,259 @@ fn validate_resolution(resolution: &str, span: &SourceCodeSpan) -> Option<Diagno
     None
 }
 
-fn validate_bitrate(bitrate: &str, span: &SourceCodeSpan, is_video: bool) -> Option<DiagnosticMessage> {
-    // Extract numeric part
-    let numeric_part = bitrate.trim_end_matches(|c: char| c.is_alphabetic());
+/// Check `-b:v`, `-minrate`, `-maxrate` and `-bufsize` against each other, and the target
+/// bitrate against the output resolution, frame rate and codec
+fn validate_rate_control(
+    output: &OutputSpec,
+    codec: Option<&str>,
+    tracker: &StreamTracker,
+    db: &CodecDatabase,
+    locale: Locale,
+) -> Vec<DiagnosticMessage> {
+    let mut diagnostics = Vec::new();
+    
+    let mut bitrate = None;
+    let mut rates: HashMap<&str, (Bitrate, &OptionNode)> = HashMap::new();
+    for option in &output.options {
+        match option {
+            OptionNode::VideoBitrate { bitrate: value, bitrate_span, .. } => {
+                bitrate = Bitrate::parse(value).map(|b| (b, bitrate_span));
+            }
+            OptionNode::Generic { name, value: Some(value), value_span: Some(value_span), .. } => {
+                let Some(base @ ("maxrate" | "minrate" | "bufsize")) = video_option_name(name) else { continue };
+                match Bitrate::parse(value) {
+                    Some(rate) => {
+                        rates.insert(base, (rate, option));
+                    }
+                    None => diagnostics.push(DiagnosticMessage {
+                        code: "E402".to_string(),
+                        severity: Severity::Error,
+                        kind: DiagnosticKind::InvalidBitrate { value: value.clone() },
+                        message: Message::new("invalid-option-bitrate").arg("value", value).arg("option", name).render(locale),
+                        spans: vec![DiagnosticSpan { span: value_span.clone(), role: SpanRole::Target, message: locale.text("span-invalid-bitrate") }],
+                        rich: None,
+                    }),
+                }
+            }
+            _ => {}
+        }
+    }
+    let value_span = |option: &OptionNode| match option {
+        OptionNode::Generic { value_span: Some(span), .. } => span.clone(),
+        other => other.span().clone(),
+    };
     
-    if let Ok(value) = numeric_part.parse::<u32>() {
-        // Check for extremely high bitrates (warning)
-        let threshold = if is_video { 50000 } else { 500 }; // 50Mbps for video, 500kbps for audio
+    if let Some((maxrate, max_option)) = rates.get("maxrate") {
+        if !rates.contains_key("bufsize") {
+            let bufsize = Bitrate(maxrate.bits_per_second() * 2.0);
+            diagnostics.push(DiagnosticMessage {
+                code: "W413".to_string(),
+                severity: Severity::Warning,
+                kind: DiagnosticKind::MissingRequiredOption {
+                    option: "-bufsize".to_string(),
+                    context: "-maxrate".to_string(),
+                },
+                message: locale.text("maxrate-without-bufsize"),
+                spans: vec![
+                    DiagnosticSpan { span: max_option.span().clone(), role: SpanRole::Target, message: locale.text("span-needs-bufsize") },
+                    DiagnosticSpan {
+                        span: max_option.span().clone(),
+                        role: SpanRole::Suggestion { replacement: format!("{} {} -bufsize {}", max_option.name(), maxrate, bufsize) },
+                        message: locale.text("fix-add-bufsize"),
+                    },
+                ],
+                rich: None,
+            });
+        }
         
-        if value > threshold {
-            return Some(DiagnosticMessage {
-                code: "W101".to_string(),
+        if let Some((minrate, min_option)) = rates.get("minrate").filter(|(minrate, _)| minrate > maxrate) {
+            diagnostics.push(DiagnosticMessage {
+                code: "E413".to_string(),
+                severity: Severity::Error,
+                kind: DiagnosticKind::ParameterOutOfRange {
+                    option: "-minrate".to_string(),
+                    value: minrate.to_string(),
+                    min: "0".to_string(),
+                    max: maxrate.to_string(),
+                },
+                message: Message::new("minrate-above-maxrate").arg("minrate", minrate.to_string()).arg("maxrate", maxrate.to_string()).render(locale),
+                spans: vec![
+                    DiagnosticSpan { span: value_span(min_option), role: SpanRole::Target, message: locale.text("span-above-maxrate") },
+                    DiagnosticSpan { span: value_span(max_option), role: SpanRole::Reference, message: locale.text("span-maxrate") },
+                ],
+                rich: None,
+            });
+        }
+        
+        if let Some((target, target_span)) = bitrate.filter(|(target, _)| target > maxrate) {
+            diagnostics.push(DiagnosticMessage {
+                code: "W412".to_string(),
                 severity: Severity::Warning,
-                kind: DiagnosticKind::HighBitrateWarning {
-                    bitrate: bitrate.to_string(),
+                kind: DiagnosticKind::ParameterOutOfRange {
+                    option: "-b:v".to_string(),
+                    value: target.to_string(),
+                    min: "0".to_string(),
+                    max: maxrate.to_string(),
                 },
-                message: format!("Extremely high bitrate specified: {}", bitrate),
-                spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: "high bitrate".to_string() }],
+                message: Message::new("bitrate-above-maxrate").arg("bitrate", target.to_string()).arg("maxrate", maxrate.to_string()).render(locale),
+                spans: vec![
+                    DiagnosticSpan { span: target_span.clone(), role: SpanRole::Target, message: locale.text("span-above-maxrate") },
+                    DiagnosticSpan { span: value_span(max_option), role: SpanRole::Reference, message: locale.text("span-maxrate") },
+                ],
                 rich: None,
             });
         }
-    } else {
-        return Some(DiagnosticMessage {
-            code: "E402".to_string(),
-            severity: Severity::Error,
-            kind: DiagnosticKind::InvalidBitrate {
-                value: bitrate.to_string(),
+    }
+    
+    // Bits per pixel needs the output size, which is the source size unless scaled; the frame
+    // rate defaults to 30 fps when neither the output nor the source sets it
+    let (Some((target, target_span)), Some(codec)) = (bitrate, codec) else { return diagnostics };
+    let Some((min_bpp, max_bpp)) = db.get_bits_per_pixel_range(codec) else { return diagnostics };
+    let sources = tracker.get_streams_of_type(&StreamType::Video);
+    let source = match sources.as_slice() {
+        [source] => Some(*source),
+        _ => None,
+    };
+    let source_dimensions = source.and_then(|s| Some((s.width? as f64, s.height? as f64)));
+    let Some((width, height)) = output_dimensions(output).or(source_dimensions) else { return diagnostics };
+    let fps = output_frame_rate(output).or(source.and_then(|s| s.frame_rate));
+    let bpp = target.bits_per_second() / (width * height * fps.unwrap_or(30.0));
+    let frame_desc = match fps {
+        Some(fps) => Message::new("frame-size-at-rate").arg("fps", fps),
+        None => Message::new("frame-size-at-assumed-rate"),
+    }
+    .arg("width", width)
+    .arg("height", height);
+    
+    if bpp < min_bpp {
+        let suggested = round_bitrate(min_bpp * width * height * fps.unwrap_or(30.0), f64::ceil);
+        diagnostics.push(DiagnosticMessage {
+            code: "W107".to_string(),
+            severity: Severity::Warning,
+            kind: DiagnosticKind::ParameterOutOfRange {
+                option: "-b:v".to_string(),
+                value: target.to_string(),
+                min: suggested.to_string(),
+                max: Bitrate(max_bpp * width * height * fps.unwrap_or(30.0)).to_string(),
             },
-            message: format!("Invalid bitrate format '{}'", bitrate),
-            spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: "invalid bitrate".to_string() }],
+            message: Message::new("bitrate-too-low-for-frame")
+                .arg("bitrate", target.to_string())
+                .arg("encoder", codec)
+                .arg("frame", frame_desc)
+                .render(locale),
+            spans: vec![
+                DiagnosticSpan { span: target_span.clone(), role: SpanRole::Target, message: Message::new("span-bits-per-pixel").arg("bpp", Arg::Fixed(bpp, 3)).render(locale) },
+                DiagnosticSpan {
+                    span: target_span.clone(),
+                    role: SpanRole::Suggestion { replacement: suggested.to_string() },
+                    message: locale.text("fix-minimum-for-resolution"),
+                },
+            ],
+            rich: None,
+        });
+    } else if bpp > max_bpp {
+        let suggested = round_bitrate(max_bpp * width * height * fps.unwrap_or(30.0), f64::floor);
+        diagnostics.push(DiagnosticMessage {
+            code: "W108".to_string(),
+            severity: Severity::Hint,
+            kind: DiagnosticKind::HighBitrateWarning { bitrate: target.to_string() },
+            message: Message::new("bitrate-too-high-for-frame")
+                .arg("bitrate", target.to_string())
+                .arg("encoder", codec)
+                .arg("frame", frame_desc)
+                .render(locale),
+            spans: vec![
+                DiagnosticSpan { span: target_span.clone(), role: SpanRole::Target, message: Message::new("span-bits-per-pixel").arg("bpp", Arg::Fixed(bpp, 3)).render(locale) },
+                DiagnosticSpan {
+                    span: target_span.clone(),
+                    role: SpanRole::Suggestion { replacement: suggested.to_string() },
+                    message: locale.text("fix-enough-for-resolution"),
+                },
+            ],
             rich: None,
         });
     }
     
-    None
+    diagnostics
+}
+
+/// Round a suggested bitrate to two significant digits so quick fixes read like `1.3M`
+fn round_bitrate(bps: f64, round: fn(f64) -> f64) -> Bitrate {
+    let scale = 10f64.powi(bps.log10().floor() as i32 - 1);
+    Bitrate(round(bps / scale) * scale)
+}
+
+/// Output frame size from `-s WxH` or a `scale=W:H` video filter
+fn output_dimensions(output: &OutputSpec) -> Option<(f64, f64)> {
+    output_dimensions_with_span(output).map(|(dimensions, _)| dimensions)
+}
+
+/// Output frame size and the span of the option or filter that sets it
+fn output_dimensions_with_span(output: &OutputSpec) -> Option<((f64, f64), &SourceCodeSpan)> {
+    let parse = |w: &str, h: &str| Some((w.parse::<f64>().ok()?, h.parse::<f64>().ok()?)).filter(|(w, h)| *w > 0.0 && *h > 0.0);
+    output.options.iter().rev().find_map(|option| match option {
+        OptionNode::Resolution { resolution, resolution_span, .. } => {
+            let (w, h) = resolution.split_once('x')?;
+            Some((parse(w, h)?, resolution_span))
+        }
+        OptionNode::VideoFilter { filter, .. } => filter
+            .filters()
+            .filter(|f| f.name == "scale")
+            .find_map(|f| Some((parse(&f.param("w", 0)?.value, &f.param("h", 1)?.value)?, &f.span))),
+        _ => None,
+    })
+}
+
+/// Hint when the output frame size is larger than the single video source it is made from
+fn validate_upscaling(output: &OutputSpec, tracker: &StreamTracker, locale: Locale) -> Option<DiagnosticMessage> {
+    let sources = tracker.get_streams_of_type(&StreamType::Video);
+    let [source] = sources.as_slice() else { return None };
+    let (source_width, source_height) = (source.width? as f64, source.height? as f64);
+    let ((width, height), span) = output_dimensions_with_span(output)?;
+    if width <= source_width && height <= source_height {
+        return None;
+    }
+    
+    let from_res = format!("{}x{}", source_width, source_height);
+    let to_res = format!("{}x{}", width, height);
+    Some(DiagnosticMessage {
+        code: "W109".to_string(),
+        severity: Severity::Hint,
+        kind: DiagnosticKind::ResolutionUpscaling { from_res: from_res.clone(), to_res: to_res.clone() },
+        message: Message::new("upscaling").arg("to", &to_res).arg("from", &from_res).render(locale),
+        spans: vec![
+            DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-upscales") },
+            DiagnosticSpan {
+                span: tracker.input_file_spans[source.input_index].clone(),
+                role: SpanRole::Reference,
+                message: Message::new("span-source").arg("source", &from_res).render(locale),
+            },
+        ],
+        rich: None,
+    })
 }
 
-fn validate_framerate(rate: &str, span: &SourceCodeSpan) -> Option<DiagnosticMessage> {
+/// Output frame rate from `-r` or an `fps=N` video filter
+fn output_frame_rate(output: &OutputSpec) -> Option<f64> {
+    output.options.iter().rev().find_map(|option| match option {
+        OptionNode::FrameRate { rate, .. } => parse_frame_rate(rate),
+        OptionNode::VideoFilter { filter, .. } => filter
+            .filters()
+            .filter(|f| f.name == "fps")
+            .find_map(|f| parse_frame_rate(&f.param("fps", 0)?.value)),
+        _ => None,
+    })
+}
+
+fn parse_frame_rate(rate: &str) -> Option<f64> {
+    let fps = match rate.split_once('/') {
+        Some((num, den)) => num.parse::<f64>().ok()? / den.parse::<f64>().ok()?,
+        None => rate.parse::<f64>().ok()?,
+    };
+    (fps.is_finite() && fps > 0.0).then_some(fps)
+}
+
+fn validate_framerate(rate: &str, span: &SourceCodeSpan, locale: Locale) -> Option<DiagnosticMessage> {
     if let Ok(fps) = rate.parse::<f64>() {
         if fps <= 0.0 || fps > 1000.0 {
             return Some(DiagnosticMessage {
@@ -311,8 +2030,8 @@ fn validate_framerate(rate: &str, span: &SourceCodeSpan) -> Option<DiagnosticMes
                 kind: DiagnosticKind::InvalidFrameRate {
                     value: rate.to_string(),
                 },
-                message: format!("Invalid frame rate '{}' (must be between 0 and 1000)", rate),
-                spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: "invalid frame rate".to_string() }],
+                message: Message::new("frame-rate-out-of-range").arg("value", rate).render(locale),
+                spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-invalid-frame-rate") }],
                 rich: None,
             });
         }
@@ -323,8 +2042,8 @@ fn validate_framerate(rate: &str, span: &SourceCodeSpan) -> Option<DiagnosticMes
             kind: DiagnosticKind::InvalidFrameRate {
                 value: rate.to_string(),
             },
-            message: format!("Invalid frame rate format '{}'", rate),
-            spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: "invalid frame rate format".to_string() }],
+            message: Message::new("invalid-frame-rate").arg("value", rate).render(locale),
+            spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-invalid-frame-rate-format") }],
             rich: None,
         });
     }
@@ -336,6 +2055,7 @@ fn validate_mapping(
     mapping: &str,
     span: &SourceCodeSpan,
     tracker: &StreamTracker,
+    locale: Locale,
 ) -> Option<DiagnosticMessage> {
     // Parse mapping format: [input_index]:[stream_type]:[stream_index] or [label]
     
@@ -350,8 +2070,8 @@ fn validate_mapping(
                     mapping: mapping.to_string(),
                     reason: format!("Filter output label '{}' does not exist", label),
                 },
-                message: format!("Referenced filter output '{}' does not exist", label),
-                spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: "unknown label".to_string() }],
+                message: Message::new("unknown-filter-label").arg("label", label).render(locale),
+                spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-unknown-label") }],
                 rich: None,
             });
         }
@@ -361,22 +2081,16 @@ fn validate_mapping(
         
         if let Some(input_idx_str) = parts.first() {
             if let Ok(input_idx) = input_idx_str.parse::<usize>() {
-                // Check if input exists
-                let max_input = tracker.input_streams
-                    .iter()
-                    .map(|s| s.input_index)
-                    .max()
-                    .unwrap_or(0);
-                
-                if input_idx > max_input {
+                // Check if input exists; inputs with unknown streams count too
+                if input_idx >= tracker.input_file_spans.len() {
                     return Some(DiagnosticMessage {
                         code: "E301".to_string(),
                         severity: Severity::Error,
                         kind: DiagnosticKind::NonExistentStream {
                             stream_ref: mapping.to_string(),
                         },
-                        message: format!("Input index {} does not exist", input_idx),
-                        spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: "non-existent input index".to_string() }],
+                        message: Message::new("unknown-input-index").arg("index", input_idx).render(locale),
+                        spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-unknown-input-index") }],
                         rich: None,
                     });
                 }
@@ -391,6 +2105,16 @@ fn validate_mapping(
 mod tests {
     use super::*;
     use crate::parser::parse_command;
+    use shared_types::MediaStream;
+    
+    fn codes(input: &str) -> Vec<String> {
+        codes_with(input, AnalysisOptions::default())
+    }
+    
+    fn codes_with(input: &str, options: AnalysisOptions) -> Vec<String> {
+        let cmd = parse_command(input, 0, 0).unwrap();
+        analyze_command_with_options(cmd, options).messages.into_iter().map(|d| d.code).collect()
+    }
     
     #[test]
     fn test_analyze_simple_command() {
@@ -398,7 +2122,7 @@ mod tests {
         let cmd = parse_command(input, 0, 0).unwrap();
         let result = analyze_command(cmd);
         // Should have no errors for simple valid command
-        assert!(result.messages.is_empty() || result.messages.iter().all(|m| matches!(m.severity, Severity::Warning | Severity::Info)));
+        assert!(result.messages.is_empty() || result.messages.iter().all(|m| matches!(m.severity, Severity::Warning | Severity::Info | Severity::Hint)));
     }
     
     #[test]
@@ -422,5 +2146,366 @@ mod tests {
         let has_error = result.messages.iter().any(|m| m.code == "E401");
         assert!(has_error);
     }
+    
+    #[test]
+    fn test_encoder_option_range() {
+        let input = "ffmpeg -i input.mp4 -c:v libx264 -crf 60 output.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "E404"));
+        
+        // crf 60 is valid for libvpx-vp9
+        let input = "ffmpeg -i input.mp4 -c:v libvpx-vp9 -crf 60 output.webm";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(!result.messages.iter().any(|m| m.code == "E404"));
+    }
+    
+    #[test]
+    fn test_encoder_option_invalid_value() {
+        let input = "ffmpeg -i input.mp4 -c:v libx264 -preset turbo -cq 20 output.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "E405"));
+        assert!(result.messages.iter().any(|m| m.code == "W401"));
+        
+        // profile and level belong to every encoder, listed or not
+        let input = "ffmpeg -i input.mp4 -c:v libaom-av1 -crf 30 -profile:v main -level 5.1 output.mkv";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(!result.messages.iter().any(|m| m.code == "W401"));
+    }
+    
+    #[test]
+    fn test_profile_pixel_format_conflict() {
+        let input = "ffmpeg -i input.mp4 -c:v libx264 -crf 20 -profile:v high -pix_fmt yuv444p output.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "E405" && m.spans.len() == 2));
+    }
+    
+    #[test]
+    fn test_pixel_format_validation() {
+        let input = "ffmpeg -i input.mp4 -c:v libvpx -crf 10 -pix_fmt yuv444p output.webm";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "W202"));
+        
+        let input = "ffmpeg -i input.mp4 -c:v libvpx -crf 10 -vf format=yuv422p output.webm";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "W202"));
+        
+        let input = "ffmpeg -i input.mp4 -c:v libx264 -crf 20 -pix_fmt yuv9000p output.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "W402"));
+        
+        // A format= filter is reported as the filter, not as -pix_fmt
+        let input = "ffmpeg -i input.mp4 -c:v libx264 -crf 20 -vf scale=1280:-2,format=yuv9000p output.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        let diag = result.messages.iter().find(|m| m.code == "W402").unwrap();
+        assert!(matches!(&diag.kind, DiagnosticKind::InvalidParameter { option, .. } if option == "format"));
+        assert_eq!((diag.spans[0].span.start_column, diag.spans[0].span.end_column), (59, 74));
+    }
+    
+    #[test]
+    fn test_browser_incompatible_pixel_format() {
+        let input = "ffmpeg -i input.mp4 -c:v libx264 -crf 20 -pix_fmt yuv420p10le -movflags +faststart output.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "W203"));
+        
+        // An mp4 without +faststart may be an archive or intermediate
+        let input = "ffmpeg -i input.mp4 -c:v libx264 -crf 20 -pix_fmt yuv420p10le output.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(!result.messages.iter().any(|m| m.code == "W203"));
+        
+        let input = "ffmpeg -i input.mp4 -c:v libx264 -crf 20 -vf format=yuv420p -movflags +faststart output.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(!result.messages.iter().any(|m| m.code == "W203"));
+    }
+    
+    #[test]
+    fn test_unsupported_sample_rate() {
+        let input = "ffmpeg -i input.mp4 -c:a libopus -ar 44100 output.webm";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        let diag = result.messages.iter().find(|m| m.code == "E203").unwrap();
+        assert!(diag.spans.iter().any(|s| matches!(&s.role, SpanRole::Suggestion { replacement } if replacement == "48000")));
+        
+        let input = "ffmpeg -i input.mp4 -c:a libmp3lame -af aresample=96000 output.mkv";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "E203"));
+    }
+    
+    #[test]
+    fn test_unsupported_channel_count() {
+        let input = "ffmpeg -i input.mp4 -c:a ac3 -ac 8 output.mkv";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "E206"));
+        
+        let input = "ffmpeg -i input.mp4 -c:a aac -af \"pan=10c|c0=c0\" output.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "E206"));
+        
+        let input = "ffmpeg -i input.mp4 -c:a aac -channel_layout 7.1 output.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(!result.messages.iter().any(|m| m.code == "E206"));
+    }
+    
+    #[test]
+    fn test_trim_consistency() {
+        let input = "ffmpeg -i input.mp4 -ss 30 -to 00:00:10 output.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "E410"));
+        
+        let input = "ffmpeg -i input.mp4 -t 0 output.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "E411"));
+        
+        let input = "ffmpeg -i input.mp4 -t 5 -to 10 output.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "W410"));
+        
+        let input = "ffmpeg -i input.mp4 -sseof -10 output.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "E412"));
+    }
+    
+    #[test]
+    fn test_seek_past_known_duration() {
+        let input = "ffmpeg -ss 10 -f lavfi -i sine=frequency=1000:duration=5 output.wav";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "W411"));
+    }
+    
+    #[test]
+    fn test_unknown_input_streams() {
+        assert!(codes("ffmpeg -i capture.dat -c:v libx264 out.mp4").contains(&"W200".to_string()));
+        assert!(codes("ffmpeg -i file://capture.dat -c:v libx264 out.mp4").contains(&"W200".to_string()));
+        for input in ["rtmp://live.example.com/app/key", "https://cdn.example.com/live/index.m3u8", "srt://10.0.0.2:9000", "-", "pipe:0"] {
+            let found = codes(&format!("ffmpeg -i {} -c:v libx264 out.mp4", input));
+            assert!(!found.contains(&"W200".to_string()), "{}: {:?}", input, found);
+        }
+    }
+    
+    #[test]
+    fn test_seek_with_stream_copy() {
+        let input = "ffmpeg -ss 10 -i input.mp4 -c copy output.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        let hint = result.messages.iter().find(|m| m.code == "I002").unwrap();
+        assert!(matches!(hint.severity, Severity::Info));
+        assert!(matches!(&hint.kind, DiagnosticKind::SeekWithStreamCopy { seek, position, copy_option }
+            if seek == "10" && position == "input" && copy_option == "-c"));
+        
+        let input = "ffmpeg -i input.mp4 -ss 10 -c:v copy output.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        let hint = result.messages.iter().find(|m| m.code == "I002").unwrap();
+        assert!(matches!(&hint.kind, DiagnosticKind::SeekWithStreamCopy { position, copy_option, .. }
+            if position == "output" && copy_option == "-c:v"));
+    }
+    
+    #[test]
+    fn test_output_overwrites_input() {
+        let input = "ffmpeg -y -i clip.mp4 -c:v libx264 -crf 20 ./clip.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        let diag = result.messages.iter().find(|m| m.code == "E601").unwrap();
+        assert!(matches!(diag.severity, Severity::Error));
+        assert_eq!(diag.spans.len(), 2);
+        
+        let input = "ffmpeg -n -i clip.mp4 clip.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "E601" && matches!(m.severity, Severity::Warning)));
+        
+        // -n is global wherever it is written
+        let input = "ffmpeg -i clip.mp4 -n clip.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "E601" && matches!(m.severity, Severity::Warning)));
+    }
+    
+    #[test]
+    fn test_duplicate_output() {
+        let input = "ffmpeg -i input.mp4 -s 1280x720 out/a.mp4 -s 640x360 \"out/./a.mp4\"";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "E602"));
+        
+        let input = "ffmpeg -i input.mp4 -f null - -f null -";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(!result.messages.iter().any(|m| m.code == "E602"));
+    }
+    
+    #[test]
+    fn test_no_quality_setting_hint() {
+        let input = "ffmpeg -i input.mp4 -c:v libx264 output.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(result.messages.iter().any(|m| m.code == "W102" && matches!(m.severity, Severity::Hint)));
+        
+        let input = "ffmpeg -i input.mp4 -c:v libx264 -crf 23 output.mp4";
+        let result = analyze_command(parse_command(input, 0, 0).unwrap());
+        assert!(!result.messages.iter().any(|m| m.code == "W102"));
+    }
+    
+    #[test]
+    fn test_image_sequence_input() {
+        let ok = codes("ffmpeg -framerate 24 -start_number 10 -i frames/img%04d.png -c:v libx264 -crf 20 out.mp4");
+        assert!(!ok.iter().any(|c| c.starts_with("W60") || c.starts_with('E')), "{:?}", ok);
+        
+        let cmd = parse_command("ffmpeg -r 24 -i frames/img%04d.png -c:v libx264 -crf 20 out.mp4", 0, 0).unwrap();
+        let diags = analyze_command(cmd).messages;
+        let diag = diags.iter().find(|d| d.code == "W602").expect("expected W602");
+        assert!(diag.spans.iter().any(|s| matches!(&s.role, SpanRole::Suggestion { replacement } if replacement == "-framerate 24")));
+        
+        assert!(codes("ffmpeg -i frames/img%04d.png out.mp4").contains(&"W603".to_string()));
+        assert!(codes("ffmpeg -framerate 10 -i '*.jpg' out.mp4").contains(&"W604".to_string()));
+        assert!(!codes("ffmpeg -framerate 10 -pattern_type glob -i '*.jpg' out.mp4").contains(&"W604".to_string()));
+        // Image sequences carry no audio
+        assert!(codes("ffmpeg -framerate 10 -i img%03d.png -c:a aac out.mp4").contains(&"E105".to_string()));
+    }
+    
+    #[test]
+    fn test_image_output_without_pattern() {
+        assert!(codes("ffmpeg -i input.mp4 -vf fps=1 thumb.png").contains(&"W601".to_string()));
+        assert!(!codes("ffmpeg -i input.mp4 -vf fps=1 thumbs/%03d.png").contains(&"W601".to_string()));
+        assert!(!codes("ffmpeg -ss 5 -i input.mp4 -frames:v 1 thumb.png").contains(&"W601".to_string()));
+        assert!(!codes("ffmpeg -i cover.jpg cover.png").contains(&"W601".to_string()));
+    }
+    
+    #[test]
+    fn test_subtitle_stream_copy() {
+        let cmd = parse_command("ffmpeg -i movie.mp4 -i subs.srt -c:v copy -c:s copy out.mp4", 0, 0).unwrap();
+        let diags = analyze_command(cmd).messages;
+        let diag = diags.iter().find(|d| d.code == "E207").expect("expected E207");
+        assert!(diag.spans.iter().any(|s| matches!(&s.role, SpanRole::Suggestion { replacement } if replacement == "mov_text")));
+        
+        assert!(codes("ffmpeg -i movie.mkv -i subs.srt -c copy out.mp4").contains(&"E207".to_string()));
+        assert!(!codes("ffmpeg -i movie.mkv -i subs.srt -c copy out.mkv").contains(&"E207".to_string()));
+        assert!(!codes("ffmpeg -i movie.mkv -i subs.srt -c copy -sn out.mp4").contains(&"E207".to_string()));
+        assert!(codes("ffmpeg -i movie.mp4 -i subs.sup -c:s mov_text out.mp4").contains(&"E208".to_string()));
+        assert!(codes("ffmpeg -i movie.mp4 -c:s mov_text out.mp4").contains(&"E106".to_string()));
+    }
+    
+    #[test]
+    fn test_encoder_and_decoder_names() {
+        let cmd = parse_command("ffmpeg -i input.mp4 -c:v hevc out.mp4", 0, 0).unwrap();
+        let diags = analyze_command(cmd).messages;
+        let diag = diags.iter().find(|d| d.code == "W205").expect("expected W205");
+        let first_suggestion = diag.spans.iter().find_map(|s| match &s.role {
+            SpanRole::Suggestion { replacement } => Some(replacement.as_str()),
+            _ => None,
+        });
+        assert_eq!(first_suggestion, Some("libx265"));
+        
+        assert!(codes("ffmpeg -i input.mp4 -c:a mp3 out.mkv").contains(&"W205".to_string()));
+        assert!(!codes("ffmpeg -i input.mp4 -c:v libx264 -c:a copy out.mp4").contains(&"W205".to_string()));
+        assert!(codes("ffmpeg -i movie.mkv -c:s hdmv_pgs_subtitle out.mkv").contains(&"E211".to_string()));
+        // Before -i the codec picks a decoder
+        assert!(codes("ffmpeg -c:v libx264 -i input.mp4 out.mkv").contains(&"E212".to_string()));
+        assert!(!codes("ffmpeg -c:v h264 -i input.mp4 out.mkv").contains(&"E212".to_string()));
+        assert!(!codes("ffmpeg -c:v h264_cuvid -i input.mp4 out.mkv").contains(&"E212".to_string()));
+    }
+    
+    #[test]
+    fn test_did_you_mean() {
+        let suggestions = |input: &str, code: &str| {
+            let cmd = parse_command(input, 0, 0).unwrap();
+            let diag = analyze_command(cmd).messages.into_iter().find(|d| d.code == code).expect("expected diagnostic");
+            diag.spans
+                .into_iter()
+                .filter_map(|s| match s.role {
+                    SpanRole::Suggestion { replacement } => Some(replacement),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(suggestions("ffmpeg -i input.mp4 -c:v libx246 out.mp4", "W201"), vec!["libx264"]);
+        assert_eq!(suggestions("ffmpeg -i input.mp4 -c:v h.264 out.mkv", "W201")[0], "libx264");
+        assert_eq!(suggestions("ffmpeg -i input.mp4 -vf scael=1280:720 out.mp4", "E502"), vec!["scale"]);
+        assert_eq!(suggestions("ffmpeg -i input.mp4 -vcodc libx264 out.mp4", "W415"), vec!["-vcodec"]);
+        assert_eq!(suggestions("ffmpeg -i input.mp4 -f mkv out.mkv", "W206"), vec!["matroska"]);
+        // Audio context: no video encoders, and no video filters
+        assert!(suggestions("ffmpeg -i input.mp4 -c:a libx246 out.mp4", "W201").is_empty());
+        assert!(suggestions("ffmpeg -i input.mp4 -af scael=2 out.mp4", "E502").is_empty());
+    }
+    
+    #[test]
+    fn test_subtitle_burn_in_needs_video() {
+        let cmd = parse_command("ffmpeg -i song.mp3 -vf subtitles=subs.srt out.mp4", 0, 0).unwrap();
+        let diags = analyze_command(cmd).messages;
+        let diag = diags.iter().find(|d| d.code == "E104").expect("expected E104");
+        let rich = diag.rich.as_ref().unwrap();
+        assert!(rich.blocks.iter().any(|b| matches!(b, RichBlock::MarkdownGfm { markdown } if markdown.contains("Burned-in"))));
+    }
+    
+    #[test]
+    fn test_lossy_transcoding_hints() {
+        let fix = |input: &str, code: &str| {
+            let cmd = parse_command(input, 0, 0).unwrap();
+            let diags = analyze_command(cmd).messages;
+            diags.into_iter().find(|d| d.code == code).and_then(|d| {
+                d.spans.into_iter().find_map(|s| match s.role {
+                    SpanRole::Suggestion { replacement } => Some(replacement),
+                    _ => None,
+                })
+            })
+        };
+        
+        assert_eq!(fix("ffmpeg -i song.mp3 -c:a libmp3lame out.mp3", "W103"), Some("copy".to_string()));
+        assert_eq!(fix("ffmpeg -i song.mp3 -af volume=2 -c:a libmp3lame out.mp3", "W103"), None);
+        // A new bitrate still re-encodes at no better quality than the source
+        assert_eq!(fix("ffmpeg -i song.mp3 -c:a libmp3lame -b:a 320k out.mp3", "W103"), Some("copy".to_string()));
+        assert_eq!(fix("ffmpeg -i song.mp3 -c:a aac out.mp4", "W104"), Some("copy".to_string()));
+        assert_eq!(fix("ffmpeg -i song.mp3 -c:a libopus out.webm", "W104"), Some(" -b:a 192k".to_string()));
+        assert_eq!(fix("ffmpeg -i song.flac -c:a aac -b:a 64k out.mp4", "W105"), Some("192k".to_string()));
+        assert_eq!(fix("ffmpeg -i song.flac -c:a aac -b:a 256k out.mp4", "W105"), None);
+        assert_eq!(fix("ffmpeg -framerate 24 -i img%04d.png -c:v libx264 -crf 40 out.mp4", "W105"), Some("25".to_string()));
+    }
+    
+    #[test]
+    fn test_bitrate_units() {
+        assert!(codes("ffmpeg -i input.mp4 -b:v abc output.mp4").contains(&"E402".to_string()));
+        assert!(codes("ffmpeg -i input.mp4 -b:v 100M output.mp4").contains(&"W101".to_string()));
+        assert!(!codes("ffmpeg -i input.mp4 -b:v 2.5M output.mp4").iter().any(|c| c == "E402" || c.starts_with("W10")));
+        let low = codes("ffmpeg -i input.mp4 -b:a 600 output.mp4");
+        assert!(low.contains(&"W106".to_string()) && !low.contains(&"W101".to_string()));
+        
+        // -b:v 0 with -crf selects constant quality for libvpx-vp9
+        let constant_quality = codes("ffmpeg -i a.mp4 -c:v libvpx-vp9 -crf 30 -b:v 0 out.webm");
+        assert!(!constant_quality.iter().any(|c| c.starts_with('W') || c.starts_with('E')), "{:?}", constant_quality);
+    }
+    
+    #[test]
+    fn test_rate_control_consistency() {
+        let vbv = codes("ffmpeg -i input.mp4 -c:v libx264 -b:v 4M -maxrate 3M output.mp4");
+        assert!(vbv.contains(&"W413".to_string()) && vbv.contains(&"W412".to_string()));
+        assert!(codes("ffmpeg -i input.mp4 -c:v libx264 -b:v 2M -minrate 4M -maxrate 3M -bufsize 6M output.mp4").contains(&"E413".to_string()));
+        assert!(!codes("ffmpeg -i input.mp4 -c:v libx264 -b:v 2M -maxrate 3M -bufsize 6M output.mp4").iter().any(|c| c.starts_with("W41") || c == "E413"));
+    }
+    
+    #[test]
+    fn test_bits_per_pixel() {
+        let cmd = parse_command("ffmpeg -i input.mp4 -c:v libx264 -b:v 200k -s 1920x1080 -r 30 output.mp4", 0, 0).unwrap();
+        let diags = analyze_command(cmd).messages;
+        let diag = diags.iter().find(|d| d.code == "W107").expect("expected W107");
+        assert!(diag.spans.iter().any(|s| matches!(&s.role, SpanRole::Suggestion { replacement } if replacement == "1.3M")));
+        
+        let cmd = parse_command("ffmpeg -i input.mp4 -c:v libx265 -b:v 40M -vf scale=1280:720 output.mp4", 0, 0).unwrap();
+        assert!(analyze_command(cmd).messages.iter().any(|d| d.code == "W108"));
+        // Unknown output size: no estimate
+        let cmd = parse_command("ffmpeg -i input.mp4 -c:v libx264 -b:v 200k output.mp4", 0, 0).unwrap();
+        assert!(!analyze_command(cmd).messages.iter().any(|d| d.code == "W107"));
+    }
+    
+    #[test]
+    fn test_media_manifest() {
+        let video = |width, height, frame_rate| MediaStream {
+            stream_type: StreamType::Video,
+            codec: Some("h264".to_string()),
+            width: Some(width),
+            height: Some(height),
+            frame_rate: Some(frame_rate),
+            sample_rate: None,
+            channels: None,
+        };
+        let media = vec![MediaFile { path: "./silent.mp4".to_string(), duration: Some(12.0), streams: vec![video(640, 360, 25.0)] }];
+        let codes = |input: &str| codes_with(input, AnalysisOptions { media: media.clone(), ..AnalysisOptions::default() });
+        // The manifest says there is no audio, which the extension cannot tell
+        assert!(codes("ffmpeg -i silent.mp4 -c:a aac output.mp4").contains(&"E105".to_string()));
+        assert!(!codes("ffmpeg -i other.mp4 -c:a aac output.mp4").contains(&"E105".to_string()));
+        // Duration, frame size and frame rate come from the manifest
+        assert!(codes("ffmpeg -ss 20 -i silent.mp4 output.mp4").contains(&"W411".to_string()));
+        assert!(codes("ffmpeg -i silent.mp4 -c:v libx264 -b:v 50k output.mp4").contains(&"W107".to_string()));
+        assert!(codes("ffmpeg -i silent.mp4 -vf scale=1920:1080 output.mp4").contains(&"W109".to_string()));
+        assert!(!codes("ffmpeg -i silent.mp4 -vf scale=320:180 output.mp4").contains(&"W109".to_string()));
+    }
 }
-
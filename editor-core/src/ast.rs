@@ -47,6 +47,11 @@ pub enum OptionNode {
         codec_span: SourceCodeSpan,
         span: SourceCodeSpan,
     },
+    SubtitleCodec {
+        codec: String,
+        codec_span: SourceCodeSpan,
+        span: SourceCodeSpan,
+    },
     Codec {
         codec: String,
         codec_span: SourceCodeSpan,
@@ -55,11 +60,15 @@ pub enum OptionNode {
     
     // Bitrate options
     VideoBitrate {
+        /// Flag as written (`-b:v`, `-b` or the legacy `-vb`)
+        flag: String,
         bitrate: String,
         bitrate_span: SourceCodeSpan,
         span: SourceCodeSpan,
     },
     AudioBitrate {
+        /// Flag as written (`-b:a` or the legacy `-ab`)
+        flag: String,
         bitrate: String,
         bitrate_span: SourceCodeSpan,
         span: SourceCodeSpan,
@@ -77,6 +86,13 @@ pub enum OptionNode {
         span: SourceCodeSpan,
     },
     
+    // Pixel format
+    PixelFormat {
+        format: String,
+        format_span: SourceCodeSpan,
+        span: SourceCodeSpan,
+    },
+    
     // Filters
     VideoFilter {
         filter: FilterSpec,
@@ -116,6 +132,16 @@ pub enum OptionNode {
         time_span: SourceCodeSpan,
         span: SourceCodeSpan,
     },
+    SeekEnd {
+        time: String,
+        time_span: SourceCodeSpan,
+        span: SourceCodeSpan,
+    },
+    SeekFromEnd {
+        time: String,
+        time_span: SourceCodeSpan,
+        span: SourceCodeSpan,
+    },
     
     // Audio options
     SampleRate {
@@ -138,6 +164,82 @@ pub enum OptionNode {
     },
 }
 
+/// Options that apply to the whole command rather than to a single input or output
+pub const GLOBAL_OPTIONS: &[&str] = &[
+    "-y", "-n", "-hide_banner", "-loglevel", "-v", "-stats", "-nostats", "-nostdin",
+    "-report", "-benchmark", "-benchmark_all", "-progress", "-stats_period",
+    "-filter_threads", "-filter_complex_threads", "-init_hw_device", "-filter_hw_device",
+    "-xerror", "-abort_on", "-max_error_rate", "-ignore_unknown", "-copy_unknown",
+    "-sdp_file", "-vsync", "-lavfi", "-filter_complex_script",
+];
+
+impl OptionNode {
+    /// Canonical option name as written on the command line (aliases such as
+    /// `-vcodec` are reported as `-c:v`)
+    pub fn name(&self) -> &str {
+        match self {
+            OptionNode::GlobalFlag { name, .. } | OptionNode::Generic { name, .. } => name,
+            OptionNode::VideoCodec { .. } => "-c:v",
+            OptionNode::AudioCodec { .. } => "-c:a",
+            OptionNode::SubtitleCodec { .. } => "-c:s",
+            OptionNode::Codec { .. } => "-c",
+            OptionNode::VideoBitrate { .. } => "-b:v",
+            OptionNode::AudioBitrate { .. } => "-b:a",
+            OptionNode::Resolution { .. } => "-s",
+            OptionNode::FrameRate { .. } => "-r",
+            OptionNode::PixelFormat { .. } => "-pix_fmt",
+            OptionNode::VideoFilter { .. } => "-vf",
+            OptionNode::AudioFilter { .. } => "-af",
+            OptionNode::FilterComplex { .. } => "-filter_complex",
+            OptionNode::Map { .. } => "-map",
+            OptionNode::Format { .. } => "-f",
+            OptionNode::SeekStart { .. } => "-ss",
+            OptionNode::Duration { .. } => "-t",
+            OptionNode::SeekEnd { .. } => "-to",
+            OptionNode::SeekFromEnd { .. } => "-sseof",
+            OptionNode::SampleRate { .. } => "-ar",
+            OptionNode::AudioChannels { .. } => "-ac",
+        }
+    }
+    
+    /// Span of the whole option including its value
+    pub fn span(&self) -> &SourceCodeSpan {
+        match self {
+            OptionNode::GlobalFlag { span, .. }
+            | OptionNode::VideoCodec { span, .. }
+            | OptionNode::AudioCodec { span, .. }
+            | OptionNode::SubtitleCodec { span, .. }
+            | OptionNode::Codec { span, .. }
+            | OptionNode::VideoBitrate { span, .. }
+            | OptionNode::AudioBitrate { span, .. }
+            | OptionNode::Resolution { span, .. }
+            | OptionNode::FrameRate { span, .. }
+            | OptionNode::PixelFormat { span, .. }
+            | OptionNode::VideoFilter { span, .. }
+            | OptionNode::AudioFilter { span, .. }
+            | OptionNode::FilterComplex { span, .. }
+            | OptionNode::Map { span, .. }
+            | OptionNode::Format { span, .. }
+            | OptionNode::SeekStart { span, .. }
+            | OptionNode::Duration { span, .. }
+            | OptionNode::SeekEnd { span, .. }
+            | OptionNode::SeekFromEnd { span, .. }
+            | OptionNode::SampleRate { span, .. }
+            | OptionNode::AudioChannels { span, .. }
+            | OptionNode::Generic { span, .. } => span,
+        }
+    }
+    
+    /// Whether this option applies to the whole command
+    pub fn is_global(&self) -> bool {
+        match self {
+            OptionNode::GlobalFlag { .. } | OptionNode::FilterComplex { .. } => true,
+            OptionNode::Generic { name, .. } => GLOBAL_OPTIONS.contains(&name.as_str()),
+            _ => false,
+        }
+    }
+}
+
 /// Filter specification
 #[derive(Debug, Clone)]
 pub struct FilterSpec {
@@ -162,9 +264,36 @@ pub struct Filter {
     pub name: String,
     pub name_span: SourceCodeSpan,
     pub params: Vec<FilterParam>,
+    /// Link labels before the filter name, e.g. `[0:v]`
+    pub input_labels: Vec<String>,
+    /// Link labels after the filter parameters, e.g. `[out]`
+    pub output_labels: Vec<String>,
+    /// Spans of `input_labels` and `output_labels`, brackets included
+    pub input_label_spans: Vec<SourceCodeSpan>,
+    pub output_label_spans: Vec<SourceCodeSpan>,
     pub span: SourceCodeSpan,
 }
 
+impl Filter {
+    /// Find a parameter by key, falling back to its positional index
+    pub fn param(&self, key: &str, position: usize) -> Option<&FilterParam> {
+        self.params
+            .iter()
+            .find(|p| p.key.as_deref() == Some(key))
+            .or_else(|| self.params.get(position).filter(|p| p.key.is_none()))
+    }
+}
+
+impl FilterSpec {
+    /// All filters in the parsed graph (empty if the graph could not be parsed)
+    pub fn filters(&self) -> impl Iterator<Item = &Filter> {
+        self.parsed
+            .iter()
+            .flat_map(|graph| graph.chains.iter())
+            .flat_map(|chain| chain.filters.iter())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct FilterParam {
     pub key: Option<String>,
@@ -201,11 +330,117 @@ pub fn span_from_pest(span: pest::Span, line_offset: usize, column_offset: usize
     }
 }
 
+/// Parse an ffmpeg time duration (`[-][HH:]MM:SS[.m...]` or `[-]S+[.m...][s|ms|us]`) into seconds
+pub fn parse_time_seconds(time: &str) -> Option<f64> {
+    let (sign, time) = match time.strip_prefix('-') {
+        Some(rest) => (-1.0, rest),
+        None => (1.0, time),
+    };
+    
+    let seconds = if time.contains(':') {
+        time.split(':').try_fold(0.0, |acc, part| Some(acc * 60.0 + part.parse::<f64>().ok()?))?
+    } else if let Some(ms) = time.strip_suffix("ms") {
+        ms.parse::<f64>().ok()? / 1_000.0
+    } else if let Some(us) = time.strip_suffix("us") {
+        us.parse::<f64>().ok()? / 1_000_000.0
+    } else {
+        time.strip_suffix('s').unwrap_or(time).parse::<f64>().ok()?
+    };
+    
+    Some(sign * seconds)
+}
+
+/// A bitrate in bits per second
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Bitrate(pub f64);
+
+impl Bitrate {
+    /// Parse a bitrate the way ffmpeg's `av_strtod` does: an SI prefix (`m`, `k`/`K`, `M`, `G`),
+    /// optionally followed by `i` for powers of 1024 and `B` for bytes (`128k`, `2.5M`, `1Mi`, `16kB`)
+    pub fn parse(text: &str) -> Option<Bitrate> {
+        let number_len = text
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(text.len());
+        let (number, mut suffix) = text.split_at(number_len);
+        let mut value = number.parse::<f64>().ok()?;
+        
+        let exponent = match suffix.chars().next() {
+            Some('m') => -1,
+            Some('k' | 'K') => 1,
+            Some('M') => 2,
+            Some('G') => 3,
+            _ => 0,
+        };
+        if exponent != 0 {
+            suffix = &suffix[1..];
+            value *= match suffix.strip_prefix('i') {
+                Some(rest) => {
+                    suffix = rest;
+                    1024f64.powi(exponent)
+                }
+                None => 1000f64.powi(exponent),
+            };
+        }
+        if let Some(rest) = suffix.strip_prefix('B') {
+            suffix = rest;
+            value *= 8.0;
+        }
+        
+        suffix.is_empty().then_some(Bitrate(value))
+    }
+    
+    pub fn bits_per_second(&self) -> f64 {
+        self.0
+    }
+}
+
+impl std::fmt::Display for Bitrate {
+    /// Format in ffmpeg syntax with the largest whole SI prefix (`6M`, `192k`, `600`)
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (value, prefix) = if self.0 >= 1_000_000.0 {
+            (self.0 / 1_000_000.0, "M")
+        } else if self.0 >= 1_000.0 {
+            (self.0 / 1_000.0, "k")
+        } else {
+            (self.0, "")
+        };
+        // Drop float noise such as 2.4999999
+        let value = (value * 1000.0).round() / 1000.0;
+        write!(f, "{}{}", value, prefix)
+    }
+}
+
 /// Stream information tracked during analysis
 #[derive(Debug, Clone)]
 pub struct StreamInfo {
     pub stream_type: StreamType,
     pub index: usize,
     pub input_index: usize,
+    /// Codec of the stream when it can be known (e.g. `srt` for a `.srt` input)
+    pub codec: Option<String>,
+    /// Frame size, frame rate and audio layout, known only from a media manifest
+    pub width: Option<usize>,
+    pub height: Option<usize>,
+    pub frame_rate: Option<f64>,
+    pub sample_rate: Option<usize>,
+    pub channels: Option<usize>,
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_bitrate_parse() {
+        let bps = |text: &str| Bitrate::parse(text).map(|b| b.bits_per_second());
+        assert_eq!(bps("128k"), Some(128_000.0));
+        assert_eq!(bps("2.5M"), Some(2_500_000.0));
+        assert_eq!(bps("600"), Some(600.0));
+        assert_eq!(bps("1Ki"), Some(1024.0));
+        assert_eq!(bps("16kB"), Some(128_000.0));
+        assert_eq!(bps("abc"), None);
+        assert_eq!(bps("5Mx"), None);
+        assert_eq!(Bitrate(6_000_000.0).to_string(), "6M");
+        assert_eq!(Bitrate(192_000.0).to_string(), "192k");
+    }
+}
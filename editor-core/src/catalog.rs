@@ -0,0 +1,844 @@
+//! Every diagnostic code the analyzer reports, with its default severity, a long-form
+//! explanation and a command that triggers it next to one that does not.
+//!
+//! Titles and explanations are English reference text and are not routed through message IDs:
+//! they quote ffmpeg's own English output and option names, and `explain_code` takes no locale.
+
+use shared_types::{DiagnosticRich, RichBlock, Severity};
+
+/// A command illustrating a diagnostic code
+pub struct Example {
+    pub command: &'static str,
+    /// ffmpeg release the command targets; None is the latest release
+    pub ffmpeg_version: Option<&'static str>,
+    /// Frame size of the video input, for codes that need a media manifest
+    pub video_size: Option<(usize, usize)>,
+}
+
+impl Example {
+    const fn new(command: &'static str) -> Self {
+        Example { command, ffmpeg_version: None, video_size: None }
+    }
+    
+    const fn targeting(self, version: &'static str) -> Self {
+        Example { ffmpeg_version: Some(version), ..self }
+    }
+    
+    const fn with_video_size(self, width: usize, height: usize) -> Self {
+        Example { video_size: Some((width, height)), ..self }
+    }
+}
+
+/// Catalog entry of a diagnostic code
+pub struct DiagnosticInfo {
+    pub code: &'static str,
+    /// Severity the analyzer reports the code with, before lint configuration
+    pub severity: Severity,
+    pub title: &'static str,
+    pub explanation: &'static str,
+    /// A command reporting the code
+    pub bad: Example,
+    /// The same command fixed; None when the code is not a problem to fix
+    pub good: Option<Example>,
+}
+
+const fn entry(
+    code: &'static str,
+    severity: Severity,
+    title: &'static str,
+    explanation: &'static str,
+    bad: Example,
+    good: Example,
+) -> DiagnosticInfo {
+    DiagnosticInfo { code, severity, title, explanation, bad, good: Some(good) }
+}
+
+const fn ex(command: &'static str) -> Example {
+    Example::new(command)
+}
+
+use Severity::{Error, Hint, Info, Warning};
+
+pub static CATALOG: &[DiagnosticInfo] = &[
+    entry(
+        "E000",
+        Error,
+        "Command could not be parsed",
+        "The line is not a command the parser understands, so nothing else in it is checked. Commands \
+         start with `ffmpeg`, every input follows `-i`, and options that take a value need one.",
+        ex("ffmpeg -i input.mp4 -vf"),
+        ex("ffmpeg -i input.mp4 output.mp4"),
+    ),
+    // E100-E199: stream types
+    entry(
+        "E101",
+        Error,
+        "Filter applied to a stream of the wrong type",
+        "Video filters (`-vf`) only take video frames and audio filters (`-af`) only take audio samples. \
+         ffmpeg fails to configure the filter graph when a filter gets the other kind of stream.",
+        ex("ffmpeg -i input.mp4 -af scale=640:480 output.mp4"),
+        ex("ffmpeg -i input.mp4 -vf scale=640:480 output.mp4"),
+    ),
+    entry(
+        "E104",
+        Error,
+        "No video stream for a video operation",
+        "A video codec, filter or option is set on an output that has no video to work on, for example \
+         because every input is audio. Remove the video options, or add a video input.",
+        ex("ffmpeg -i audio.mp3 -c:v libx264 output.mp4"),
+        ex("ffmpeg -i audio.mp3 -c:a aac output.m4a"),
+    ),
+    entry(
+        "E105",
+        Error,
+        "No audio stream for an audio operation",
+        "An audio codec, filter or option is set on an output that has no audio to work on, for example \
+         because the only input is an image or audio was disabled with `-an`.",
+        ex("ffmpeg -i image.png -c:a aac output.mp4"),
+        ex("ffmpeg -i image.png -c:v libx264 output.mp4"),
+    ),
+    entry(
+        "E106",
+        Error,
+        "No subtitle stream for subtitle encoding",
+        "A subtitle codec is set but no input provides subtitles. Add the subtitle file as another input, \
+         or drop `-c:s`.",
+        ex("ffmpeg -i video.mp4 -c:s mov_text output.mp4"),
+        ex("ffmpeg -i video.mp4 -i subs.srt -c:s mov_text output.mp4"),
+    ),
+    // E200-E299: codecs and formats
+    entry(
+        "E201",
+        Error,
+        "Codec not supported in the output container",
+        "Each container stores a fixed set of codecs; ffmpeg refuses to write a stream the muxer cannot \
+         store. Pick an encoder the container supports or a container that supports the codec. The \
+         quick fixes offer both.",
+        ex("ffmpeg -i input.mp4 -c:v vp9 output.mp4"),
+        ex("ffmpeg -i input.mp4 -c:v libvpx-vp9 output.webm"),
+    ),
+    entry(
+        "E203",
+        Error,
+        "Sample rate not supported by the audio encoder",
+        "Some encoders only accept a few sample rates; Opus, for one, runs at 48 kHz. ffmpeg stops with an \
+         error instead of resampling, so use a rate the encoder lists.",
+        ex("ffmpeg -i input.mp4 -c:a libopus -ar 44100 output.webm"),
+        ex("ffmpeg -i input.mp4 -c:a libopus -ar 48000 output.webm"),
+    ),
+    entry(
+        "E205",
+        Error,
+        "Codec used for the wrong stream type",
+        "The codec encodes another kind of stream than the option selects, such as a video encoder given \
+         to `-c:a`.",
+        ex("ffmpeg -i input.mp4 -c:a libx264 output.mp4"),
+        ex("ffmpeg -i input.mp4 -c:a aac output.mp4"),
+    ),
+    entry(
+        "E206",
+        Error,
+        "Too many channels for the audio encoder",
+        "The encoder has a maximum channel count (AC-3 stops at 5.1). Downmix with a lower `-ac` or use an \
+         encoder that supports the layout.",
+        ex("ffmpeg -i input.mp4 -c:a ac3 -ac 8 output.mkv"),
+        ex("ffmpeg -i input.mp4 -c:a ac3 -ac 6 output.mkv"),
+    ),
+    entry(
+        "E207",
+        Error,
+        "Subtitle stream copied into a container that cannot store it",
+        "Stream copy keeps the subtitle codec, and MP4 cannot store SubRip or ASS subtitles. Convert them \
+         with `-c:s mov_text`, or write a container such as Matroska that stores them as they are.",
+        ex("ffmpeg -i movie.mp4 -i subs.srt -c copy output.mp4"),
+        ex("ffmpeg -i movie.mp4 -i subs.srt -c copy -c:s mov_text output.mp4"),
+    ),
+    entry(
+        "E208",
+        Error,
+        "Subtitle conversion between text and bitmap codecs",
+        "ffmpeg can only convert text subtitles to text and bitmap subtitles to bitmap. Turning images of \
+         text into text needs OCR, outside ffmpeg; bitmap subtitles can still be copied or burned in.",
+        ex("ffmpeg -i movie.mp4 -i subs.sup -c:s mov_text output.mp4"),
+        ex("ffmpeg -i movie.mkv -i subs.sup -c:s copy output.mkv"),
+    ),
+    entry(
+        "E209",
+        Error,
+        "Codec not available in the target ffmpeg version",
+        "The codec was added in a later ffmpeg release than the one the command targets, so that ffmpeg \
+         reports it as unknown. Upgrade, or choose a codec the release has.",
+        ex("ffmpeg -i input.mp4 -c:v av1_vaapi output.mkv").targeting("6.0"),
+        ex("ffmpeg -i input.mp4 -c:v libx264 output.mkv").targeting("6.0"),
+    ),
+    entry(
+        "E210",
+        Error,
+        "Experimental encoder without `-strict experimental`",
+        "ffmpeg refuses to use encoders marked experimental, such as the native Opus encoder, unless the \
+         command opts in with `-strict experimental`. A stable encoder like `libopus` needs no flag.",
+        ex("ffmpeg -i input.wav -c:a opus output.ogg"),
+        ex("ffmpeg -i input.wav -c:a libopus output.ogg"),
+    ),
+    entry(
+        "E211",
+        Error,
+        "Output codec that ffmpeg can decode but not encode",
+        "Some codecs only have a decoder, PGS subtitles among them. They can be read and stream copied, \
+         but not encoded.",
+        ex("ffmpeg -i movie.mkv -c:s hdmv_pgs_subtitle output.mkv"),
+        ex("ffmpeg -i movie.mkv -c:s copy output.mkv"),
+    ),
+    entry(
+        "E212",
+        Error,
+        "Encoder named as an input decoder",
+        "A codec option before `-i` selects the decoder for that input. Encoder-only names such as \
+         `libx264` stop ffmpeg with \"Unknown decoder\"; move the option after the input to set the \
+         output encoder.",
+        ex("ffmpeg -c:v libx264 -i input.mp4 output.mkv"),
+        ex("ffmpeg -i input.mp4 -c:v libx264 output.mkv"),
+    ),
+    entry(
+        "E213",
+        Error,
+        "Audio stream copied into a container that cannot store it",
+        "Stream copy keeps the audio codec, and the container cannot store it, as with PCM audio in MP4. \
+         Encode the audio instead (`-c:a aac`) or pick a container that stores the codec.",
+        ex("ffmpeg -i song.wav -c copy output.mp4"),
+        ex("ffmpeg -i song.wav -c copy -c:a aac output.mp4"),
+    ),
+    entry(
+        "W200",
+        Warning,
+        "Unknown stream types for an input",
+        "Neither a media manifest nor the input's extension or `-f` format tells which streams the input \
+         has, so stream checks are skipped for it: it may provide any stream. Name the input's format with \
+         `-f` before `-i`, or pass the probed streams in the media manifest. Network streams and pipes are \
+         not reported, since there is no file to probe.",
+        ex("ffmpeg -i capture.dat -c:v libx264 output.mp4"),
+        ex("ffmpeg -f mpegts -i capture.dat -c:v libx264 output.mp4"),
+    ),
+    entry(
+        "W201",
+        Warning,
+        "Unknown codec",
+        "The codec is not in the codec database, usually because of a typo. Codecs from a custom ffmpeg \
+         build can be added by importing its `-codecs` list.",
+        ex("ffmpeg -i input.mp4 -c:v libx246 output.mp4"),
+        ex("ffmpeg -i input.mp4 -c:v libx264 output.mp4"),
+    ),
+    entry(
+        "W202",
+        Warning,
+        "Pixel format not supported by the encoder",
+        "The encoder cannot take the requested pixel format, so ffmpeg converts to the nearest one it \
+         supports and the output does not have the format asked for.",
+        ex("ffmpeg -i input.mp4 -c:v libvpx -pix_fmt yuv444p output.webm"),
+        ex("ffmpeg -i input.mp4 -c:v libvpx -pix_fmt yuv420p output.webm"),
+    ),
+    entry(
+        "W203",
+        Warning,
+        "H.264/HEVC pixel format that browsers cannot decode",
+        "Browsers and most hardware decoders only play 4:2:0 H.264 and HEVC. Use `-pix_fmt yuv420p` for \
+         files meant for playback. Only web deliverables are checked: webm outputs and mp4 outputs written \
+         with `-movflags +faststart`.",
+        ex("ffmpeg -i input.mp4 -c:v libx264 -pix_fmt yuv444p -movflags +faststart output.mp4"),
+        ex("ffmpeg -i input.mp4 -c:v libx264 -pix_fmt yuv420p -movflags +faststart output.mp4"),
+    ),
+    entry(
+        "W204",
+        Warning,
+        "Codec deprecated in the target ffmpeg version",
+        "The codec still works in the targeted release but is deprecated and will be removed; `nvenc_h264` \
+         was renamed `h264_nvenc` and the old name dropped in 5.0.",
+        ex("ffmpeg -i input.mp4 -c:v nvenc_h264 output.mp4").targeting("4.4"),
+        ex("ffmpeg -i input.mp4 -c:v h264_nvenc output.mp4").targeting("4.4"),
+    ),
+    entry(
+        "W205",
+        Warning,
+        "Decoder name used as an output codec",
+        "`h264` names the codec, not an encoder; ffmpeg picks the default encoder of the codec, which may \
+         not be the one wanted. Name the encoder, such as `libx264`.",
+        ex("ffmpeg -i input.mp4 -c:v h264 output.mp4"),
+        ex("ffmpeg -i input.mp4 -c:v libx264 output.mp4"),
+    ),
+    entry(
+        "W206",
+        Warning,
+        "Unknown output format",
+        "The `-f` value is not a known muxer. `-f` takes format names, which are not always file \
+         extensions: Matroska is `matroska`, not `mkv`.",
+        ex("ffmpeg -i input.mp4 -f mkv output.mkv"),
+        ex("ffmpeg -i input.mp4 -f matroska output.mkv"),
+    ),
+    // E300-E399: stream mapping
+    entry(
+        "E301",
+        Error,
+        "Mapped stream does not exist",
+        "`-map` refers to an input index beyond the inputs of the command. Inputs are numbered from 0 in \
+         the order of their `-i`.",
+        ex("ffmpeg -i input.mp4 -map 2:0 output.mp4"),
+        ex("ffmpeg -i input.mp4 -map 0:0 output.mp4"),
+    ),
+    entry(
+        "E303",
+        Error,
+        "Filter output label does not exist",
+        "`-map [label]` selects an output of `-filter_complex`, and no filter graph output has that label.",
+        ex("ffmpeg -i input.mp4 -map [out] output.mp4"),
+        ex("ffmpeg -i input.mp4 -filter_complex [0:v]scale=640:360[out] -map [out] output.mp4"),
+    ),
+    // E400-E499: options
+    entry(
+        "E401",
+        Error,
+        "Invalid resolution format",
+        "Frame sizes are written `WIDTHxHEIGHT` or as a size name such as `hd720`.",
+        ex("ffmpeg -i input.mp4 -s 1920 output.mp4"),
+        ex("ffmpeg -i input.mp4 -s 1920x1080 output.mp4"),
+    ),
+    entry(
+        "E402",
+        Error,
+        "Invalid bitrate format",
+        "Bitrates are a number of bits per second with an optional `k` or `M` suffix, such as `128k`.",
+        ex("ffmpeg -i input.mp4 -b:v abc output.mp4"),
+        ex("ffmpeg -i input.mp4 -b:v 4M output.mp4"),
+    ),
+    entry(
+        "E403",
+        Error,
+        "Invalid frame rate",
+        "Frame rates must be positive and at most 1000 frames per second.",
+        ex("ffmpeg -i input.mp4 -r 0 output.mp4"),
+        ex("ffmpeg -i input.mp4 -r 30 output.mp4"),
+    ),
+    entry(
+        "E404",
+        Error,
+        "Encoder option out of range",
+        "The value is outside the range the encoder accepts for the option; libx264's `-crf`, for \
+         example, runs from 0 to 51.",
+        ex("ffmpeg -i input.mp4 -c:v libx264 -crf 60 output.mp4"),
+        ex("ffmpeg -i input.mp4 -c:v libx264 -crf 23 output.mp4"),
+    ),
+    entry(
+        "E405",
+        Error,
+        "Invalid encoder option value",
+        "The encoder does not know the value, such as a preset name it does not have.",
+        ex("ffmpeg -i input.mp4 -c:v libx264 -preset turbo output.mp4"),
+        ex("ffmpeg -i input.mp4 -c:v libx264 -preset fast output.mp4"),
+    ),
+    entry(
+        "E406",
+        Error,
+        "Mutually exclusive options",
+        "The two options ask for opposite behavior, like `-y` (overwrite outputs) and `-n` (never \
+         overwrite). Keep one.",
+        ex("ffmpeg -y -n -i input.mp4 output.mp4"),
+        ex("ffmpeg -y -i input.mp4 output.mp4"),
+    ),
+    entry(
+        "E410",
+        Error,
+        "`-ss` is not before `-to`",
+        "`-to` is a position, not a duration, so an end before the start leaves nothing to write. Use `-t` \
+         for a duration.",
+        ex("ffmpeg -i input.mp4 -ss 30 -to 10 output.mp4"),
+        ex("ffmpeg -i input.mp4 -ss 30 -t 10 output.mp4"),
+    ),
+    entry(
+        "E411",
+        Error,
+        "Zero or negative `-t` duration",
+        "`-t` sets how long to read or write; a duration of zero or less produces an empty file.",
+        ex("ffmpeg -i input.mp4 -t 0 output.mp4"),
+        ex("ffmpeg -i input.mp4 -t 10 output.mp4"),
+    ),
+    entry(
+        "E412",
+        Error,
+        "`-sseof` used on an output",
+        "`-sseof` seeks relative to the end of an input and is only valid before `-i`.",
+        ex("ffmpeg -i input.mp4 -sseof -10 output.mp4"),
+        ex("ffmpeg -sseof -10 -i input.mp4 output.mp4"),
+    ),
+    entry(
+        "E413",
+        Error,
+        "`-minrate` above `-maxrate`",
+        "The rate control bounds are reversed, which the encoder rejects.",
+        ex("ffmpeg -i input.mp4 -minrate 4M -maxrate 3M -bufsize 6M output.mp4"),
+        ex("ffmpeg -i input.mp4 -minrate 2M -maxrate 3M -bufsize 6M output.mp4"),
+    ),
+    entry(
+        "E414",
+        Error,
+        "Option not available in the target ffmpeg version",
+        "The option was added in a later release than the one the command targets; older ffmpeg stops \
+         with \"Unrecognized option\".",
+        ex("ffmpeg -i input.mp4 -fps_mode cfr output.mp4").targeting("5.0"),
+        ex("ffmpeg -i input.mp4 -vsync cfr output.mp4").targeting("5.0"),
+    ),
+    entry(
+        "W401",
+        Warning,
+        "Option not supported by the selected encoder",
+        "The option belongs to other encoders and is ignored by this one; `-cq` is an NVENC option, not \
+         a libx264 one.",
+        ex("ffmpeg -i input.mp4 -c:v libx264 -cq 20 output.mp4"),
+        ex("ffmpeg -i input.mp4 -c:v libx264 -crf 20 output.mp4"),
+    ),
+    entry(
+        "W402",
+        Warning,
+        "Unknown pixel format",
+        "The pixel format is not one ffmpeg knows; `ffmpeg -pix_fmts` lists them.",
+        ex("ffmpeg -i input.mp4 -pix_fmt yuv9000p output.mp4"),
+        ex("ffmpeg -i input.mp4 -pix_fmt yuv420p output.mp4"),
+    ),
+    entry(
+        "W403",
+        Warning,
+        "Option ignored because of a conflicting option",
+        "A later option overrides this one, such as a video codec on an output that disables video with \
+         `-vn`.",
+        ex("ffmpeg -i input.mp4 -vn -c:v libx264 output.mp4"),
+        ex("ffmpeg -i input.mp4 -c:v libx264 output.mp4"),
+    ),
+    entry(
+        "W404",
+        Hint,
+        "Flag given more than once",
+        "Repeating a flag has no further effect; the repeat can be removed.",
+        ex("ffmpeg -hide_banner -hide_banner -i input.mp4 output.mp4"),
+        ex("ffmpeg -hide_banner -i input.mp4 output.mp4"),
+    ),
+    entry(
+        "W410",
+        Warning,
+        "`-t` and `-to` on the same file",
+        "ffmpeg uses `-t` when both are given and ignores `-to`.",
+        ex("ffmpeg -i input.mp4 -t 5 -to 10 output.mp4"),
+        ex("ffmpeg -i input.mp4 -t 5 output.mp4"),
+    ),
+    entry(
+        "W411",
+        Warning,
+        "Input seek past the known input duration",
+        "The seek position is after the end of the input, so no frames are read.",
+        ex("ffmpeg -ss 10 -f lavfi -i sine=duration=5 output.wav"),
+        ex("ffmpeg -ss 2 -f lavfi -i sine=duration=5 output.wav"),
+    ),
+    entry(
+        "W412",
+        Warning,
+        "`-b:v` above `-maxrate`",
+        "The average bitrate cannot exceed the peak, so the encoder caps it at `-maxrate`.",
+        ex("ffmpeg -i input.mp4 -b:v 4M -maxrate 3M -bufsize 6M output.mp4"),
+        ex("ffmpeg -i input.mp4 -b:v 2M -maxrate 3M -bufsize 6M output.mp4"),
+    ),
+    entry(
+        "W413",
+        Warning,
+        "`-maxrate` without `-bufsize`",
+        "The encoder needs a buffer size to enforce a maximum rate; without one `-maxrate` is ignored or \
+         rejected depending on the encoder. Twice the maxrate is a common choice.",
+        ex("ffmpeg -i input.mp4 -c:v libx264 -maxrate 3M output.mp4"),
+        ex("ffmpeg -i input.mp4 -c:v libx264 -maxrate 3M -bufsize 6M output.mp4"),
+    ),
+    entry(
+        "W414",
+        Warning,
+        "Option deprecated in the target ffmpeg version",
+        "The option still works but is deprecated; the quick fix switches to its replacement.",
+        ex("ffmpeg -i input.mp4 -vsync 1 output.mp4"),
+        ex("ffmpeg -i input.mp4 -fps_mode cfr output.mp4"),
+    ),
+    entry(
+        "W415",
+        Warning,
+        "Unknown option close to a known one",
+        "The option is not one ffmpeg knows but is a small edit away from one, which ffmpeg would \
+         otherwise reject as \"Unrecognized option\".",
+        ex("ffmpeg -i input.mp4 -vcodc libx264 output.mp4"),
+        ex("ffmpeg -i input.mp4 -vcodec libx264 output.mp4"),
+    ),
+    // E500-E599: filters
+    entry(
+        "E502",
+        Warning,
+        "Unknown filter",
+        "ffmpeg stops with \"No such filter\" when a filter graph names a filter it does not have. The \
+         built-in list may lack filters of a custom build, so this is a warning; after importing the \
+         build's `-filters` list it is an error.",
+        ex("ffmpeg -i input.mp4 -vf scael=1280:720 output.mp4"),
+        ex("ffmpeg -i input.mp4 -vf scale=1280:720 output.mp4"),
+    ),
+    entry(
+        "E503",
+        Error,
+        "Filter not available in the target ffmpeg version",
+        "The filter was added in a later release than the one the command targets.",
+        ex("ffmpeg -i input.mp4 -af loudnorm output.mp4").targeting("3.0"),
+        ex("ffmpeg -i input.mp4 -af volume=2 output.mp4").targeting("3.0"),
+    ),
+    entry(
+        "W503",
+        Warning,
+        "Filter deprecated in the target ffmpeg version",
+        "The filter still works in the targeted release but is deprecated and will be removed; `scale2ref` \
+         gave way to the reference input of `scale` in 7.1.",
+        ex("ffmpeg -i input.mp4 -i logo.png -filter_complex \"[1:v][0:v]scale2ref[logo][base]\" -map \"[base]\" output.mp4"),
+        ex("ffmpeg -i input.mp4 -i logo.png -filter_complex \"[1:v][0:v]scale=rw:rh[logo]\" -map 0:v output.mp4"),
+    ),
+    // E600-E699: files
+    entry(
+        "E601",
+        Error,
+        "Output overwrites an input",
+        "ffmpeg cannot edit a file in place: it truncates the output before reading the input. Write to \
+         a new file and rename it afterwards. With `-n` nothing is overwritten and this is a warning.",
+        ex("ffmpeg -i clip.mp4 -c:v libx264 ./clip.mp4"),
+        ex("ffmpeg -i clip.mp4 -c:v libx264 clip-x264.mp4"),
+    ),
+    entry(
+        "E602",
+        Error,
+        "Same output path written twice",
+        "Two outputs of the command write the same file, and one replaces the other.",
+        ex("ffmpeg -i input.mp4 a.mp4 ./a.mp4"),
+        ex("ffmpeg -i input.mp4 a.mp4 b.mp4"),
+    ),
+    entry(
+        "W601",
+        Warning,
+        "Image output without a frame number pattern",
+        "Every frame is written to the same image file, leaving only the last one. Add a pattern such as \
+         `%03d`, or write a single frame with `-frames:v 1`.",
+        ex("ffmpeg -i input.mp4 -vf fps=1 thumb.png"),
+        ex("ffmpeg -i input.mp4 -vf fps=1 thumb%03d.png"),
+    ),
+    entry(
+        "W602",
+        Warning,
+        "`-r` instead of `-framerate` on an image sequence input",
+        "`-r` on an image sequence input is applied after the images are read at 25 fps. `-framerate` \
+         sets the rate the images are read at.",
+        ex("ffmpeg -r 24 -i img%04d.png output.mp4"),
+        ex("ffmpeg -framerate 24 -i img%04d.png output.mp4"),
+    ),
+    entry(
+        "W603",
+        Hint,
+        "Image sequence input without `-framerate`",
+        "Image sequences are read at 25 frames per second unless `-framerate` says otherwise.",
+        ex("ffmpeg -i img%04d.png output.mp4"),
+        ex("ffmpeg -framerate 25 -i img%04d.png output.mp4"),
+    ),
+    entry(
+        "W604",
+        Warning,
+        "Wildcard input without `-pattern_type glob`",
+        "ffmpeg reads `*` literally unless the input sets `-pattern_type glob`. Quote the pattern so the \
+         shell does not expand it.",
+        ex("ffmpeg -i '*.jpg' output.mp4"),
+        ex("ffmpeg -pattern_type glob -i '*.jpg' output.mp4"),
+    ),
+    // E700-E799: hardware acceleration
+    entry(
+        "E701",
+        Error,
+        "GPU frames passed to a CPU filter or encoder",
+        "With `-hwaccel_output_format` decoded frames stay in GPU memory, where CPU filters and encoders \
+         cannot read them. Use the GPU variant of the filter or `hwdownload` the frames first.",
+        ex("ffmpeg -hwaccel cuda -hwaccel_output_format cuda -i in.mp4 -vf scale=640:360 -c:v h264_nvenc out.mp4"),
+        ex("ffmpeg -hwaccel cuda -hwaccel_output_format cuda -i in.mp4 -vf scale_cuda=640:360 -c:v h264_nvenc out.mp4"),
+    ),
+    entry(
+        "E702",
+        Error,
+        "CPU frames passed to a GPU-only filter",
+        "The filter only takes frames in GPU memory. Decode to the GPU with `-hwaccel` and \
+         `-hwaccel_output_format`, or `hwupload` the frames first.",
+        ex("ffmpeg -i in.mp4 -vf scale_cuda=640:360 -c:v h264_nvenc out.mp4"),
+        ex("ffmpeg -hwaccel cuda -hwaccel_output_format cuda -i in.mp4 -vf scale_cuda=640:360 -c:v h264_nvenc out.mp4"),
+    ),
+    entry(
+        "E703",
+        Error,
+        "Frames from one hardware family passed to another",
+        "GPU frames of one API (such as CUDA) cannot be used by filters or encoders of another (such as \
+         VAAPI) without mapping them.",
+        ex("ffmpeg -hwaccel cuda -hwaccel_output_format cuda -i in.mp4 -c:v h264_vaapi out.mp4"),
+        ex("ffmpeg -hwaccel cuda -hwaccel_output_format cuda -i in.mp4 -c:v h264_nvenc out.mp4"),
+    ),
+    entry(
+        "W701",
+        Warning,
+        "`-hwaccel_output_format` without `-hwaccel`",
+        "The output format of hardware decoding has no effect when hardware decoding is not enabled.",
+        ex("ffmpeg -hwaccel_output_format cuda -i in.mp4 out.mp4"),
+        ex("ffmpeg -hwaccel cuda -hwaccel_output_format cuda -i in.mp4 out.mp4"),
+    ),
+    entry(
+        "W702",
+        Warning,
+        "Unknown `-hwaccel` method",
+        "The method is not one ffmpeg knows; `ffmpeg -hwaccels` lists the ones a build supports.",
+        ex("ffmpeg -hwaccel nvidia -i in.mp4 out.mp4"),
+        ex("ffmpeg -hwaccel cuda -i in.mp4 out.mp4"),
+    ),
+    // W100-W199: quality
+    entry(
+        "W101",
+        Warning,
+        "Extremely high bitrate",
+        "The bitrate is far above what any delivery format needs, making large files with no visible gain.",
+        ex("ffmpeg -i input.mp4 -b:v 100M output.mp4"),
+        ex("ffmpeg -i input.mp4 -b:v 8M output.mp4"),
+    ),
+    entry(
+        "W102",
+        Hint,
+        "Lossy encoder without a bitrate or quality setting",
+        "Without `-crf`, `-q` or a bitrate the encoder falls back to its default quality, which may not be \
+         what the output needs.",
+        ex("ffmpeg -i input.mp4 -c:v libx264 output.mp4"),
+        ex("ffmpeg -i input.mp4 -c:v libx264 -crf 23 output.mp4"),
+    ),
+    entry(
+        "W103",
+        Hint,
+        "Lossy stream re-encoded to the same codec",
+        "Decoding and encoding again with the same codec and no filters only loses quality, and a new bitrate \
+         or quality setting cannot recover what the source lost. Copy the stream with `-c copy`.",
+        ex("ffmpeg -i song.mp3 -c:a libmp3lame output.mp3"),
+        ex("ffmpeg -i song.mp3 -c:a copy output.mp3"),
+    ),
+    entry(
+        "W104",
+        Hint,
+        "Lossy audio transcoded to another lossy codec",
+        "Each lossy encode adds artifacts. Copy the audio when the container allows it, or start from a \
+         lossless source.",
+        ex("ffmpeg -i song.mp3 -c:a aac output.mp4"),
+        ex("ffmpeg -i song.mp3 -c:a copy output.mp4"),
+    ),
+    entry(
+        "W105",
+        Hint,
+        "Lossless source compressed at low quality",
+        "The source is lossless, and the bitrate chosen throws most of that quality away.",
+        ex("ffmpeg -i song.flac -c:a aac -b:a 64k output.mp4"),
+        ex("ffmpeg -i song.flac -c:a aac -b:a 192k output.mp4"),
+    ),
+    entry(
+        "W106",
+        Warning,
+        "Extremely low bitrate",
+        "Bitrates are in bits per second, so `128` is 128 bit/s. This is usually a missing `k` suffix. \
+         A bitrate of 0 is not reported: with `-crf` it selects constant quality for libvpx-vp9 and libaom.",
+        ex("ffmpeg -i input.mp4 -b:a 128 output.mp4"),
+        ex("ffmpeg -i input.mp4 -b:a 128k output.mp4"),
+    ),
+    entry(
+        "W107",
+        Warning,
+        "Bitrate too low for the output",
+        "The bitrate is below what the codec needs for the output's resolution and frame rate, so the \
+         video will be visibly blocky.",
+        ex("ffmpeg -i input.mp4 -c:v libx264 -b:v 200k -s 1920x1080 output.mp4"),
+        ex("ffmpeg -i input.mp4 -c:v libx264 -b:v 5M -s 1920x1080 output.mp4"),
+    ),
+    entry(
+        "W108",
+        Hint,
+        "Bitrate higher than the codec needs",
+        "The bitrate is well above what the codec needs for the output's resolution, making a larger file \
+         without a visible improvement.",
+        ex("ffmpeg -i input.mp4 -c:v libx265 -b:v 40M -s 1280x720 output.mp4"),
+        ex("ffmpeg -i input.mp4 -c:v libx265 -b:v 3M -s 1280x720 output.mp4"),
+    ),
+    entry(
+        "W109",
+        Hint,
+        "Output frame size larger than the source",
+        "Upscaling makes the file bigger without adding detail. Reported when a media manifest gives the \
+         size of the source.",
+        ex("ffmpeg -i clip.mp4 -vf scale=1920:1080 output.mp4").with_video_size(1280, 720),
+        ex("ffmpeg -i clip.mp4 -vf scale=1280:720 output.mp4").with_video_size(1280, 720),
+    ),
+    // I000-I099: information
+    DiagnosticInfo {
+        code: "I001",
+        severity: Hint,
+        title: "Pipeline overview",
+        explanation: "A diagram of how the streams of the inputs flow through filters and encoders to the outputs.",
+        bad: ex("ffmpeg -i input.mp4 -c:v libx264 output.mp4"),
+        good: None,
+    },
+    entry(
+        "I002",
+        Info,
+        "Seeking with stream copy snaps to keyframes",
+        "Copied streams can only start at a keyframe, so the output starts at the keyframe before the seek \
+         position. Re-encode for a frame-accurate start.",
+        ex("ffmpeg -ss 10 -i input.mp4 -c copy output.mp4"),
+        ex("ffmpeg -ss 10 -i input.mp4 -c:v libx264 -c:a copy output.mp4"),
+    ),
+    entry(
+        "I003",
+        Hint,
+        "Unused suppression pragma",
+        "A `# ffmpeg-lint: disable` comment suppresses a code that the commands it covers do not report. \
+         Remove the pragma, or the codes that no longer apply.",
+        ex("# ffmpeg-lint: disable-next-line=W101\nffmpeg -i input.mp4 output.mp4"),
+        ex("# ffmpeg-lint: disable-next-line=W101\nffmpeg -i input.mp4 -b:v 100M output.mp4"),
+    ),
+];
+
+/// Catalog entry of a code, e.g. "E201"
+pub fn lookup(code: &str) -> Option<&'static DiagnosticInfo> {
+    CATALOG.iter().find(|info| info.code.eq_ignore_ascii_case(code))
+}
+
+fn severity_name(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "info",
+        Severity::Hint => "hint",
+    }
+}
+
+fn example_block(label: &str, example: &Example) -> String {
+    let mut context = Vec::new();
+    if let Some(version) = example.ffmpeg_version {
+        context.push(format!("targeting ffmpeg {}", version));
+    }
+    if let Some((width, height)) = example.video_size {
+        context.push(format!("{}x{} video input", width, height));
+    }
+    let context = if context.is_empty() { String::new() } else { format!(" ({})", context.join(", ")) };
+    format!("{}{}:\n\n```sh\n{}\n```\n", label, context, example.command)
+}
+
+/// Long-form explanation of a code as markdown
+pub fn explain(info: &DiagnosticInfo) -> DiagnosticRich {
+    let mut markdown = format!(
+        "## {}: {}\n\n**Severity:** {}\n\n{}\n\n",
+        info.code,
+        info.title,
+        severity_name(&info.severity),
+        info.explanation
+    );
+    markdown.push_str(&example_block("Reported for", &info.bad));
+    if let Some(good) = &info.good {
+        markdown.push('\n');
+        markdown.push_str(&example_block("Not reported for", good));
+    }
+    DiagnosticRich { blocks: vec![RichBlock::MarkdownGfm { markdown }] }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::AnalysisOptions;
+    use crate::codec_db::FfmpegVersion;
+    use crate::handler::analyze_content;
+    use shared_types::{DiagnosticMessage, MediaFile, MediaStream, StreamType};
+    
+    /// Sources of the modules that report diagnostics
+    const SOURCES: &[&str] = &[
+        include_str!("analyzer.rs"),
+        include_str!("stream_tracker.rs"),
+        include_str!("option_rules.rs"),
+        include_str!("hwaccel.rs"),
+        include_str!("versions.rs"),
+        include_str!("suppressions.rs"),
+        include_str!("handler.rs"),
+    ];
+    
+    /// Code literals such as `"E201"` outside the tests of a source file
+    fn emitted_codes(source: &str) -> Vec<&str> {
+        let code = source.split("#[cfg(test)]").next().unwrap_or(source);
+        code.split('"')
+            .filter(|s| s.len() == 4 && s.starts_with(['E', 'W', 'I']) && s[1..].bytes().all(|b| b.is_ascii_digit()))
+            .collect()
+    }
+    
+    fn analyze(example: &Example) -> Vec<DiagnosticMessage> {
+        let mut options = AnalysisOptions {
+            ffmpeg_version: example.ffmpeg_version.map(|v| FfmpegVersion::parse(v).unwrap()),
+            ..AnalysisOptions::default()
+        };
+        if let Some((width, height)) = example.video_size {
+            let path = example.command.split_whitespace().skip_while(|w| *w != "-i").nth(1).unwrap();
+            let stream = MediaStream {
+                stream_type: StreamType::Video,
+                codec: Some("h264".to_string()),
+                width: Some(width),
+                height: Some(height),
+                frame_rate: Some(25.0),
+                sample_rate: None,
+                channels: None,
+            };
+            options.media = vec![MediaFile { path: path.to_string(), duration: None, streams: vec![stream] }];
+        }
+        analyze_content(example.command, 1, 0, options).messages
+    }
+    
+    #[test]
+    fn test_emitted_codes_are_registered() {
+        for code in SOURCES.iter().flat_map(|source| emitted_codes(source)) {
+            assert!(lookup(code).is_some(), "{} is reported but not in the catalog", code);
+        }
+        for (idx, info) in CATALOG.iter().enumerate() {
+            assert!(!CATALOG[..idx].iter().any(|other| other.code == info.code), "{} is registered twice", info.code);
+        }
+    }
+    
+    #[test]
+    fn test_examples_report_their_code() {
+        for info in CATALOG {
+            let bad = &info.bad;
+            let reported = analyze(bad);
+            let diagnostic = reported
+                .iter()
+                .find(|d| d.code == info.code)
+                .unwrap_or_else(|| panic!("{} not reported for {:?}", info.code, bad.command));
+            assert_eq!(
+                severity_name(&diagnostic.severity),
+                severity_name(&info.severity),
+                "{} reported at another severity for {:?}",
+                info.code,
+                bad.command
+            );
+            if let Some(good) = &info.good {
+                assert!(
+                    !analyze(good).iter().any(|d| d.code == info.code),
+                    "{} reported for the good example {:?}",
+                    info.code,
+                    good.command
+                );
+            }
+        }
+    }
+    
+    #[test]
+    fn test_explain() {
+        let info = lookup("e201").unwrap();
+        assert_eq!(info.code, "E201");
+        let RichBlock::MarkdownGfm { markdown } = &explain(info).blocks[0] else { panic!("expected markdown") };
+        assert!(markdown.starts_with("## E201: Codec not supported in the output container"));
+        assert!(markdown.contains("**Severity:** error"));
+        assert!(markdown.contains("```sh\nffmpeg -i input.mp4 -c:v vp9 output.mp4\n```"));
+        assert!(lookup("E999").is_none());
+    }
+}
@@ -0,0 +1,231 @@
+use crate::messages::{Locale, Message};
+use shared_types::{CodeAction, DiagnosticMessage, SourceCodeSpan, SpanRole, TextEdit};
+
+/// Analyzed text, numbered like the parser: lines from `line_offset`, columns from
+/// `column_offset` on the first line and from 0 on the others
+pub struct Document<'a> {
+    content: &'a str,
+    line_offset: usize,
+    column_offset: usize,
+}
+
+impl<'a> Document<'a> {
+    pub fn new(content: &'a str, line_offset: usize, column_offset: usize) -> Self {
+        Document { content, line_offset, column_offset }
+    }
+    
+    /// Byte offsets of the start of a line and of a column on it
+    fn offsets(&self, line: usize, column: usize) -> Option<(usize, usize)> {
+        let idx = line.checked_sub(self.line_offset)?;
+        let line_start: usize = self.content.split('\n').take(idx).map(|l| l.len() + 1).sum();
+        let text = self.content.split('\n').nth(idx)?;
+        let column = column.checked_sub(if idx == 0 { self.column_offset } else { 0 })?;
+        (column <= text.len()).then_some((line_start, line_start + column))
+    }
+    
+    fn range(&self, span: &SourceCodeSpan) -> Option<(usize, usize)> {
+        let (_, start) = self.offsets(span.start_line, span.start_column)?;
+        let (_, end) = self.offsets(span.end_line, span.end_column)?;
+        (start <= end).then_some((start, end))
+    }
+    
+    pub fn text(&self, span: &SourceCodeSpan) -> Option<&'a str> {
+        let (start, end) = self.range(span)?;
+        self.content.get(start..end)
+    }
+    
+    /// Widen a span over the whitespace before it on its line, so removing it leaves no gap
+    fn with_leading_whitespace(&self, span: &SourceCodeSpan) -> SourceCodeSpan {
+        let Some((line_start, start)) = self.offsets(span.start_line, span.start_column) else { return span.clone() };
+        let before = &self.content[line_start..start];
+        let whitespace = before.len() - before.trim_end().len();
+        SourceCodeSpan { start_column: span.start_column - whitespace, ..span.clone() }
+    }
+    
+    /// The content with non-overlapping edits applied; None if an edit is outside the content
+    pub fn apply(&self, edits: &[TextEdit]) -> Option<String> {
+        let mut ranges = edits
+            .iter()
+            .map(|edit| self.range(&edit.span).map(|(start, end)| (start, end, edit.new_text.as_str())))
+            .collect::<Option<Vec<_>>>()?;
+        ranges.sort_by_key(|(start, end, _)| (*start, *end));
+        
+        let mut result = String::with_capacity(self.content.len());
+        let mut position = 0;
+        for (start, end, new_text) in ranges {
+            if start < position {
+                return None;
+            }
+            result.push_str(&self.content[position..start]);
+            result.push_str(new_text);
+            position = end;
+        }
+        result.push_str(&self.content[position..]);
+        Some(result)
+    }
+}
+
+fn overlaps(a: &SourceCodeSpan, b: &SourceCodeSpan) -> bool {
+    (a.start_line, a.start_column) <= (b.end_line, b.end_column) && (b.start_line, b.start_column) <= (a.end_line, a.end_column)
+}
+
+/// Fixes for the diagnostics whose target overlaps `range`, one per suggestion span; a
+/// diagnostic's first suggestion is its preferred fix
+pub fn code_actions(
+    document: &Document,
+    diagnostics: &[DiagnosticMessage],
+    range: &SourceCodeSpan,
+    code: Option<&str>,
+    locale: Locale,
+) -> Vec<CodeAction> {
+    let mut actions = Vec::new();
+    for diagnostic in diagnostics {
+        if code.is_some_and(|c| c != diagnostic.code) {
+            continue;
+        }
+        let targeted = diagnostic.spans.iter().any(|s| matches!(s.role, SpanRole::Target) && overlaps(&s.span, range));
+        if !targeted {
+            continue;
+        }
+        
+        let suggestions = diagnostic.spans.iter().filter_map(|s| match &s.role {
+            SpanRole::Suggestion { replacement } => Some((&s.span, replacement)),
+            _ => None,
+        });
+        for (span, replacement) in suggestions {
+            let Some(current) = document.text(span) else { continue };
+            let (title, span) = if replacement.is_empty() {
+                (Message::new("action-remove").arg("text", current), document.with_leading_whitespace(span))
+            } else if current.is_empty() {
+                (Message::new("action-insert").arg("text", replacement.trim()), span.clone())
+            } else {
+                (Message::new("action-replace").arg("text", current).arg("replacement", replacement), span.clone())
+            };
+            actions.push(CodeAction {
+                title: title.render(locale),
+                diagnostic_code: diagnostic.code.clone(),
+                edits: vec![TextEdit { span, new_text: replacement.clone() }],
+                is_preferred: !actions.iter().any(|a: &CodeAction| a.diagnostic_code == diagnostic.code && a.is_preferred),
+            });
+        }
+    }
+    actions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::AnalysisOptions;
+    use crate::handler::analyze_content;
+    
+    const WHOLE_DOCUMENT: SourceCodeSpan = SourceCodeSpan { start_line: 0, start_column: 0, end_line: usize::MAX, end_column: 0 };
+    
+    fn analyze(content: &str) -> Vec<DiagnosticMessage> {
+        analyze_content(content, 1, 0, AnalysisOptions::default()).messages
+    }
+    
+    fn count(diagnostics: &[DiagnosticMessage], code: &str) -> usize {
+        diagnostics.iter().filter(|d| d.code == code).count()
+    }
+    
+    /// Apply every fix offered for `content` and check that re-analyzing clears its diagnostic
+    fn check_fixes(content: &str) -> Vec<(String, String)> {
+        let document = Document::new(content, 1, 0);
+        let diagnostics = analyze(content);
+        let actions = code_actions(&document, &diagnostics, &WHOLE_DOCUMENT, None, Locale::English);
+        assert!(!actions.is_empty(), "no fixes offered for {:?}", content);
+        
+        actions
+            .iter()
+            .map(|action| {
+                let fixed = document.apply(&action.edits).expect("edits apply");
+                let code = &action.diagnostic_code;
+                assert!(
+                    count(&analyze(&fixed), code) < count(&diagnostics, code),
+                    "'{}' did not clear {} in {:?}: {:?}",
+                    action.title,
+                    code,
+                    content,
+                    fixed
+                );
+                (code.clone(), fixed)
+            })
+            .collect()
+    }
+    
+    fn fixed(content: &str, code: &str) -> Vec<String> {
+        check_fixes(content).into_iter().filter(|(c, _)| c == code).map(|(_, fixed)| fixed).collect()
+    }
+    
+    #[test]
+    fn test_fixes_clear_their_diagnostics() {
+        let commands = [
+            "ffmpeg -i input.mp4 -c:v vp9 out.mp4",
+            "ffmpeg -i input.mp4 -c:v libvpx-vp9 -f mp4 out.bin",
+            "ffmpeg -i input.mp4 -s 1920 out.mp4",
+            "ffmpeg -y -n -i input.mp4 output.mp4",
+            "ffmpeg -i input.mp4 -vn -c:v libx264 output.mp4",
+            "ffmpeg -i input.mp4 -t 5 -to 10 output.mp4",
+            "ffmpeg -i song.wav -c copy out.mp4",
+            "ffmpeg -i song.wav -c:a copy out.mp4",
+            "ffmpeg -i movie.mkv -i subs.srt -c copy out.mp4",
+            "ffmpeg -i movie.mp4 -i subs.srt -c:v copy -c:s copy out.mp4",
+            "ffmpeg -i input.mp4 -c:v hevc out.mp4",
+            "ffmpeg -c:v libx264 -i input.mp4 out.mkv",
+            "ffmpeg -i input.mp4 -c:a libopus -ar 44100 output.webm",
+            "ffmpeg -r 24 -i frames/img%04d.png -c:v libx264 -crf 20 out.mp4",
+            "ffmpeg -i song.mp3 -c:a libmp3lame out.mp3",
+            "ffmpeg -i song.flac -c:a aac -b:a 64k out.mp4",
+            "ffmpeg -i input.mp4 -c:v libx264 -b:v 4M -maxrate 3M output.mp4",
+            "ffmpeg -i input.mp4 -c:v libx264 -b:v 200k -s 1920x1080 -r 30 output.mp4",
+            "ffmpeg -hwaccel cuda -hwaccel_output_format cuda -i in.mp4 -vf drawtext=text=hi -c:v h264_nvenc out.mp4",
+            "ffmpeg -i in.mp4 -vsync 1 out.mp4",
+            "ffmpeg -i in.wav -c:a opus out.ogg",
+            "ffmpeg -i input.mp4 -c:v libx246 out.mp4",
+            "ffmpeg -i input.mp4 -c:v h.264 out.mp4",
+            "ffmpeg -i input.mp4 -vf scael=1280:720 out.mp4",
+            "ffmpeg -i input.mp4 -vcodc libx264 out.mp4",
+            "ffmpeg -i input.mp4 -f mkv out.mkv",
+            "# ffmpeg-lint: disable-next-line=W101\nffmpeg -i input.mp4 output.mp4",
+        ];
+        for command in commands {
+            check_fixes(command);
+        }
+    }
+    
+    #[test]
+    fn test_codec_and_container_fixes() {
+        let fixes = fixed("ffmpeg -i input.mp4 -c:v vp9 out.mp4", "E201");
+        assert!(fixes.contains(&"ffmpeg -i input.mp4 -c:v libx264 out.mp4".to_string()));
+        assert!(fixes.contains(&"ffmpeg -i input.mp4 -c:v vp9 out.webm".to_string()));
+        
+        let fixes = fixed("ffmpeg -i input.mp4 -c:v libvpx-vp9 -f mp4 out.bin", "E201");
+        assert!(fixes.contains(&"ffmpeg -i input.mp4 -c:v libvpx-vp9 -f webm out.bin".to_string()));
+        
+        assert_eq!(fixed("ffmpeg -i input.mp4 -s 1920 out.mp4", "E401"), vec!["ffmpeg -i input.mp4 -s 1920x1080 out.mp4"]);
+        assert_eq!(fixed("ffmpeg -i song.wav -c copy out.mp4", "E213"), vec!["ffmpeg -i song.wav -c copy -c:a aac out.mp4"]);
+    }
+    
+    #[test]
+    fn test_removal_fixes() {
+        assert_eq!(fixed("ffmpeg -y -n -i input.mp4 output.mp4", "E406"), vec!["ffmpeg -y -i input.mp4 output.mp4"]);
+        assert_eq!(fixed("ffmpeg -i input.mp4 -t 5 -to 10 output.mp4", "W410"), vec!["ffmpeg -i input.mp4 -t 5 output.mp4"]);
+    }
+    
+    #[test]
+    fn test_actions_in_range() {
+        let content = "ffmpeg -y -n -i input.mp4 -s 1920 out.mp4";
+        let document = Document::new(content, 1, 0);
+        let diagnostics = analyze(content);
+        let at = |column| SourceCodeSpan { start_line: 1, start_column: column, end_line: 1, end_column: column };
+        
+        let actions = code_actions(&document, &diagnostics, &at(30), None, Locale::English);
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Replace `1920` with `1920x1080`");
+        assert!(actions[0].is_preferred);
+        
+        let actions = code_actions(&document, &diagnostics, &at(11), None, Locale::English);
+        assert_eq!(actions.iter().map(|a| a.title.as_str()).collect::<Vec<_>>(), vec!["Remove `-n`"]);
+        assert!(code_actions(&document, &diagnostics, &WHOLE_DOCUMENT, Some("E406"), Locale::English).iter().all(|a| a.diagnostic_code == "E406"));
+    }
+}
@@ -1,5 +1,67 @@
 use shared_types::StreamType;
 use std::collections::HashMap;
+use std::fmt;
+
+/// An ffmpeg release, compared by major and minor version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FfmpegVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl FfmpegVersion {
+    pub const fn new(major: u32, minor: u32) -> Self {
+        FfmpegVersion { major, minor }
+    }
+    
+    /// Parse `6.1`, `7`, `6.1.2`, `n7.0` or the first line of `ffmpeg -version`
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim();
+        let version = version.strip_prefix("ffmpeg version ").unwrap_or(version);
+        let version = version.strip_prefix('n').unwrap_or(version);
+        let mut parts = version.split(|c: char| !c.is_ascii_digit());
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().and_then(|m| m.parse().ok()).unwrap_or(0);
+        Some(FfmpegVersion { major, minor })
+    }
+}
+
+impl fmt::Display for FfmpegVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// The ffmpeg releases that provide a codec, filter or option
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Availability {
+    pub since: Option<FfmpegVersion>,
+    pub deprecated_in: Option<FfmpegVersion>,
+    pub removed_in: Option<FfmpegVersion>,
+    /// What to use instead once deprecated or removed
+    pub replacement: Option<&'static str>,
+}
+
+impl Availability {
+    /// Whether a release provides it at all
+    pub fn is_available_in(&self, version: FfmpegVersion) -> bool {
+        self.since.is_none_or(|since| since <= version) && self.removed_in.is_none_or(|removed| version < removed)
+    }
+    
+    /// Whether the latest release still provides it
+    pub fn is_current(&self) -> bool {
+        self.removed_in.is_none()
+    }
+    
+    /// Whether it is deprecated in a release, or in the latest release when `version` is None
+    pub fn is_deprecated_in(&self, version: Option<FfmpegVersion>) -> bool {
+        match (self.deprecated_in.or(self.removed_in), version) {
+            (Some(deprecated), Some(version)) => deprecated <= version,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
 
 /// Codec information
 #[derive(Debug, Clone)]
@@ -8,6 +70,11 @@ pub struct CodecInfo {
     pub stream_type: StreamType,
     pub is_encoder: bool,
     pub is_decoder: bool,
+    pub availability: Availability,
+    /// Encoder that ffmpeg refuses without `-strict experimental`
+    pub experimental: bool,
+    /// Release in which a formerly experimental encoder became stable
+    pub experimental_until: Option<FfmpegVersion>,
 }
 
 /// Format (container) information
@@ -16,7 +83,12 @@ pub struct FormatInfo {
     pub name: String,
     pub supported_video_codecs: Vec<String>,
     pub supported_audio_codecs: Vec<String>,
+    /// Subtitle codecs the muxer can store, preferred encoder first
+    pub supported_subtitle_codecs: Vec<String>,
     pub extensions: Vec<String>,
+    /// Whether the codec lists are exhaustive; muxers imported from an ffmpeg listing only
+    /// know their default codecs, so other codecs are not reported as unsupported
+    pub codecs_complete: bool,
 }
 
 /// Filter information
@@ -25,62 +97,242 @@ pub struct FilterInfo {
     pub name: String,
     pub input_type: StreamType,
     pub output_type: StreamType,
+    /// English summary; an imported database copies it from the `-filters` listing
     pub description: String,
+    pub availability: Availability,
+    /// Number of input pads: 0 for sources, None for a number set by a parameter (amix, concat)
+    pub input_pads: Option<usize>,
+    /// Parameters in positional order; empty when unknown
+    pub params: Vec<FilterParamInfo>,
+}
+
+/// A filter parameter
+#[derive(Debug, Clone)]
+pub struct FilterParamInfo {
+    pub name: String,
+    /// Kind of value, e.g. "expression", "integer" or "fast|slow"
+    pub value: String,
+    pub description: String,
+}
+
+/// A hardware acceleration API and the frames it keeps in GPU memory
+#[derive(Debug, Clone)]
+pub struct HardwareFamily {
+    pub name: String,
+    /// `-hwaccel` values that select this family
+    pub hwaccel_names: Vec<String>,
+    /// Pixel format of frames left in GPU memory (`-hwaccel_output_format`)
+    pub frame_format: String,
+    pub platform: String,
+    /// Whether encoders of this family also accept frames from system memory
+    pub encoders_accept_cpu_frames: bool,
+    /// Encoders of other families that read this family's GPU frames directly
+    pub frame_consumers: Vec<String>,
+}
+
+/// Accepted values for an encoder private option
+#[derive(Debug, Clone)]
+pub enum OptionValues {
+    /// Inclusive numeric range
+    Range { min: f64, max: f64 },
+    /// One of a fixed set of names
+    OneOf(Vec<String>),
+}
+
+/// Encoder private option (e.g. `-crf`, `-preset`)
+#[derive(Debug, Clone)]
+pub struct EncoderOptionInfo {
+    pub name: String,
+    pub values: OptionValues,
 }
 
+/// Pixel formats allowed by an encoder profile
+#[derive(Debug, Clone)]
+pub struct ProfileInfo {
+    pub name: String,
+    pub max_bit_depth: u8,
+    /// Allowed chroma subsampling, e.g. "420", "422", "444"
+    pub chroma: Vec<String>,
+}
+
+/// Private options and profiles accepted by an encoder
+#[derive(Debug, Clone)]
+pub struct EncoderOptionsInfo {
+    pub encoder: String,
+    pub is_lossy: bool,
+    pub options: Vec<EncoderOptionInfo>,
+    pub profiles: Vec<ProfileInfo>,
+}
+
+impl EncoderOptionsInfo {
+    pub fn get_option(&self, name: &str) -> Option<&EncoderOptionInfo> {
+        self.options.iter().find(|o| o.name == name)
+    }
+    
+    pub fn get_profile(&self, name: &str) -> Option<&ProfileInfo> {
+        self.profiles.iter().find(|p| p.name == name)
+    }
+}
+
+/// Pixel format information
+#[derive(Debug, Clone)]
+pub struct PixelFormatInfo {
+    pub name: String,
+    pub bit_depth: u8,
+    /// Chroma subsampling: "420", "422", "444", "440", "411", "gray" or "rgb"
+    pub chroma: String,
+    pub has_alpha: bool,
+}
+
+/// Sample rate and channel constraints of an audio encoder
+#[derive(Debug, Clone)]
+pub struct AudioEncoderConstraints {
+    pub encoder: String,
+    /// Supported sample rates in Hz (empty means any rate up to `max_sample_rate`)
+    pub sample_rates: Vec<u32>,
+    pub max_sample_rate: Option<u32>,
+    pub max_channels: u32,
+}
+
+impl AudioEncoderConstraints {
+    pub fn supports_sample_rate(&self, rate: u32) -> bool {
+        let in_list = self.sample_rates.is_empty() || self.sample_rates.contains(&rate);
+        let below_max = self.max_sample_rate.is_none_or(|max| rate <= max);
+        in_list && below_max
+    }
+    
+    /// Closest supported sample rate to `rate`
+    pub fn nearest_sample_rate(&self, rate: u32) -> Option<u32> {
+        if self.sample_rates.is_empty() {
+            return self.max_sample_rate.map(|max| rate.min(max));
+        }
+        self.sample_rates
+            .iter()
+            .copied()
+            .filter(|r| self.max_sample_rate.is_none_or(|max| *r <= max))
+            .min_by_key(|r| r.abs_diff(rate))
+    }
+}
+
+/// Encoder private options checked by the analyzer; anything else is passed through
+pub const KNOWN_ENCODER_OPTIONS: &[&str] = &[
+    "crf", "preset", "tune", "profile", "level", "qp", "cq", "deadline", "cpu-used",
+];
+
+/// Options of every encoder (AVCodecContext), checked only against encoders that list them
+pub const GENERIC_ENCODER_OPTIONS: &[&str] = &["profile", "level"];
+
+/// `-c copy` passes packets through without decoding or encoding, so it is not a codec in the database
+pub const STREAM_COPY: &str = "copy";
+
+/// Subtitle codecs stored as images; they cannot be converted to text codecs
+pub const BITMAP_SUBTITLE_CODECS: &[&str] = &["dvd_subtitle", "dvb_subtitle", "hdmv_pgs_subtitle"];
+
+/// Codecs that preserve the decoded signal exactly
+pub const LOSSLESS_CODECS: &[&str] = &[
+    "flac", "alac", "pcm_s16le", "pcm_s24le", "pcm_f32le", "png", "rawvideo", "ffv1", "huffyuv", "utvideo",
+];
+
 /// Static codec database
+#[derive(Clone)]
 pub struct CodecDatabase {
-    codecs: HashMap<String, CodecInfo>,
-    formats: HashMap<String, FormatInfo>,
-    filters: HashMap<String, FilterInfo>,
+    pub(crate) codecs: HashMap<String, CodecInfo>,
+    pub(crate) formats: HashMap<String, FormatInfo>,
+    pub(crate) filters: HashMap<String, FilterInfo>,
+    /// Whether `filters` holds a build's full `-filters` listing, so a missing filter certainly does not exist
+    pub(crate) filters_complete: bool,
+    encoder_options: HashMap<String, EncoderOptionsInfo>,
+    pixel_formats: HashMap<String, PixelFormatInfo>,
+    /// Pixel formats accepted by each encoder (encoders not listed accept any format)
+    encoder_pixel_formats: HashMap<String, Vec<String>>,
+    audio_constraints: HashMap<String, AudioEncoderConstraints>,
+    /// Channel count for named channel layouts (e.g. "5.1" -> 6)
+    channel_layouts: HashMap<String, u32>,
+    /// Codec (format) implemented by each encoder library, e.g. "libx264" -> "h264"
+    pub(crate) encoder_codecs: HashMap<String, String>,
+    /// Codec implied by a single-codec file extension, e.g. "flac" -> flac audio
+    extension_codecs: HashMap<String, (StreamType, String)>,
+    /// Bits per pixel per frame from barely acceptable to visually transparent, by codec
+    bits_per_pixel: HashMap<String, (f64, f64)>,
+    hardware_families: HashMap<String, HardwareFamily>,
+    /// Hardware family of each hardware codec and filter (e.g. "h264_nvenc" -> "cuda")
+    hardware_components: HashMap<String, String>,
 }
 
 impl CodecDatabase {
     pub fn new() -> Self {
-        let mut db = CodecDatabase {
-            codecs: HashMap::new(),
-            formats: HashMap::new(),
-            filters: HashMap::new(),
-        };
+        let mut db = Self::empty();
         
         db.init_codecs();
         db.init_formats();
         db.init_filters();
+        db.init_encoder_options();
+        db.init_pixel_formats();
+        db.init_audio_constraints();
+        db.init_codec_identities();
+        db.init_bitrate_guidelines();
+        db.init_hardware();
+        db.init_filter_params();
+        db.init_versions();
         
         db
     }
     
+    /// Database with no entries, to be filled from ffmpeg listings (see [`crate::codec_import`])
+    pub fn empty() -> Self {
+        CodecDatabase {
+            codecs: HashMap::new(),
+            formats: HashMap::new(),
+            filters: HashMap::new(),
+            filters_complete: false,
+            encoder_options: HashMap::new(),
+            pixel_formats: HashMap::new(),
+            encoder_pixel_formats: HashMap::new(),
+            audio_constraints: HashMap::new(),
+            channel_layouts: HashMap::new(),
+            encoder_codecs: HashMap::new(),
+            extension_codecs: HashMap::new(),
+            bits_per_pixel: HashMap::new(),
+            hardware_families: HashMap::new(),
+            hardware_components: HashMap::new(),
+        }
+    }
+    
     fn init_codecs(&mut self) {
-        // Video codecs
+        // Video codecs as (name, is_encoder, is_decoder). ffmpeg has native decoders for
+        // h264/hevc/vp8/vp9/av1 but encodes them only through external libraries
         let video_codecs = vec![
-            "libx264", "libx265", "h264", "hevc", "vp8", "vp9", "av1", "libaom-av1",
-            "mpeg4", "mpeg2video", "libvpx", "libvpx-vp9", "prores", "dnxhd",
-            "mjpeg", "png", "rawvideo", "copy",
+            ("libx264", true, false), ("libx265", true, false), ("h264", false, true), ("hevc", false, true),
+            ("vp8", false, true), ("vp9", false, true), ("av1", false, true), ("libaom-av1", true, true),
+            ("mpeg4", true, true), ("mpeg2video", true, true), ("libvpx", true, true), ("libvpx-vp9", true, true),
+            ("prores", true, true), ("dnxhd", true, true), ("mjpeg", true, true), ("png", true, true),
+            ("rawvideo", true, true),
         ];
         
-        for codec in video_codecs {
-            self.codecs.insert(codec.to_string(), CodecInfo {
-                name: codec.to_string(),
-                stream_type: StreamType::Video,
-                is_encoder: true,
-                is_decoder: true,
-            });
+        for (codec, is_encoder, is_decoder) in video_codecs {
+            self.codecs.insert(codec.to_string(), codec_info(codec, StreamType::Video, is_encoder, is_decoder));
         }
         
-        // Audio codecs
+        // Audio codecs; MP3 is decoded natively and encoded by libmp3lame
         let audio_codecs = vec![
-            "aac", "libfdk_aac", "mp3", "libmp3lame", "opus", "libopus",
-            "vorbis", "libvorbis", "flac", "alac", "ac3", "eac3",
-            "pcm_s16le", "pcm_s24le", "pcm_f32le", "copy",
+            ("aac", true, true), ("libfdk_aac", true, true), ("mp3", false, true), ("libmp3lame", true, false),
+            ("opus", true, true), ("libopus", true, true), ("vorbis", true, true), ("libvorbis", true, true),
+            ("flac", true, true), ("alac", true, true), ("ac3", true, true), ("eac3", true, true),
+            ("pcm_s16le", true, true), ("pcm_s24le", true, true), ("pcm_f32le", true, true),
         ];
         
-        for codec in audio_codecs {
-            self.codecs.insert(codec.to_string(), CodecInfo {
-                name: codec.to_string(),
-                stream_type: StreamType::Audio,
-                is_encoder: true,
-                is_decoder: true,
-            });
+        for (codec, is_encoder, is_decoder) in audio_codecs {
+            self.codecs.insert(codec.to_string(), codec_info(codec, StreamType::Audio, is_encoder, is_decoder));
+        }
+        
+        // Subtitle codecs (PGS can be decoded and copied but not encoded)
+        let subtitle_codecs = vec![
+            ("mov_text", true), ("srt", true), ("subrip", true), ("ass", true), ("ssa", true),
+            ("webvtt", true), ("dvd_subtitle", true), ("dvb_subtitle", true), ("hdmv_pgs_subtitle", false),
+        ];
+        
+        for (codec, is_encoder) in subtitle_codecs {
+            self.codecs.insert(codec.to_string(), codec_info(codec, StreamType::Subtitle, is_encoder, true));
         }
     }
     
@@ -95,7 +347,9 @@ impl CodecDatabase {
             supported_audio_codecs: vec![
                 "aac".to_string(), "mp3".to_string(), "ac3".to_string(),
             ],
+            supported_subtitle_codecs: vec!["mov_text".to_string()],
             extensions: vec!["mp4".to_string(), "m4v".to_string()],
+            codecs_complete: true,
         });
         
         // WebM container
@@ -108,7 +362,9 @@ impl CodecDatabase {
             supported_audio_codecs: vec![
                 "opus".to_string(), "vorbis".to_string(), "libopus".to_string(),
             ],
+            supported_subtitle_codecs: vec!["webvtt".to_string()],
             extensions: vec!["webm".to_string()],
+            codecs_complete: true,
         });
         
         // MKV container (Matroska) - very permissive
@@ -122,7 +378,13 @@ impl CodecDatabase {
                 "aac".to_string(), "mp3".to_string(), "opus".to_string(),
                 "vorbis".to_string(), "flac".to_string(), "ac3".to_string(),
             ],
+            supported_subtitle_codecs: vec![
+                "ass".to_string(), "srt".to_string(), "subrip".to_string(), "ssa".to_string(),
+                "webvtt".to_string(), "dvd_subtitle".to_string(), "dvb_subtitle".to_string(),
+                "hdmv_pgs_subtitle".to_string(),
+            ],
             extensions: vec!["mkv".to_string(), "mka".to_string()],
+            codecs_complete: true,
         });
         
         // AVI container
@@ -134,7 +396,9 @@ impl CodecDatabase {
             supported_audio_codecs: vec![
                 "mp3".to_string(), "ac3".to_string(), "pcm_s16le".to_string(),
             ],
+            supported_subtitle_codecs: vec![],
             extensions: vec!["avi".to_string()],
+            codecs_complete: true,
         });
         
         // MOV container (QuickTime)
@@ -147,8 +411,29 @@ impl CodecDatabase {
             supported_audio_codecs: vec![
                 "aac".to_string(), "alac".to_string(), "pcm_s16le".to_string(),
             ],
+            supported_subtitle_codecs: vec!["mov_text".to_string()],
             extensions: vec!["mov".to_string(), "qt".to_string()],
+            codecs_complete: true,
         });
+        
+        // Standalone subtitle files
+        let subtitle_files = vec![
+            ("srt", vec!["srt", "subrip"], vec!["srt"]),
+            ("ass", vec!["ass", "ssa"], vec!["ass", "ssa"]),
+            ("webvtt", vec!["webvtt"], vec!["vtt"]),
+            ("sup", vec!["hdmv_pgs_subtitle"], vec!["sup"]),
+        ];
+        
+        for (name, codecs, extensions) in subtitle_files {
+            self.formats.insert(name.to_string(), FormatInfo {
+                name: name.to_string(),
+                supported_video_codecs: vec![],
+                supported_audio_codecs: vec![],
+                supported_subtitle_codecs: codecs.into_iter().map(String::from).collect(),
+                extensions: extensions.into_iter().map(String::from).collect(),
+                codecs_complete: true,
+            });
+        }
     }
     
     fn init_filters(&mut self) {
@@ -166,15 +451,12 @@ impl CodecDatabase {
             ("drawtext", "Draw text on video", StreamType::Video, StreamType::Video),
             ("colorbalance", "Adjust color balance", StreamType::Video, StreamType::Video),
             ("eq", "Adjust brightness/contrast", StreamType::Video, StreamType::Video),
+            ("subtitles", "Burn subtitles into video", StreamType::Video, StreamType::Video),
+            ("ass", "Burn ASS/SSA subtitles into video", StreamType::Video, StreamType::Video),
         ];
         
         for (name, desc, in_type, out_type) in video_filters {
-            self.filters.insert(name.to_string(), FilterInfo {
-                name: name.to_string(),
-                input_type: in_type,
-                output_type: out_type,
-                description: desc.to_string(),
-            });
+            self.filters.insert(name.to_string(), filter_info(name, desc, in_type, out_type));
         }
         
         // Audio filters
@@ -191,13 +473,555 @@ impl CodecDatabase {
         ];
         
         for (name, desc, in_type, out_type) in audio_filters {
-            self.filters.insert(name.to_string(), FilterInfo {
+            self.filters.insert(name.to_string(), filter_info(name, desc, in_type, out_type));
+        }
+    }
+    
+    fn init_filter_params(&mut self) {
+        // Parameters in positional order
+        let filter_params = vec![
+            ("scale", vec![
+                filter_param("w", "expression", "Output width; -1 keeps the aspect ratio, -2 also keeps it even"),
+                filter_param("h", "expression", "Output height; -1 keeps the aspect ratio, -2 also keeps it even"),
+                filter_param("flags", "bilinear|bicubic|lanczos|...", "Scaling algorithm"),
+            ]),
+            ("crop", vec![
+                filter_param("w", "expression", "Width of the cropped area"),
+                filter_param("h", "expression", "Height of the cropped area"),
+                filter_param("x", "expression", "Left edge of the cropped area; centered by default"),
+                filter_param("y", "expression", "Top edge of the cropped area; centered by default"),
+            ]),
+            ("pad", vec![
+                filter_param("w", "expression", "Width of the padded frame"),
+                filter_param("h", "expression", "Height of the padded frame"),
+                filter_param("x", "expression", "Left edge of the input in the padded frame"),
+                filter_param("y", "expression", "Top edge of the input in the padded frame"),
+                filter_param("color", "color", "Color of the padding"),
+            ]),
+            ("rotate", vec![
+                filter_param("angle", "expression", "Clockwise rotation in radians"),
+                filter_param("out_w", "expression", "Output width"),
+                filter_param("out_h", "expression", "Output height"),
+                filter_param("fillcolor", "color", "Color of the uncovered area"),
+            ]),
+            ("fps", vec![
+                filter_param("fps", "rational", "Output frame rate"),
+                filter_param("round", "zero|inf|down|up|near", "Rounding of timestamps"),
+            ]),
+            ("format", vec![
+                filter_param("pix_fmts", "pixel formats separated by |", "Pixel formats to convert to"),
+            ]),
+            ("overlay", vec![
+                filter_param("x", "expression", "Left edge of the overlay"),
+                filter_param("y", "expression", "Top edge of the overlay"),
+                filter_param("eof_action", "repeat|endall|pass", "What happens when the overlay ends"),
+                filter_param("shortest", "boolean", "End with the shorter input"),
+            ]),
+            ("drawtext", vec![
+                filter_param("text", "string", "Text to draw"),
+                filter_param("fontfile", "path", "Font file"),
+                filter_param("fontsize", "expression", "Font size in pixels"),
+                filter_param("fontcolor", "color", "Text color"),
+                filter_param("x", "expression", "Left edge of the text"),
+                filter_param("y", "expression", "Top edge of the text"),
+                filter_param("box", "boolean", "Draw a box behind the text"),
+            ]),
+            ("colorbalance", vec![
+                filter_param("rs", "-1 to 1", "Red shift of the shadows"),
+                filter_param("gs", "-1 to 1", "Green shift of the shadows"),
+                filter_param("bs", "-1 to 1", "Blue shift of the shadows"),
+                filter_param("rm", "-1 to 1", "Red shift of the midtones"),
+                filter_param("gm", "-1 to 1", "Green shift of the midtones"),
+                filter_param("bm", "-1 to 1", "Blue shift of the midtones"),
+                filter_param("rh", "-1 to 1", "Red shift of the highlights"),
+                filter_param("gh", "-1 to 1", "Green shift of the highlights"),
+                filter_param("bh", "-1 to 1", "Blue shift of the highlights"),
+            ]),
+            ("eq", vec![
+                filter_param("contrast", "-1000 to 1000", "Contrast; 1 keeps it"),
+                filter_param("brightness", "-1 to 1", "Brightness; 0 keeps it"),
+                filter_param("saturation", "0 to 3", "Saturation; 1 keeps it"),
+                filter_param("gamma", "0.1 to 10", "Gamma; 1 keeps it"),
+            ]),
+            ("subtitles", vec![
+                filter_param("filename", "path", "Subtitle file to burn in"),
+                filter_param("force_style", "string", "ASS style overrides, e.g. FontSize=24"),
+                filter_param("si", "integer", "Index of the subtitle stream in the file"),
+            ]),
+            ("ass", vec![
+                filter_param("filename", "path", "ASS/SSA file to burn in"),
+            ]),
+            ("volume", vec![
+                filter_param("volume", "expression", "Gain as a factor (0.5) or in decibels (-6dB)"),
+                filter_param("precision", "fixed|float|double", "Sample precision"),
+            ]),
+            ("atempo", vec![
+                filter_param("tempo", "0.5 to 100", "Speed factor; pitch is kept"),
+            ]),
+            ("aresample", vec![
+                filter_param("sample_rate", "integer", "Output sample rate in Hz"),
+            ]),
+            ("aformat", vec![
+                filter_param("sample_fmts", "sample formats separated by |", "Sample formats to convert to"),
+                filter_param("sample_rates", "rates separated by |", "Sample rates to convert to"),
+                filter_param("channel_layouts", "layouts separated by |", "Channel layouts to convert to"),
+            ]),
+            ("loudnorm", vec![
+                filter_param("I", "-70 to -5", "Target integrated loudness in LUFS"),
+                filter_param("LRA", "1 to 50", "Target loudness range in LU"),
+                filter_param("TP", "-9 to 0", "Maximum true peak in dBTP"),
+            ]),
+            ("equalizer", vec![
+                filter_param("f", "Hz", "Center frequency"),
+                filter_param("t", "h|q|o|s|k", "Unit of the width"),
+                filter_param("w", "float", "Width of the band"),
+                filter_param("g", "dB", "Gain"),
+            ]),
+            ("highpass", vec![
+                filter_param("f", "Hz", "Cutoff frequency"),
+                filter_param("p", "1|2", "Number of poles"),
+            ]),
+            ("lowpass", vec![
+                filter_param("f", "Hz", "Cutoff frequency"),
+                filter_param("p", "1|2", "Number of poles"),
+            ]),
+            ("pan", vec![
+                filter_param("args", "layout|outX=gain*inY...", "Output channel layout and the mix of each output channel"),
+            ]),
+        ];
+        
+        for (name, params) in filter_params {
+            if let Some(filter) = self.filters.get_mut(name) {
+                filter.params = params;
+            }
+        }
+        for name in ["overlay", "overlay_cuda"] {
+            if let Some(filter) = self.filters.get_mut(name) {
+                filter.input_pads = Some(2);
+            }
+        }
+    }
+    
+    fn init_encoder_options(&mut self) {
+        let x26x_presets = [
+            "ultrafast", "superfast", "veryfast", "faster", "fast",
+            "medium", "slow", "slower", "veryslow", "placebo",
+        ];
+        let x264_levels = [
+            "1", "1b", "1.1", "1.2", "1.3", "2", "2.1", "2.2", "3", "3.1", "3.2",
+            "4", "4.1", "4.2", "5", "5.1", "5.2", "6", "6.1", "6.2",
+        ];
+        let vpx_deadlines = ["good", "best", "realtime"];
+        
+        self.add_encoder_options(
+            "libx264",
+            true,
+            vec![
+                range_option("crf", 0.0, 51.0),
+                range_option("qp", 0.0, 69.0),
+                one_of_option("preset", &x26x_presets),
+                one_of_option("tune", &[
+                    "film", "animation", "grain", "stillimage",
+                    "fastdecode", "zerolatency", "psnr", "ssim",
+                ]),
+                one_of_option("profile", &["baseline", "main", "high", "high10", "high422", "high444"]),
+                one_of_option("level", &x264_levels),
+            ],
+            vec![
+                profile("baseline", 8, &["420"]),
+                profile("main", 8, &["420"]),
+                profile("high", 8, &["420"]),
+                profile("high10", 10, &["420"]),
+                profile("high422", 10, &["420", "422"]),
+                profile("high444", 10, &["420", "422", "444"]),
+            ],
+        );
+        
+        self.add_encoder_options(
+            "libx265",
+            true,
+            vec![
+                range_option("crf", 0.0, 51.0),
+                range_option("qp", 0.0, 51.0),
+                one_of_option("preset", &x26x_presets),
+                one_of_option("tune", &["psnr", "ssim", "grain", "zerolatency", "fastdecode", "animation"]),
+                one_of_option("profile", &[
+                    "main", "main10", "mainstillpicture", "main12",
+                    "main422-10", "main422-12", "main444-8", "main444-10", "main444-12",
+                ]),
+            ],
+            vec![
+                profile("main", 8, &["420"]),
+                profile("mainstillpicture", 8, &["420"]),
+                profile("main10", 10, &["420"]),
+                profile("main12", 12, &["420"]),
+                profile("main422-10", 10, &["420", "422"]),
+                profile("main422-12", 12, &["420", "422"]),
+                profile("main444-8", 8, &["420", "422", "444"]),
+                profile("main444-10", 10, &["420", "422", "444"]),
+                profile("main444-12", 12, &["420", "422", "444"]),
+            ],
+        );
+        
+        self.add_encoder_options(
+            "libvpx-vp9",
+            true,
+            vec![
+                range_option("crf", 0.0, 63.0),
+                one_of_option("deadline", &vpx_deadlines),
+                range_option("cpu-used", -8.0, 8.0),
+                one_of_option("profile", &["0", "1", "2", "3"]),
+            ],
+            vec![
+                profile("0", 8, &["420"]),
+                profile("1", 8, &["422", "444"]),
+                profile("2", 12, &["420"]),
+                profile("3", 12, &["422", "444"]),
+            ],
+        );
+        
+        self.add_encoder_options(
+            "libvpx",
+            true,
+            vec![
+                range_option("crf", 4.0, 63.0),
+                one_of_option("deadline", &vpx_deadlines),
+                range_option("cpu-used", -16.0, 16.0),
+            ],
+            vec![],
+        );
+        
+        self.add_encoder_options(
+            "libaom-av1",
+            true,
+            vec![
+                range_option("crf", 0.0, 63.0),
+                range_option("cpu-used", 0.0, 8.0),
+                one_of_option("tune", &["psnr", "ssim"]),
+            ],
+            vec![],
+        );
+        
+        // Native encoders without private quality options
+        for encoder in ["mpeg4", "mpeg2video", "mjpeg"] {
+            self.add_encoder_options(encoder, true, vec![], vec![]);
+        }
+    }
+    
+    fn init_pixel_formats(&mut self) {
+        // (name, bit depth, chroma, alpha)
+        let pixel_formats = vec![
+            ("yuv420p", 8, "420", false), ("yuvj420p", 8, "420", false),
+            ("yuv422p", 8, "422", false), ("yuvj422p", 8, "422", false),
+            ("yuv444p", 8, "444", false), ("yuvj444p", 8, "444", false),
+            ("yuv440p", 8, "440", false), ("yuv411p", 8, "411", false),
+            ("yuva420p", 8, "420", true), ("yuva444p", 8, "444", true),
+            ("nv12", 8, "420", false), ("nv21", 8, "420", false), ("nv16", 8, "422", false),
+            ("yuv420p10le", 10, "420", false), ("yuv422p10le", 10, "422", false),
+            ("yuv444p10le", 10, "444", false), ("yuv440p10le", 10, "440", false),
+            ("yuva444p10le", 10, "444", true), ("p010le", 10, "420", false),
+            ("nv20le", 10, "422", false),
+            ("yuv420p12le", 12, "420", false), ("yuv422p12le", 12, "422", false),
+            ("yuv444p12le", 12, "444", false),
+            ("gray", 8, "gray", false), ("gray10le", 10, "gray", false),
+            ("gray12le", 12, "gray", false), ("gray16be", 16, "gray", false),
+            ("ya8", 8, "gray", true), ("monob", 1, "gray", false),
+            ("rgb24", 8, "rgb", false), ("bgr24", 8, "rgb", false),
+            ("rgba", 8, "rgb", true), ("bgra", 8, "rgb", true),
+            ("argb", 8, "rgb", true), ("abgr", 8, "rgb", true),
+            ("rgb48be", 16, "rgb", false), ("rgba64be", 16, "rgb", true),
+            ("gbrp", 8, "rgb", false), ("gbrp10le", 10, "rgb", false),
+            ("gbrp12le", 12, "rgb", false), ("pal8", 8, "rgb", false),
+        ];
+        
+        for (name, bit_depth, chroma, has_alpha) in pixel_formats {
+            self.pixel_formats.insert(name.to_string(), PixelFormatInfo {
+                name: name.to_string(),
+                bit_depth,
+                chroma: chroma.to_string(),
+                has_alpha,
+            });
+        }
+        
+        let encoder_pixel_formats: Vec<(&str, &[&str])> = vec![
+            ("libx264", &[
+                "yuv420p", "yuvj420p", "yuv422p", "yuvj422p", "yuv444p", "yuvj444p",
+                "nv12", "nv16", "nv21", "yuv420p10le", "yuv422p10le", "yuv444p10le",
+                "nv20le", "gray", "gray10le",
+            ]),
+            ("libx265", &[
+                "yuv420p", "yuvj420p", "yuv422p", "yuvj422p", "yuv444p", "yuvj444p", "gbrp",
+                "yuv420p10le", "yuv422p10le", "yuv444p10le", "gbrp10le",
+                "yuv420p12le", "yuv422p12le", "yuv444p12le", "gbrp12le",
+                "gray", "gray10le", "gray12le",
+            ]),
+            ("libvpx-vp9", &[
+                "yuv420p", "yuva420p", "yuv422p", "yuv440p", "yuv444p",
+                "yuv420p10le", "yuv422p10le", "yuv440p10le", "yuv444p10le",
+                "yuv420p12le", "yuv422p12le", "yuv444p12le",
+                "gbrp", "gbrp10le", "gbrp12le",
+            ]),
+            ("libvpx", &["yuv420p", "yuva420p"]),
+            ("libaom-av1", &[
+                "yuv420p", "yuv422p", "yuv444p", "gbrp",
+                "yuv420p10le", "yuv422p10le", "yuv444p10le", "gbrp10le",
+                "yuv420p12le", "yuv422p12le", "yuv444p12le", "gbrp12le",
+                "gray", "gray10le", "gray12le",
+            ]),
+            ("mpeg4", &["yuv420p"]),
+            ("mpeg2video", &["yuv420p", "yuv422p"]),
+            ("mjpeg", &["yuvj420p", "yuvj422p", "yuvj444p"]),
+            ("prores", &["yuv422p10le", "yuv444p10le", "yuva444p10le"]),
+            ("dnxhd", &["yuv422p", "yuv422p10le", "yuv444p10le", "gbrp10le"]),
+            ("png", &["rgb24", "rgba", "rgb48be", "rgba64be", "pal8", "gray", "gray16be", "ya8", "monob"]),
+        ];
+        
+        for (encoder, formats) in encoder_pixel_formats {
+            self.encoder_pixel_formats.insert(
+                encoder.to_string(),
+                formats.iter().map(|f| f.to_string()).collect(),
+            );
+        }
+    }
+    
+    fn init_audio_constraints(&mut self) {
+        let opus_rates: &[u32] = &[8000, 12000, 16000, 24000, 48000];
+        let mp3_rates: &[u32] = &[8000, 11025, 12000, 16000, 22050, 24000, 32000, 44100, 48000];
+        let ac3_rates: &[u32] = &[32000, 44100, 48000];
+        let aac_rates: &[u32] = &[
+            7350, 8000, 11025, 12000, 16000, 22050, 24000,
+            32000, 44100, 48000, 64000, 88200, 96000,
+        ];
+        
+        // (encoder, sample rates, max sample rate, max channels)
+        let constraints: Vec<(&str, &[u32], Option<u32>, u32)> = vec![
+            ("libopus", opus_rates, None, 8),
+            ("opus", &[48000], None, 2),
+            ("libmp3lame", mp3_rates, Some(48000), 2),
+            ("mp3", mp3_rates, Some(48000), 2),
+            ("ac3", ac3_rates, None, 6),
+            ("eac3", ac3_rates, None, 6),
+            ("aac", aac_rates, None, 8),
+            ("libfdk_aac", aac_rates, None, 8),
+            ("libvorbis", &[], Some(192000), 8),
+            ("vorbis", &[], Some(192000), 2),
+            ("flac", &[], Some(655350), 8),
+            ("alac", &[], Some(384000), 8),
+        ];
+        
+        for (encoder, sample_rates, max_sample_rate, max_channels) in constraints {
+            self.audio_constraints.insert(encoder.to_string(), AudioEncoderConstraints {
+                encoder: encoder.to_string(),
+                sample_rates: sample_rates.to_vec(),
+                max_sample_rate,
+                max_channels,
+            });
+        }
+        
+        let layouts = vec![
+            ("mono", 1), ("stereo", 2), ("2.1", 3), ("3.0", 3), ("quad", 4),
+            ("4.0", 4), ("5.0", 5), ("5.0(side)", 5), ("5.1", 6), ("5.1(side)", 6),
+            ("6.1", 7), ("7.1", 8), ("7.1(wide)", 8), ("hexadecagonal", 16),
+        ];
+        
+        for (name, channels) in layouts {
+            self.channel_layouts.insert(name.to_string(), channels);
+        }
+    }
+    
+    fn add_encoder_options(
+        &mut self,
+        encoder: &str,
+        is_lossy: bool,
+        options: Vec<EncoderOptionInfo>,
+        profiles: Vec<ProfileInfo>,
+    ) {
+        self.encoder_options.insert(encoder.to_string(), EncoderOptionsInfo {
+            encoder: encoder.to_string(),
+            is_lossy,
+            options,
+            profiles,
+        });
+    }
+    
+    fn init_codec_identities(&mut self) {
+        let encoder_codecs = vec![
+            ("libx264", "h264"), ("libx265", "hevc"), ("libvpx", "vp8"), ("libvpx-vp9", "vp9"),
+            ("libaom-av1", "av1"), ("libmp3lame", "mp3"), ("libopus", "opus"), ("libvorbis", "vorbis"),
+            ("libfdk_aac", "aac"),
+        ];
+        for (encoder, codec) in encoder_codecs {
+            self.encoder_codecs.insert(encoder.to_string(), codec.to_string());
+        }
+        
+        let extension_codecs = vec![
+            ("mp3", StreamType::Audio, "mp3"), ("flac", StreamType::Audio, "flac"),
+            ("wav", StreamType::Audio, "pcm_s16le"), ("aac", StreamType::Audio, "aac"),
+            ("m4a", StreamType::Audio, "aac"), ("opus", StreamType::Audio, "opus"),
+            ("ogg", StreamType::Audio, "vorbis"), ("png", StreamType::Video, "png"),
+            ("jpg", StreamType::Video, "mjpeg"), ("jpeg", StreamType::Video, "mjpeg"),
+            ("y4m", StreamType::Video, "rawvideo"),
+        ];
+        for (ext, stream_type, codec) in extension_codecs {
+            self.extension_codecs.insert(ext.to_string(), (stream_type, codec.to_string()));
+        }
+    }
+    
+    fn init_hardware(&mut self) {
+        let families = vec![
+            ("cuda", vec!["cuda", "nvdec"], "cuda", "NVIDIA GPUs (Linux, Windows)", true),
+            ("vaapi", vec!["vaapi"], "vaapi", "Intel and AMD GPUs (Linux)", false),
+            ("qsv", vec!["qsv"], "qsv", "Intel Quick Sync Video", true),
+            ("videotoolbox", vec!["videotoolbox"], "videotoolbox_vld", "Apple (macOS, iOS)", true),
+            ("amf", vec![], "amf_surface", "AMD GPUs (Windows)", true),
+            ("d3d11", vec!["d3d11va"], "d3d11", "Direct3D 11 (Windows)", false),
+        ];
+        for (name, hwaccel_names, frame_format, platform, encoders_accept_cpu_frames) in families {
+            self.hardware_families.insert(name.to_string(), HardwareFamily {
                 name: name.to_string(),
-                input_type: in_type,
-                output_type: out_type,
-                description: desc.to_string(),
+                hwaccel_names: hwaccel_names.into_iter().map(String::from).collect(),
+                frame_format: frame_format.to_string(),
+                platform: platform.to_string(),
+                encoders_accept_cpu_frames,
+                frame_consumers: Vec::new(),
             });
         }
+        // D3D11 frames are a shared Windows surface that the AMF, NVENC and Quick Sync encoders import
+        if let Some(d3d11) = self.hardware_families.get_mut("d3d11") {
+            d3d11.frame_consumers = ["h264_amf", "hevc_amf", "h264_nvenc", "hevc_nvenc", "av1_nvenc", "h264_qsv", "hevc_qsv", "av1_qsv"]
+                .into_iter()
+                .map(String::from)
+                .collect();
+        }
+        
+        // (name, family, codec, is_encoder, is_decoder)
+        let hardware_codecs = vec![
+            ("h264_nvenc", "cuda", "h264", true, false),
+            ("hevc_nvenc", "cuda", "hevc", true, false),
+            ("av1_nvenc", "cuda", "av1", true, false),
+            ("h264_cuvid", "cuda", "h264", false, true),
+            ("hevc_cuvid", "cuda", "hevc", false, true),
+            ("vp9_cuvid", "cuda", "vp9", false, true),
+            ("av1_cuvid", "cuda", "av1", false, true),
+            ("h264_vaapi", "vaapi", "h264", true, false),
+            ("hevc_vaapi", "vaapi", "hevc", true, false),
+            ("vp9_vaapi", "vaapi", "vp9", true, false),
+            ("av1_vaapi", "vaapi", "av1", true, false),
+            ("h264_qsv", "qsv", "h264", true, true),
+            ("hevc_qsv", "qsv", "hevc", true, true),
+            ("av1_qsv", "qsv", "av1", true, true),
+            ("h264_videotoolbox", "videotoolbox", "h264", true, false),
+            ("hevc_videotoolbox", "videotoolbox", "hevc", true, false),
+            ("prores_videotoolbox", "videotoolbox", "prores", true, false),
+            ("h264_amf", "amf", "h264", true, false),
+            ("hevc_amf", "amf", "hevc", true, false),
+        ];
+        for (name, family, codec, is_encoder, is_decoder) in hardware_codecs {
+            self.codecs.insert(name.to_string(), codec_info(name, StreamType::Video, is_encoder, is_decoder));
+            self.encoder_codecs.insert(name.to_string(), codec.to_string());
+            self.hardware_components.insert(name.to_string(), family.to_string());
+        }
+        
+        let hardware_filters = vec![
+            ("scale_cuda", "cuda", "Resize video on NVIDIA GPUs"),
+            ("scale_npp", "cuda", "Resize video with NVIDIA Performance Primitives"),
+            ("yadif_cuda", "cuda", "Deinterlace video on NVIDIA GPUs"),
+            ("overlay_cuda", "cuda", "Overlay video on NVIDIA GPUs"),
+            ("scale_vaapi", "vaapi", "Resize video with VAAPI"),
+            ("deinterlace_vaapi", "vaapi", "Deinterlace video with VAAPI"),
+            ("scale_qsv", "qsv", "Resize video with Quick Sync"),
+            ("vpp_qsv", "qsv", "Quick Sync video post-processing"),
+        ];
+        for (name, family, desc) in hardware_filters {
+            self.filters.insert(name.to_string(), filter_info(name, desc, StreamType::Video, StreamType::Video));
+            self.hardware_components.insert(name.to_string(), family.to_string());
+        }
+        
+        // Transfers between system and GPU memory
+        let transfer_filters = vec![
+            ("hwupload", "Upload frames to GPU memory"),
+            ("hwupload_cuda", "Upload frames to CUDA memory"),
+            ("hwdownload", "Download frames from GPU memory"),
+            ("hwmap", "Map frames between devices"),
+        ];
+        for (name, desc) in transfer_filters {
+            self.filters.insert(name.to_string(), filter_info(name, desc, StreamType::Video, StreamType::Video));
+        }
+    }
+    
+    fn init_versions(&mut self) {
+        let v = FfmpegVersion::new;
+        // (name, since)
+        let codecs_since = vec![
+            ("libaom-av1", v(4, 0)), ("av1_nvenc", v(6, 0)), ("av1_qsv", v(6, 0)), ("av1_vaapi", v(6, 1)),
+        ];
+        for (name, since) in codecs_since {
+            if let Some(codec) = self.codecs.get_mut(name) {
+                codec.availability.since = Some(since);
+            }
+        }
+        
+        // The native AAC encoder needed `-strict experimental` before 3.0; the native Opus
+        // encoder still does
+        if let Some(aac) = self.codecs.get_mut("aac") {
+            aac.experimental_until = Some(v(3, 0));
+        }
+        if let Some(opus) = self.codecs.get_mut("opus") {
+            opus.experimental = true;
+        }
+        
+        let filters_since = vec![("loudnorm", v(3, 1)), ("scale_vaapi", v(3, 1))];
+        for (name, since) in filters_since {
+            if let Some(filter) = self.filters.get_mut(name) {
+                filter.availability.since = Some(since);
+            }
+        }
+        
+        // Renamed or dropped encoders, known so that commands written for older releases are
+        // recognized: (name, stream type, codec, deprecated in, removed in, replacement)
+        let retired_encoders = vec![
+            ("nvenc_h264", StreamType::Video, "h264", Some(v(3, 0)), v(5, 0), "h264_nvenc"),
+            ("nvenc_hevc", StreamType::Video, "hevc", Some(v(3, 0)), v(5, 0), "hevc_nvenc"),
+            ("libvo_aacenc", StreamType::Audio, "aac", None, v(3, 0), "aac"),
+            ("libfaac", StreamType::Audio, "aac", None, v(3, 2), "aac"),
+        ];
+        for (name, stream_type, codec, deprecated_in, removed_in, replacement) in retired_encoders {
+            let mut info = codec_info(name, stream_type, true, false);
+            info.availability = Availability { since: None, deprecated_in, removed_in: Some(removed_in), replacement: Some(replacement) };
+            self.codecs.insert(name.to_string(), info);
+            self.encoder_codecs.insert(name.to_string(), codec.to_string());
+        }
+        
+        // scale2ref gave way to the reference input of scale; pp went with libpostproc
+        let mut scale2ref = filter_info("scale2ref", "Scale a video to the size of a reference video", StreamType::Video, StreamType::Video);
+        scale2ref.input_pads = Some(2);
+        scale2ref.availability = Availability {
+            since: None,
+            deprecated_in: Some(v(7, 1)),
+            removed_in: None,
+            replacement: Some("scale with a reference input (scale=rw:rh)"),
+        };
+        let mut pp = filter_info("pp", "Postprocess with libpostproc", StreamType::Video, StreamType::Video);
+        pp.availability = Availability { since: None, deprecated_in: None, removed_in: Some(v(8, 0)), replacement: None };
+        for filter in [scale2ref, pp] {
+            self.filters.insert(filter.name.clone(), filter);
+        }
+    }
+    
+    fn init_bitrate_guidelines(&mut self) {
+        // Newer codecs need fewer bits for the same quality
+        let bits_per_pixel = vec![
+            ("mpeg2video", 0.06, 0.8),
+            ("mpeg4", 0.04, 0.6),
+            ("vp8", 0.025, 0.5),
+            ("h264", 0.02, 0.4),
+            ("hevc", 0.012, 0.25),
+            ("vp9", 0.012, 0.25),
+            ("av1", 0.008, 0.2),
+        ];
+        for (codec, min, max) in bits_per_pixel {
+            self.bits_per_pixel.insert(codec.to_string(), (min, max));
+        }
     }
     
     pub fn get_codec(&self, name: &str) -> Option<&CodecInfo> {
@@ -216,25 +1040,158 @@ impl CodecDatabase {
         self.filters.get(name)
     }
     
+    pub fn get_encoder_options(&self, encoder: &str) -> Option<&EncoderOptionsInfo> {
+        self.encoder_options.get(encoder)
+    }
+    
+    pub fn get_pixel_format(&self, name: &str) -> Option<&PixelFormatInfo> {
+        self.pixel_formats.get(name)
+    }
+    
+    /// Pixel formats accepted by an encoder, or None if it is unrestricted/unknown
+    pub fn get_encoder_pixel_formats(&self, encoder: &str) -> Option<&Vec<String>> {
+        self.encoder_pixel_formats.get(encoder)
+    }
+    
+    pub fn get_audio_constraints(&self, encoder: &str) -> Option<&AudioEncoderConstraints> {
+        self.audio_constraints.get(encoder)
+    }
+    
+    /// Channel count of a layout name, also accepting the `<N>c` form (e.g. "6c")
+    pub fn channel_layout_channels(&self, layout: &str) -> Option<u32> {
+        if let Some(count) = layout.strip_suffix('c').and_then(|n| n.parse().ok()) {
+            return Some(count);
+        }
+        self.channel_layouts.get(layout).copied()
+    }
+    
     pub fn is_codec_supported_in_format(&self, codec: &str, format: &str) -> bool {
         if let Some(codec_info) = self.get_codec(codec) {
             if let Some(format_info) = self.get_format(format) {
-                match codec_info.stream_type {
-                    StreamType::Video => {
-                        return format_info.supported_video_codecs.contains(&codec.to_string());
-                    }
-                    StreamType::Audio => {
-                        return format_info.supported_audio_codecs.contains(&codec.to_string());
-                    }
-                    _ => return false,
+                if !format_info.codecs_complete {
+                    return true;
                 }
+                return self.format_stores(format_info, codec, &codec_info.stream_type);
             }
         }
         false
     }
     
+    /// Whether a container lists a codec, by its name or by the codec an encoder library
+    /// implements (`libopus` is stored as `opus`)
+    fn format_stores(&self, format_info: &FormatInfo, codec: &str, stream_type: &StreamType) -> bool {
+        let codecs = match stream_type {
+            StreamType::Video => &format_info.supported_video_codecs,
+            StreamType::Audio => &format_info.supported_audio_codecs,
+            StreamType::Subtitle => &format_info.supported_subtitle_codecs,
+            _ => return false,
+        };
+        let identity = self.codec_identity(codec);
+        codecs.iter().any(|c| c == codec || c == identity)
+    }
+    
+    /// Codec of a stream in a file whose extension implies a single codec (`.flac`, `.srt`)
+    pub fn codec_for_filename(&self, filename: &str, stream_type: &StreamType) -> Option<String> {
+        let ext = filename.rsplit('.').next()?.to_ascii_lowercase();
+        if *stream_type == StreamType::Subtitle {
+            let format = self.get_format_by_extension(&ext)?;
+            return format.supported_subtitle_codecs.first().cloned();
+        }
+        match self.extension_codecs.get(&ext) {
+            Some((ext_type, codec)) if ext_type == stream_type => Some(codec.clone()),
+            _ => None,
+        }
+    }
+    
+    /// Codec produced by an encoder ("libx264" -> "h264"); codec names map to themselves
+    pub fn codec_identity<'a>(&'a self, codec: &'a str) -> &'a str {
+        self.encoder_codecs.get(codec).map(|c| c.as_str()).unwrap_or(codec)
+    }
+    
+    /// Encoders producing a codec ("h264" -> libx264, h264_nvenc, ...), software encoders first
+    pub fn encoders_for(&self, codec: &str) -> Vec<&str> {
+        self.implementations_of(codec, |c| c.is_encoder)
+    }
+    
+    /// Decoders reading a codec ("h264" -> h264, h264_cuvid, ...), the native decoder first
+    pub fn decoders_for(&self, codec: &str) -> Vec<&str> {
+        self.implementations_of(codec, |c| c.is_decoder)
+    }
+    
+    fn implementations_of(&self, codec: &str, keep: impl Fn(&CodecInfo) -> bool) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .codecs
+            .values()
+            .filter(|c| keep(c) && c.availability.is_current() && self.codec_identity(&c.name) == codec)
+            .map(|c| c.name.as_str())
+            .collect();
+        names.sort_by_key(|name| (*name != codec, self.hardware_family_of(name).is_some(), *name));
+        names
+    }
+    
+    pub fn get_hardware_family(&self, name: &str) -> Option<&HardwareFamily> {
+        self.hardware_families.get(name)
+    }
+    
+    /// Hardware family selected by a `-hwaccel` value (`nvdec` -> cuda)
+    pub fn hardware_family_for_hwaccel(&self, hwaccel: &str) -> Option<&HardwareFamily> {
+        self.hardware_families.values().find(|f| f.hwaccel_names.iter().any(|n| n == hwaccel))
+    }
+    
+    /// Whether a hardware encoder reads GPU frames of `family`, its own or one it imports
+    pub fn encoder_reads_family(&self, encoder: &str, family: &str) -> bool {
+        self.hardware_components.get(encoder).is_some_and(|own| own == family)
+            || self.hardware_families.get(family).is_some_and(|f| f.frame_consumers.iter().any(|c| c == encoder))
+    }
+    
+    /// Hardware family of a hardware codec or filter; None for software components
+    pub fn hardware_family_of(&self, name: &str) -> Option<&HardwareFamily> {
+        self.hardware_families.get(self.hardware_components.get(name)?)
+    }
+    
+    /// Reasonable bits per pixel per frame for a video encoder, as (minimum, maximum)
+    pub fn get_bits_per_pixel_range(&self, codec: &str) -> Option<(f64, f64)> {
+        self.bits_per_pixel.get(self.codec_identity(codec)).copied()
+    }
+    
+    pub fn is_lossless_codec(&self, codec: &str) -> bool {
+        LOSSLESS_CODECS.contains(&self.codec_identity(codec))
+    }
+    
+    /// Subtitle encoder ffmpeg would pick for a container (its preferred text codec)
+    pub fn default_subtitle_codec(&self, format: &str) -> Option<&str> {
+        self.get_format(format)?
+            .supported_subtitle_codecs
+            .iter()
+            .map(|c| c.as_str())
+            .find(|c| !BITMAP_SUBTITLE_CODECS.contains(c))
+    }
+    
+    /// Software encoders for the codecs of a stream type a container can store, in the
+    /// container's order ("mp4", video -> libx264, libx265, mpeg4)
+    pub fn encoders_for_format(&self, format: &str, stream_type: &StreamType) -> Vec<&str> {
+        let Some(format_info) = self.get_format(format) else { return Vec::new() };
+        let codecs = match stream_type {
+            StreamType::Video => &format_info.supported_video_codecs,
+            StreamType::Audio => &format_info.supported_audio_codecs,
+            StreamType::Subtitle => &format_info.supported_subtitle_codecs,
+            _ => return Vec::new(),
+        };
+        let mut encoders: Vec<&str> = Vec::new();
+        for codec in codecs {
+            let encoder = self
+                .encoders_for(self.codec_identity(codec))
+                .into_iter()
+                .find(|e| self.hardware_family_of(e).is_none());
+            if let Some(encoder) = encoder.filter(|e| !encoders.contains(e)) {
+                encoders.push(encoder);
+            }
+        }
+        encoders
+    }
+    
     pub fn infer_format_from_filename(&self, filename: &str) -> Option<String> {
-        if let Some(ext) = filename.split('.').last() {
+        if let Some(ext) = filename.rsplit('.').next() {
             if let Some(format_info) = self.get_format_by_extension(ext) {
                 return Some(format_info.name.clone());
             }
@@ -247,18 +1204,8 @@ impl CodecDatabase {
         let mut compatible = Vec::new();
         
         if let Some(codec_info) = self.get_codec(codec) {
-            for (_, format_info) in &self.formats {
-                let is_compatible = match codec_info.stream_type {
-                    StreamType::Video => {
-                        format_info.supported_video_codecs.contains(&codec.to_string())
-                    }
-                    StreamType::Audio => {
-                        format_info.supported_audio_codecs.contains(&codec.to_string())
-                    }
-                    _ => false,
-                };
-                
-                if is_compatible {
+            for format_info in self.formats.values() {
+                if self.format_stores(format_info, codec, &codec_info.stream_type) {
                     // Use the first extension as the representative format name
                     if let Some(ext) = format_info.extensions.first() {
                         compatible.push(ext.clone());
@@ -272,6 +1219,60 @@ impl CodecDatabase {
     }
 }
 
+pub(crate) fn codec_info(name: &str, stream_type: StreamType, is_encoder: bool, is_decoder: bool) -> CodecInfo {
+    CodecInfo {
+        name: name.to_string(),
+        stream_type,
+        is_encoder,
+        is_decoder,
+        availability: Availability::default(),
+        experimental: false,
+        experimental_until: None,
+    }
+}
+
+pub(crate) fn filter_info(name: &str, description: &str, input_type: StreamType, output_type: StreamType) -> FilterInfo {
+    FilterInfo {
+        name: name.to_string(),
+        input_type,
+        output_type,
+        description: description.to_string(),
+        availability: Availability::default(),
+        input_pads: Some(1),
+        params: Vec::new(),
+    }
+}
+
+fn filter_param(name: &str, value: &str, description: &str) -> FilterParamInfo {
+    FilterParamInfo {
+        name: name.to_string(),
+        value: value.to_string(),
+        description: description.to_string(),
+    }
+}
+
+fn range_option(name: &str, min: f64, max: f64) -> EncoderOptionInfo {
+    EncoderOptionInfo {
+        name: name.to_string(),
+        values: OptionValues::Range { min, max },
+    }
+}
+
+fn one_of_option(name: &str, values: &[&str]) -> EncoderOptionInfo {
+    EncoderOptionInfo {
+        name: name.to_string(),
+        values: OptionValues::OneOf(values.iter().map(|v| v.to_string()).collect()),
+    }
+}
+
+fn profile(name: &str, max_bit_depth: u8, chroma: &[&str]) -> ProfileInfo {
+    ProfileInfo {
+        name: name.to_string(),
+        max_bit_depth,
+        chroma: chroma.iter().map(|c| c.to_string()).collect(),
+    }
+}
+
 impl Default for CodecDatabase {
     fn default() -> Self {
         Self::new()
@@ -303,5 +1304,92 @@ mod tests {
         assert_eq!(db.infer_format_from_filename("video.mp4"), Some("mp4".to_string()));
         assert_eq!(db.infer_format_from_filename("video.webm"), Some("webm".to_string()));
     }
+    
+    #[test]
+    fn test_encoder_options() {
+        let db = CodecDatabase::new();
+        let x264 = db.get_encoder_options("libx264").unwrap();
+        assert!(matches!(
+            x264.get_option("crf").unwrap().values,
+            OptionValues::Range { max, .. } if max == 51.0
+        ));
+        assert!(x264.get_option("cq").is_none());
+        let vp9 = db.get_encoder_options("libvpx-vp9").unwrap();
+        assert!(matches!(
+            vp9.get_option("crf").unwrap().values,
+            OptionValues::Range { max, .. } if max == 63.0
+        ));
+    }
+    
+    #[test]
+    fn test_subtitle_codecs() {
+        let db = CodecDatabase::new();
+        assert!(matches!(db.get_codec("mov_text").unwrap().stream_type, StreamType::Subtitle));
+        assert!(!db.get_codec("hdmv_pgs_subtitle").unwrap().is_encoder);
+        assert!(db.is_codec_supported_in_format("mov_text", "mp4"));
+        assert!(!db.is_codec_supported_in_format("srt", "mp4"));
+        assert!(db.is_codec_supported_in_format("srt", "matroska"));
+        assert_eq!(db.default_subtitle_codec("webm"), Some("webvtt"));
+        assert_eq!(db.default_subtitle_codec("avi"), None);
+        assert_eq!(db.codec_for_filename("subs.srt", &StreamType::Subtitle), Some("srt".to_string()));
+    }
+    
+    #[test]
+    fn test_codec_identity() {
+        let db = CodecDatabase::new();
+        assert_eq!(db.codec_identity("libmp3lame"), "mp3");
+        assert_eq!(db.codec_identity("aac"), "aac");
+        for (encoder, format) in [("libopus", "matroska"), ("libvorbis", "matroska"), ("libmp3lame", "matroska"), ("libfdk_aac", "mp4")] {
+            assert!(db.is_codec_supported_in_format(encoder, format), "{} in {}", encoder, format);
+        }
+        assert!(db.get_compatible_formats("libvorbis").contains(&"webm".to_string()));
+        assert!(!db.is_codec_supported_in_format("libopus", "avi"));
+        assert!(db.is_lossless_codec("flac"));
+        assert!(!db.is_lossless_codec("libopus"));
+        assert_eq!(db.codec_for_filename("song.FLAC", &StreamType::Audio), Some("flac".to_string()));
+        assert_eq!(db.codec_for_filename("song.flac", &StreamType::Video), None);
+        assert_eq!(db.codec_for_filename("movie.mp4", &StreamType::Video), None);
+    }
+    
+    #[test]
+    fn test_hardware_codecs() {
+        let db = CodecDatabase::new();
+        assert!(db.get_codec("h264_nvenc").is_some_and(|c| c.is_encoder && !c.is_decoder));
+        assert_eq!(db.hardware_family_of("hevc_vaapi").unwrap().name, "vaapi");
+        assert_eq!(db.hardware_family_of("scale_cuda").unwrap().frame_format, "cuda");
+        assert!(db.hardware_family_of("libx264").is_none());
+        assert_eq!(db.hardware_family_for_hwaccel("nvdec").unwrap().name, "cuda");
+        assert!(db.is_codec_supported_in_format("h264_videotoolbox", "mp4"));
+        assert!(!db.is_codec_supported_in_format("vp9_vaapi", "mp4"));
+    }
+    
+    #[test]
+    fn test_pixel_formats() {
+        let db = CodecDatabase::new();
+        let yuv = db.get_pixel_format("yuv420p10le").unwrap();
+        assert_eq!(yuv.bit_depth, 10);
+        assert_eq!(yuv.chroma, "420");
+        assert!(db.get_pixel_format("yuva420p").unwrap().has_alpha);
+        
+        let vp8 = db.get_encoder_pixel_formats("libvpx").unwrap();
+        assert!(vp8.contains(&"yuva420p".to_string()));
+        assert!(!vp8.contains(&"yuv444p".to_string()));
+        assert!(db.get_encoder_pixel_formats("rawvideo").is_none());
+    }
+    
+    #[test]
+    fn test_audio_constraints() {
+        let db = CodecDatabase::new();
+        let opus = db.get_audio_constraints("libopus").unwrap();
+        assert!(opus.supports_sample_rate(48000));
+        assert!(!opus.supports_sample_rate(44100));
+        assert_eq!(opus.nearest_sample_rate(44100), Some(48000));
+        
+        let mp3 = db.get_audio_constraints("libmp3lame").unwrap();
+        assert!(!mp3.supports_sample_rate(96000));
+        assert_eq!(mp3.nearest_sample_rate(96000), Some(48000));
+        
+        assert_eq!(db.channel_layout_channels("5.1"), Some(6));
+        assert_eq!(db.channel_layout_channels("10c"), Some(10));
+    }
 }
-
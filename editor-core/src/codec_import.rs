@@ -0,0 +1,326 @@
+//! Build or extend a [`CodecDatabase`] from the text listings of a specific ffmpeg build
+//! (`ffmpeg -hide_banner -codecs`, `-encoders`, `-decoders`, `-muxers`, `-filters` and `-h muxer=NAME`)
+
+use crate::codec_db::{codec_info, filter_info, CodecDatabase, FormatInfo};
+use shared_types::StreamType;
+
+impl CodecDatabase {
+    /// Import `ffmpeg -codecs`: codec names with their decode/encode support, plus the
+    /// encoder and decoder implementations listed as `(encoders: ...)` and `(decoders: ...)`
+    pub fn import_codecs(&mut self, listing: &str) -> Result<usize, String> {
+        let entries = listing_entries(listing, "-codecs")?;
+        for entry in &entries {
+            let flags: Vec<char> = entry.flags.chars().collect();
+            let Some(stream_type) = flags.get(2).and_then(|c| stream_type_of(*c)) else { continue };
+            let (can_decode, can_encode) = (flags.first() == Some(&'D'), flags.get(1) == Some(&'E'));
+            // D/E say some implementation exists. The codec name is itself one only when no list is
+            // printed or the list includes it: `h264` is a decoder, but its encoders are libx264 etc.
+            let names_itself = |list: &str| {
+                let names = implementations(&entry.description, list);
+                names.is_empty() || names.contains(&entry.name.as_str())
+            };
+            self.add_codec(&entry.name, &stream_type, can_encode && names_itself("encoders"), can_decode && names_itself("decoders"));
+            
+            for (list, is_encoder) in [("encoders", true), ("decoders", false)] {
+                for implementation in implementations(&entry.description, list) {
+                    self.add_codec(implementation, &stream_type, is_encoder, !is_encoder);
+                    if implementation != entry.name {
+                        self.encoder_codecs.insert(implementation.to_string(), entry.name.clone());
+                    }
+                }
+            }
+        }
+        Ok(entries.len())
+    }
+    
+    /// Import `ffmpeg -encoders`; `(codec h264)` after an encoder records the codec it produces,
+    /// and the `X` flag marks encoders that need `-strict experimental`
+    pub fn import_encoders(&mut self, listing: &str) -> Result<usize, String> {
+        self.import_implementations(listing, "-encoders", true)
+    }
+    
+    /// Import `ffmpeg -decoders`
+    pub fn import_decoders(&mut self, listing: &str) -> Result<usize, String> {
+        self.import_implementations(listing, "-decoders", false)
+    }
+    
+    fn import_implementations(&mut self, listing: &str, command: &str, is_encoder: bool) -> Result<usize, String> {
+        let entries = listing_entries(listing, command)?;
+        for entry in &entries {
+            let Some(stream_type) = entry.flags.chars().next().and_then(stream_type_of) else { continue };
+            self.add_codec(&entry.name, &stream_type, is_encoder, !is_encoder);
+            // The fourth flag marks experimental implementations (`A..X.D opus`)
+            if is_encoder && entry.flags.chars().nth(3) == Some('X') {
+                if let Some(codec) = self.codecs.get_mut(&entry.name) {
+                    codec.experimental = true;
+                }
+            }
+            if let Some(codec) = produced_codec(&entry.description).filter(|codec| *codec != entry.name) {
+                self.encoder_codecs.insert(entry.name.clone(), codec.to_string());
+            }
+        }
+        Ok(entries.len())
+    }
+    
+    /// Import `ffmpeg -muxers`. New formats have no extensions or codec lists until
+    /// `ffmpeg -h muxer=NAME` is imported for them, so they never report a codec as unsupported
+    pub fn import_muxers(&mut self, listing: &str) -> Result<usize, String> {
+        let entries = listing_entries(listing, "-muxers")?;
+        let muxers: Vec<_> = entries.iter().filter(|e| e.flags.contains('E')).collect();
+        for entry in &muxers {
+            for name in entry.name.split(',') {
+                self.formats.entry(name.to_string()).or_insert_with(|| incomplete_format(name));
+            }
+        }
+        Ok(muxers.len())
+    }
+    
+    /// Import `ffmpeg -h muxer=NAME`: the muxer's file extensions and default codecs
+    pub fn import_muxer_help(&mut self, help: &str) -> Result<(), String> {
+        let name = help
+            .lines()
+            .find_map(|line| line.strip_prefix("Muxer "))
+            .and_then(|rest| rest.split_whitespace().next())
+            .ok_or("Expected the output of `ffmpeg -h muxer=NAME` starting with 'Muxer NAME'")?;
+        let format = self.formats.entry(name.to_string()).or_insert_with(|| incomplete_format(name));
+        
+        for line in help.lines() {
+            let Some((key, value)) = line.trim().split_once(':') else { continue };
+            let value = value.trim().trim_end_matches('.');
+            let (list, values) = match key {
+                "Common extensions" => (&mut format.extensions, value.split(',').collect()),
+                "Default video codec" => (&mut format.supported_video_codecs, vec![value]),
+                "Default audio codec" => (&mut format.supported_audio_codecs, vec![value]),
+                "Default subtitle codec" => (&mut format.supported_subtitle_codecs, vec![value]),
+                _ => continue,
+            };
+            for value in values.into_iter().map(str::trim).filter(|v| !v.is_empty() && *v != "none") {
+                if !list.iter().any(|v| v == value) {
+                    list.push(value.to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+    
+    /// Import `ffmpeg -filters`. Each filter's stream types come from its `A->V` column;
+    /// sources (`|`) and dynamic pads (`N`) accept any stream type
+    pub fn import_filters(&mut self, listing: &str) -> Result<usize, String> {
+        let mut count = 0;
+        for line in listing.lines() {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            let [flags, name, io, ..] = tokens.as_slice() else { continue };
+            let Some((inputs, outputs)) = io.split_once("->") else { continue };
+            if flags.len() != 3 || !flags.chars().all(|c| "TSC.".contains(c)) {
+                continue;
+            }
+            let description = tokens[3..].join(" ");
+            let description = description.trim_end_matches('.');
+            let mut filter = filter_info(name, description, pad_type(inputs), pad_type(outputs));
+            filter.input_pads = pad_count(inputs);
+            // The listing has no parameters; keep those of a built-in filter
+            if let Some(known) = self.filters.get(*name) {
+                filter.params = known.params.clone();
+            }
+            self.filters.insert(name.to_string(), filter);
+            count += 1;
+        }
+        
+        if count == 0 {
+            return Err("No filters found; expected the output of `ffmpeg -hide_banner -filters`".to_string());
+        }
+        self.filters_complete = true;
+        Ok(count)
+    }
+    
+    /// Add a codec, or widen the encode/decode support of a known one
+    fn add_codec(&mut self, name: &str, stream_type: &StreamType, is_encoder: bool, is_decoder: bool) {
+        let codec = self.codecs.entry(name.to_string()).or_insert_with(|| codec_info(name, stream_type.clone(), false, false));
+        codec.is_encoder |= is_encoder;
+        codec.is_decoder |= is_decoder;
+    }
+}
+
+/// A row of a `-codecs`/`-encoders`/`-decoders`/`-muxers` listing
+struct ListingEntry {
+    flags: String,
+    name: String,
+    description: String,
+}
+
+/// Rows after the legend, which ends with a line of dashes
+fn listing_entries(listing: &str, command: &str) -> Result<Vec<ListingEntry>, String> {
+    let mut lines = listing.lines().skip_while(|line| !is_separator(line));
+    if lines.next().is_none() {
+        return Err(format!("Expected the output of `ffmpeg -hide_banner {}` with its legend", command));
+    }
+    
+    Ok(lines
+        .filter_map(|line| {
+            // Unset flags are spaces in `-muxers` ("  E mp4"), so only the set letters remain
+            let mut tokens = line.split_whitespace();
+            let flags = tokens.next()?;
+            let name = tokens.next()?;
+            let description = tokens.collect::<Vec<_>>().join(" ");
+            Some(ListingEntry { flags: flags.to_string(), name: name.to_string(), description })
+        })
+        .collect())
+}
+
+fn is_separator(line: &str) -> bool {
+    let line = line.trim();
+    line.len() >= 2 && line.chars().all(|c| c == '-')
+}
+
+fn stream_type_of(flag: char) -> Option<StreamType> {
+    match flag {
+        'V' => Some(StreamType::Video),
+        'A' => Some(StreamType::Audio),
+        'S' => Some(StreamType::Subtitle),
+        'D' => Some(StreamType::Data),
+        _ => None,
+    }
+}
+
+fn pad_type(pads: &str) -> StreamType {
+    match pads.chars().next() {
+        Some('V') => StreamType::Video,
+        Some('A') => StreamType::Audio,
+        _ => StreamType::Unknown,
+    }
+}
+
+/// Number of pads in an `A->V` column side: `|` has none, `N` is set by a parameter
+fn pad_count(pads: &str) -> Option<usize> {
+    match pads {
+        "|" => Some(0),
+        "N" => None,
+        _ => Some(pads.len()),
+    }
+}
+
+/// Codec named by a trailing `(codec h264)` in an encoder or decoder description
+fn produced_codec(description: &str) -> Option<&str> {
+    description.rsplit_once("(codec ")?.1.strip_suffix(')')
+}
+
+/// Names in `(encoders: a b )` or `(decoders: a b )` of a `-codecs` description
+fn implementations<'a>(description: &'a str, list: &str) -> Vec<&'a str> {
+    let marker = format!("({}: ", list);
+    description
+        .split_once(marker.as_str())
+        .and_then(|(_, rest)| rest.split_once(')'))
+        .map(|(names, _)| names.split_whitespace().collect())
+        .unwrap_or_default()
+}
+
+fn incomplete_format(name: &str) -> FormatInfo {
+    FormatInfo {
+        name: name.to_string(),
+        supported_video_codecs: vec![],
+        supported_audio_codecs: vec![],
+        supported_subtitle_codecs: vec![],
+        extensions: vec![],
+        codecs_complete: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const CODECS: &str = include_str!("../fixtures/ffmpeg-codecs.txt");
+    const ENCODERS: &str = include_str!("../fixtures/ffmpeg-encoders.txt");
+    const DECODERS: &str = include_str!("../fixtures/ffmpeg-decoders.txt");
+    const MUXERS: &str = include_str!("../fixtures/ffmpeg-muxers.txt");
+    const FLV_HELP: &str = include_str!("../fixtures/ffmpeg-h-muxer-flv.txt");
+    const MPEGTS_HELP: &str = include_str!("../fixtures/ffmpeg-h-muxer-mpegts.txt");
+    const FILTERS: &str = include_str!("../fixtures/ffmpeg-filters.txt");
+    
+    fn imported_db() -> CodecDatabase {
+        let mut db = CodecDatabase::empty();
+        db.import_codecs(CODECS).unwrap();
+        db.import_encoders(ENCODERS).unwrap();
+        db.import_decoders(DECODERS).unwrap();
+        db.import_muxers(MUXERS).unwrap();
+        db.import_muxer_help(FLV_HELP).unwrap();
+        db.import_muxer_help(MPEGTS_HELP).unwrap();
+        db.import_filters(FILTERS).unwrap();
+        db
+    }
+    
+    #[test]
+    fn test_import_codecs_and_encoders() {
+        let db = imported_db();
+        let x264 = db.get_codec("libx264").unwrap();
+        assert!(x264.is_encoder && !x264.is_decoder && x264.stream_type == StreamType::Video);
+        assert_eq!(db.codec_identity("libx264"), "h264");
+        let h264 = db.get_codec("h264").unwrap();
+        assert!(h264.is_decoder && !h264.is_encoder);
+        assert!(["libx264", "h264_nvenc"].iter().all(|e| db.encoders_for("h264").contains(e)));
+        assert_eq!(db.codec_identity("libsvtav1"), "av1");
+        assert_eq!(db.codec_identity("srt"), "subrip");
+        
+        let pgs = db.get_codec("hdmv_pgs_subtitle").unwrap();
+        assert!(!pgs.is_decoder && !pgs.is_encoder);
+        assert_eq!(db.decoders_for("hdmv_pgs_subtitle"), vec!["pgssub"]);
+        assert!(db.get_codec("mp3float").is_some_and(|c| c.is_decoder && !c.is_encoder));
+        assert!(db.get_codec("flac").is_some_and(|c| c.is_decoder && c.is_encoder && c.stream_type == StreamType::Audio));
+        // Data codecs keep their own stream type
+        assert!(db.get_codec("bin_data").is_some_and(|c| c.stream_type == StreamType::Data));
+    }
+    
+    #[test]
+    fn test_import_muxers() {
+        let db = imported_db();
+        assert_eq!(db.infer_format_from_filename("clip.m2ts").as_deref(), Some("mpegts"));
+        assert_eq!(db.infer_format_from_filename("clip.flv").as_deref(), Some("flv"));
+        assert_eq!(db.default_subtitle_codec("mpegts"), None);
+        assert!(db.get_format("mpegts").unwrap().supported_subtitle_codecs.contains(&"dvb_subtitle".to_string()));
+        // Only default codecs are known, so other codecs are not rejected
+        assert!(db.is_codec_supported_in_format("libx264", "mpegts"));
+        assert!(db.is_codec_supported_in_format("libx264", "hls"));
+        assert!(!db.is_codec_supported_in_format("unknown_codec", "hls"));
+    }
+    
+    #[test]
+    fn test_import_filters() {
+        let db = imported_db();
+        let showwaves = db.get_filter("showwaves").unwrap();
+        assert_eq!((&showwaves.input_type, &showwaves.output_type), (&StreamType::Audio, &StreamType::Video));
+        assert_eq!(db.get_filter("colorchannelmixer").unwrap().description, "Adjust colors by mixing color channels");
+        assert_eq!(db.get_filter("testsrc2").unwrap().input_type, StreamType::Unknown);
+        assert_eq!(db.get_filter("amix").unwrap().output_type, StreamType::Audio);
+        assert_eq!(db.get_filter("amix").unwrap().input_pads, None);
+        assert_eq!(db.get_filter("overlay").unwrap().input_pads, Some(2));
+        assert_eq!(db.get_filter("testsrc2").unwrap().input_pads, Some(0));
+        assert!(db.get_filter("drawtext").is_none());
+        assert!(db.filters_complete && !CodecDatabase::new().filters_complete);
+    }
+    
+    #[test]
+    fn test_augment_builtin_database() {
+        let mut db = CodecDatabase::new();
+        db.import_encoders(ENCODERS).unwrap();
+        db.import_muxers(MUXERS).unwrap();
+        // Curated entries stay, imported ones are added
+        assert!(!db.is_codec_supported_in_format("libvpx-vp9", "mp4"));
+        assert!(db.get_codec("libsvtav1").is_some());
+        assert!(db.get_format("flv").is_some());
+        
+        let codes = |db: &CodecDatabase| {
+            let cmd = crate::parser::parse_command("ffmpeg -i in.mp4 -c:v libsvtav1 out.mkv", 0, 0).unwrap();
+            crate::analyzer::analyze_command_with_database(cmd, Default::default(), db).messages.into_iter().map(|d| d.code).collect::<Vec<_>>()
+        };
+        assert!(codes(&CodecDatabase::new()).contains(&"W201".to_string()));
+        assert!(!codes(&db).contains(&"W201".to_string()));
+    }
+    
+    #[test]
+    fn test_reject_other_text() {
+        let mut db = CodecDatabase::empty();
+        assert!(db.import_encoders("ffmpeg version 6.1").is_err());
+        assert!(db.import_filters(ENCODERS).is_err());
+        assert!(db.import_muxer_help(MUXERS).is_err());
+    }
+}
@@ -0,0 +1,479 @@
+//! Completion candidates for the word being typed at the cursor
+
+use crate::ast::{FfmpegCommand, OptionNode, OutputSpec, GLOBAL_OPTIONS};
+use crate::codec_db::{CodecDatabase, KNOWN_ENCODER_OPTIONS, STREAM_COPY};
+use crate::hover::{output_encoder, output_format};
+use crate::messages::{stream_type_name, Locale, Message};
+use crate::option_rules::{option_description, FILE_OPTIONS, INPUT_ONLY_OPTIONS, OUTPUT_ONLY_OPTIONS};
+use crate::parser::parse_command;
+use crate::stream_tracker::{matches_stream_type, StreamTracker};
+use shared_types::{CompletionItem, CompletionKind, CompletionList, MediaFile, SourceCodeSpan, StreamType};
+use std::ops::Range;
+
+/// Options that take no value: the grammar's boolean flags and the other switches
+const SWITCHES: &[&str] = &[
+    "-y", "-n", "-an", "-vn", "-sn", "-dn", "-shortest", "-hide_banner", "-nostdin", "-stats", "-nostats",
+    "-copyts", "-re", "-benchmark", "-benchmark_all", "-report", "-xerror", "-ignore_unknown", "-copy_unknown",
+    "-start_at_zero", "-accurate_seek", "-noaccurate_seek",
+];
+
+/// Options offered with a stream specifier, since that is how they are usually written
+const INPUT_SPECIFIED_OPTIONS: &[&str] = &["-c:v", "-c:a", "-c:s"];
+const OUTPUT_SPECIFIED_OPTIONS: &[&str] = &["-c:v", "-c:a", "-c:s", "-b:v", "-b:a", "-filter:v", "-filter:a"];
+
+/// Where an option at the cursor applies
+#[derive(Clone, Copy)]
+enum Scope<'a> {
+    Input,
+    /// The output the option belongs to, when it is already written
+    Output(Option<&'a OutputSpec>),
+}
+
+/// Whitespace-separated words of a line as byte ranges; quoted text stays in one word
+fn words(line: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut quote = None;
+    for (i, c) in line.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c.is_whitespace() => {
+                if let Some(s) = start.take() {
+                    words.push(s..i);
+                }
+            }
+            None => {
+                start.get_or_insert(i);
+                if c == '"' || c == '\'' {
+                    quote = Some(c);
+                }
+            }
+        }
+    }
+    if let Some(s) = start {
+        words.push(s..line.len());
+    }
+    words
+}
+
+/// The command parsed with the word being typed left out, or replaced by a placeholder when it
+/// is the value of the option `flag`, so the words after the cursor still parse; failing that,
+/// the words before the option alone
+fn partial_command(text: &str, word: &Range<usize>, flag: Option<&Range<usize>>, (line, column): (usize, usize)) -> Option<FfmpegCommand> {
+    let placeholder = if flag.is_some() { "0" } else { "" };
+    let completed = format!("{}{}{}", &text[..word.start], placeholder, &text[word.end..]);
+    let before = &text[..flag.map_or(word.start, |f| f.start)];
+    parse_command(&completed, line, column)
+        .or_else(|_| parse_command(before.trim_end(), line, column))
+        .ok()
+}
+
+/// Line and column of a byte of a command whose text starts at `line` and `column`; wrapped
+/// lines start at column 0
+fn position_of(text: &str, (line, column): (usize, usize), byte: usize) -> (usize, usize) {
+    let before = &text[..byte];
+    match before.rfind('\n') {
+        Some(newline) => (line + before.matches('\n').count(), before[newline + 1..].chars().count()),
+        None => (line, column + before.chars().count()),
+    }
+}
+
+/// Byte of a command whose text starts at `line` and `column` at the cursor position, or the
+/// end of the cursor's line when the column is past it
+fn byte_at(text: &str, (line, column): (usize, usize), (cursor_line, cursor_column): (usize, usize)) -> usize {
+    let mut line_start = 0;
+    for (idx, text_line) in text.split('\n').enumerate() {
+        if line + idx == cursor_line {
+            let first_column = if idx == 0 { column } else { 0 };
+            let offset = text_line.char_indices().nth(cursor_column.saturating_sub(first_column)).map_or(text_line.len(), |(i, _)| i);
+            return line_start + offset;
+        }
+        line_start += text_line.len() + 1;
+    }
+    text.len()
+}
+
+/// Completions for the word at the `cursor` line and column of a command that starts at
+/// `start`; the command may be wrapped over several lines. `media` describes the input files,
+/// as for analysis; details are in `locale`
+pub fn complete(
+    text: &str,
+    db: &CodecDatabase,
+    media: Vec<MediaFile>,
+    start: (usize, usize),
+    cursor: (usize, usize),
+    locale: Locale,
+) -> CompletionList {
+    let position = byte_at(text, start, cursor);
+    let words = words(text);
+    let current = words.iter().position(|w| w.start < position && position <= w.end);
+    let (word, previous) = match current {
+        Some(idx) => (words[idx].clone(), idx.checked_sub(1).map(|p| &words[p])),
+        None => (position..position, words.iter().rev().find(|w| w.end < position)),
+    };
+    let (cursor_line, cursor_column) = cursor;
+    let empty = CompletionList {
+        range: SourceCodeSpan { start_line: cursor_line, start_column: cursor_column, end_line: cursor_line, end_column: cursor_column },
+        items: Vec::new(),
+    };
+    // Nothing to complete in the program name or before it
+    let Some(previous) = previous else { return empty };
+    let typed = &text[word.start..position];
+    let takes_value = |p: &str| p.starts_with('-') && !SWITCHES.contains(&p) && !typed.starts_with('-');
+    let flag = Some(&text[previous.clone()]).filter(|p| takes_value(p));
+    
+    let command = partial_command(text, &word, flag.and(Some(previous)), start);
+    let mut tracker = StreamTracker::with_database(db.clone(), media);
+    if let Some(command) = &command {
+        tracker.analyze_inputs(&command.inputs);
+    }
+    let scope = scope(command.as_ref(), &text[..word.start], position_of(text, start, word.start));
+    let context = CompletionContext { db, command: command.as_ref(), tracker: &tracker, scope, locale };
+    
+    let (start_byte, items) = match flag {
+        None => (word.start, context.options(typed)),
+        Some("-c:v" | "-vcodec" | "-codec:v") => (word.start, context.codecs(Some(StreamType::Video), typed)),
+        Some("-c:a" | "-acodec" | "-codec:a") => (word.start, context.codecs(Some(StreamType::Audio), typed)),
+        Some("-c:s" | "-scodec" | "-codec:s") => (word.start, context.codecs(Some(StreamType::Subtitle), typed)),
+        Some("-c" | "-codec") => (word.start, context.codecs(None, typed)),
+        Some("-vf" | "-filter:v") => context.filter_graph(text, word.start, position, StreamType::Video),
+        Some("-af" | "-filter:a") => context.filter_graph(text, word.start, position, StreamType::Audio),
+        Some("-filter_complex" | "-lavfi" | "-filter") => context.filter_graph(text, word.start, position, StreamType::Unknown),
+        Some("-map") => (word.start, context.map_targets(typed)),
+        Some(_) => return empty,
+    };
+    let (start_line, start_column) = position_of(text, start, start_byte);
+    CompletionList {
+        range: SourceCodeSpan { start_line, start_column, end_line: cursor_line, end_column: cursor_column },
+        items,
+    }
+}
+
+/// Whether an option at the line and column `start` is written for an input or an output: it
+/// applies to the next file, an input when its path follows `-i`. After the last file, it
+/// starts a new output once the command has an input
+fn scope<'a>(command: Option<&'a FfmpegCommand>, before: &str, start: (usize, usize)) -> Scope<'a> {
+    let Some(command) = command else {
+        return if before.split_whitespace().any(|w| w == "-i") { Scope::Output(None) } else { Scope::Input };
+    };
+    let path_start = |span: &SourceCodeSpan| (span.start_line, span.start_column);
+    let next_input = command.inputs.iter().map(|i| path_start(&i.file_path_span)).find(|p| *p >= start);
+    let next_output = command.outputs.iter().find(|o| path_start(&o.file_path_span) >= start);
+    match (next_input, next_output) {
+        (Some(input), Some(output)) if input < path_start(&output.file_path_span) => Scope::Input,
+        (Some(_), None) => Scope::Input,
+        (_, Some(output)) => Scope::Output(Some(output)),
+        (None, None) if command.inputs.is_empty() => Scope::Input,
+        (None, None) => Scope::Output(None),
+    }
+}
+
+fn item(label: &str, kind: CompletionKind, detail: Option<String>, insert_text: String) -> CompletionItem {
+    CompletionItem { label: label.to_string(), kind, detail, insert_text }
+}
+
+struct CompletionContext<'a> {
+    db: &'a CodecDatabase,
+    command: Option<&'a FfmpegCommand>,
+    tracker: &'a StreamTracker,
+    scope: Scope<'a>,
+    locale: Locale,
+}
+
+impl CompletionContext<'_> {
+    /// Options valid where the cursor is: the encoder options of the output's encoders and the
+    /// per-file options of an input or output, then the global options
+    fn options(&self, typed: &str) -> Vec<CompletionItem> {
+        let mut names: Vec<String> = Vec::new();
+        match self.scope {
+            Scope::Input => {
+                names.extend(INPUT_SPECIFIED_OPTIONS.iter().map(|o| o.to_string()));
+                names.extend(FILE_OPTIONS.iter().filter(|o| !OUTPUT_ONLY_OPTIONS.contains(o)).map(|o| o.to_string()));
+            }
+            Scope::Output(output) => {
+                let encoder_options: Vec<&str> = output
+                    .into_iter()
+                    .flat_map(|o| [StreamType::Video, StreamType::Audio].map(|t| output_encoder(o, self.db, &t)))
+                    .flatten()
+                    .filter_map(|encoder| self.db.get_encoder_options(encoder))
+                    .flat_map(|info| info.options.iter().map(|o| o.name.as_str()))
+                    .collect();
+                let encoder_options = if encoder_options.is_empty() { KNOWN_ENCODER_OPTIONS.to_vec() } else { encoder_options };
+                names.extend(encoder_options.iter().map(|o| format!("-{}", o)));
+                names.extend(OUTPUT_SPECIFIED_OPTIONS.iter().map(|o| o.to_string()));
+                names.extend(FILE_OPTIONS.iter().filter(|o| !INPUT_ONLY_OPTIONS.contains(o)).map(|o| o.to_string()));
+            }
+        }
+        names.extend(GLOBAL_OPTIONS.iter().map(|o| o.to_string()));
+        
+        let mut items: Vec<CompletionItem> = Vec::new();
+        for name in names.iter().filter(|n| n.starts_with(typed)) {
+            if !items.iter().any(|i| i.label == *name) {
+                items.push(item(name, CompletionKind::Option, option_description(name).map(str::to_string), name.clone()));
+            }
+        }
+        items
+    }
+    
+    /// Encoders of a stream type the output's container can store, software ones first, after
+    /// `copy`; decoders for an input
+    fn codecs(&self, stream_type: Option<StreamType>, typed: &str) -> Vec<CompletionItem> {
+        let decoding = matches!(self.scope, Scope::Input);
+        let format = match self.scope {
+            Scope::Output(Some(output)) => output_format(output, self.db).filter(|f| self.db.get_format(f).is_some()),
+            _ => None,
+        };
+        let mut codecs: Vec<_> = self
+            .db
+            .codecs
+            .values()
+            .filter(|c| stream_type.as_ref().is_none_or(|t| c.stream_type == *t))
+            .filter(|c| if decoding { c.is_decoder } else { c.is_encoder })
+            .filter(|c| c.availability.is_current())
+            .filter(|c| format.as_ref().is_none_or(|f| self.db.is_codec_supported_in_format(&c.name, f)))
+            .filter(|c| c.name.starts_with(typed))
+            .collect();
+        codecs.sort_by_key(|c| (self.db.hardware_family_of(&c.name).is_some(), c.name.clone()));
+        
+        let mut items = Vec::new();
+        if !decoding && STREAM_COPY.starts_with(typed) {
+            let detail = self.locale.text("copy-description");
+            items.push(item(STREAM_COPY, CompletionKind::Codec, Some(detail), STREAM_COPY.to_string()));
+        }
+        for codec in codecs {
+            let role = Message::new(if decoding { "codec-role-decoder" } else { "codec-role-encoder" }).arg("stream", stream_type_name(&codec.stream_type));
+            let identity = self.db.codec_identity(&codec.name);
+            let role = match identity != codec.name {
+                true => Message::new("codec-role-for").arg("role", role).arg("codec", identity),
+                false => role,
+            };
+            let mut detail = role.render(self.locale);
+            if let Some(family) = self.db.hardware_family_of(&codec.name) {
+                detail = format!("{} ({})", detail, family.name);
+            }
+            items.push(item(&codec.name, CompletionKind::Codec, Some(detail), codec.name.clone()));
+        }
+        items
+    }
+    
+    /// Filter names, parameter names or link labels inside the filter graph that starts at
+    /// `start`, with where the completed text starts
+    fn filter_graph(&self, line: &str, start: usize, position: usize, stream_type: StreamType) -> (usize, Vec<CompletionItem>) {
+        let start = if line[start..].starts_with(['"', '\'']) { start + 1 } else { start };
+        let graph = &line[start..position];
+        let mut offset = graph.rfind([',', ';']).map_or(0, |i| i + 1);
+        offset += graph[offset..].len() - graph[offset..].trim_start().len();
+        // Skip the input labels of the filter
+        while graph[offset..].starts_with('[') {
+            let Some(end) = graph[offset..].find(']') else {
+                return (start + offset, self.labels(Some(graph), &graph[offset..]));
+            };
+            offset += end + 1;
+            offset += graph[offset..].len() - graph[offset..].trim_start().len();
+        }
+        let filter = &graph[offset..];
+        if filter.contains('[') {
+            return (position, Vec::new());
+        }
+        let Some((name, params)) = filter.split_once('=') else {
+            return (start + offset, self.filters(filter, &stream_type));
+        };
+        let param_start = params.rfind(':').map_or(0, |i| i + 1);
+        let param = &params[param_start..];
+        let Some(info) = self.db.get_filter(name).filter(|_| !param.contains('=')) else {
+            return (position, Vec::new());
+        };
+        let items = info
+            .params
+            .iter()
+            .filter(|p| p.name.starts_with(param))
+            .map(|p| item(&p.name, CompletionKind::FilterParameter, Some(format!("{} ({})", p.description, p.value)), format!("{}=", p.name)))
+            .collect();
+        (position - param.len(), items)
+    }
+    
+    /// Filters that take a stream type, by name
+    fn filters(&self, typed: &str, stream_type: &StreamType) -> Vec<CompletionItem> {
+        let mut filters: Vec<_> = self
+            .db
+            .filters
+            .values()
+            .filter(|f| f.name.starts_with(typed) && matches_stream_type(&f.input_type, stream_type) && f.availability.is_current())
+            .collect();
+        filters.sort_by(|a, b| a.name.cmp(&b.name));
+        filters
+            .into_iter()
+            .map(|f| item(&f.name, CompletionKind::Filter, Some(f.description.clone()), f.name.clone()))
+            .collect()
+    }
+    
+    /// Input stream specifiers and filter graph labels as `[label]`: the labels of `graph`,
+    /// or the outputs of the command's filter graphs when it is None
+    fn labels(&self, graph: Option<&str>, typed: &str) -> Vec<CompletionItem> {
+        let mut items: Vec<CompletionItem> = self
+            .stream_specifiers()
+            .into_iter()
+            .map(|(specifier, detail)| item(&specifier, CompletionKind::StreamSpecifier, Some(detail), format!("[{}]", specifier)))
+            .collect();
+        let mut labels: Vec<(String, String)> = Vec::new();
+        match graph {
+            Some(graph) => {
+                let names = graph.split('[').skip(1).filter_map(|part| part.split_once(']')).map(|(name, _)| name);
+                labels.extend(names.filter(|n| !n.starts_with(|c: char| c.is_ascii_digit())).map(|n| (n.to_string(), self.locale.text("filter-graph-link"))));
+            }
+            None => {
+                let options = self.command.into_iter().flat_map(|c| c.global_options.iter().chain(c.outputs.iter().flat_map(|o| &o.options)));
+                for option in options {
+                    if let OptionNode::FilterComplex { filter, .. } = option {
+                        for f in filter.filters() {
+                            let stream_type = self.db.get_filter(&f.name).map_or(StreamType::Unknown, |info| info.output_type.clone());
+                            let detail = Message::new("filter-output").arg("filter", &f.name).arg("stream", stream_type_name(&stream_type));
+                            labels.extend(f.output_labels.iter().map(|l| (l.clone(), detail.render(self.locale))));
+                        }
+                    }
+                }
+            }
+        }
+        for (label, detail) in labels {
+            if !items.iter().any(|i| i.label == label) {
+                items.push(item(&label, CompletionKind::Label, Some(detail), format!("[{}]", label)));
+            }
+        }
+        items.retain(|i| i.insert_text.starts_with(typed));
+        items
+    }
+    
+    /// What `-map` can select: input streams and filter graph outputs
+    fn map_targets(&self, typed: &str) -> Vec<CompletionItem> {
+        let mut items: Vec<CompletionItem> = self
+            .stream_specifiers()
+            .into_iter()
+            .map(|(specifier, detail)| item(&specifier, CompletionKind::StreamSpecifier, Some(detail), specifier.clone()))
+            .filter(|i| i.insert_text.starts_with(typed))
+            .collect();
+        items.extend(self.labels(None, typed).into_iter().filter(|i| matches!(i.kind, CompletionKind::Label)));
+        items
+    }
+    
+    /// Stream specifiers of the inputs' streams with a description: the whole input, its
+    /// streams of each type, and each of them when there are several
+    fn stream_specifiers(&self) -> Vec<(String, String)> {
+        let Some(command) = self.command else { return Vec::new() };
+        let mut specifiers = Vec::new();
+        for (idx, input) in command.inputs.iter().enumerate() {
+            let all = Message::new("specifier-all-streams").arg("path", &input.file_path);
+            specifiers.push((idx.to_string(), all.render(self.locale)));
+            let types = [(StreamType::Video, 'v'), (StreamType::Audio, 'a'), (StreamType::Subtitle, 's'), (StreamType::Data, 'd')];
+            for (stream_type, letter) in types {
+                let count = self.tracker.input_streams.iter().filter(|s| s.input_index == idx && s.stream_type == stream_type).count();
+                if count == 0 {
+                    continue;
+                }
+                let of_type = Message::new("specifier-type-streams").arg("stream", stream_type_name(&stream_type)).arg("path", &input.file_path);
+                specifiers.push((format!("{}:{}", idx, letter), of_type.render(self.locale)));
+                if count > 1 {
+                    for n in 0..count {
+                        let stream = Message::new("specifier-stream")
+                            .arg("stream", stream_type_name(&stream_type))
+                            .arg("index", n)
+                            .arg("path", &input.file_path);
+                        specifiers.push((format!("{}:{}:{}", idx, letter, n), stream.render(self.locale)));
+                    }
+                }
+            }
+        }
+        specifiers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn complete_at(command: &str, column: usize) -> CompletionList {
+        complete(command, &CodecDatabase::new(), Vec::new(), (1, 0), (1, column), Locale::English)
+    }
+    
+    /// Labels of the completions at a column of a one-line command
+    fn labels_at(command: &str, column: usize) -> Vec<String> {
+        complete_at(command, column).items.into_iter().map(|i| i.label).collect()
+    }
+    
+    #[test]
+    fn test_complete_codecs() {
+        let command = "ffmpeg -i input.mp4 -c:v  output.webm";
+        let encoders = labels_at(command, 25);
+        assert_eq!(encoders[0], "copy");
+        assert!(encoders.contains(&"libvpx-vp9".to_string()));
+        assert!(!encoders.contains(&"libx264".to_string()));
+        assert!(!encoders.contains(&"aac".to_string()));
+        
+        let list = complete_at("ffmpeg -i input.mp4 -c:v libx2 output.mp4", 30);
+        assert_eq!((list.range.start_column, list.range.end_column), (25, 30));
+        let labels: Vec<_> = list.items.iter().map(|i| i.label.as_str()).collect();
+        assert_eq!(labels, vec!["libx264", "libx265"]);
+        assert!(labels_at("ffmpeg -i input.mp4 -c:a ", 25).contains(&"aac".to_string()));
+    }
+    
+    #[test]
+    fn test_complete_filters() {
+        let filters = labels_at("ffmpeg -i input.mp4 -vf sca output.mp4", 26);
+        assert!(filters.contains(&"scale".to_string()));
+        assert!(labels_at("ffmpeg -i input.mp4 -af vol", 27).contains(&"volume".to_string()));
+        assert!(!labels_at("ffmpeg -i input.mp4 -vf vol", 27).contains(&"volume".to_string()));
+        
+        let command = "ffmpeg -i input.mp4 -vf \"fps=30, scale=w=1280:\" output.mp4";
+        let list = complete_at(command, 46);
+        assert!(list.items.iter().any(|i| i.label == "h" && i.insert_text == "h="));
+        assert_eq!(list.range.start_column, 46);
+        
+        let labels = labels_at("ffmpeg -i a.mp4 -i b.mp4 -filter_complex \"[0:v][1", 49);
+        assert_eq!(labels, vec!["1", "1:v", "1:a"]);
+    }
+    
+    #[test]
+    fn test_complete_map() {
+        let command = "ffmpeg -i a.mp4 -i b.mp4 -filter_complex \"[0:v][1:v]overlay[out]\" -map  output.mp4";
+        let targets = labels_at(command, 71);
+        assert_eq!(targets, vec!["0", "0:v", "0:a", "1", "1:v", "1:a", "out"]);
+        assert_eq!(labels_at(command.replace("-map  ", "-map 1: ").as_str(), 73), vec!["1:v", "1:a"]);
+    }
+    
+    #[test]
+    fn test_completion_details() {
+        let detail = |list: &CompletionList, label: &str| list.items.iter().find(|i| i.label == label).and_then(|i| i.detail.clone());
+        let map = "ffmpeg -i a.mp4 -filter_complex \"[0:v]scale=640:-2[small]\" -map  output.mp4";
+        let targets = complete_at(map, 64);
+        assert_eq!(detail(&targets, "0:a").as_deref(), Some("Audio streams of a.mp4"));
+        assert_eq!(detail(&targets, "small").as_deref(), Some("Output of filter scale (Video stream)"));
+        let codec = "ffmpeg -i a.mp4 -c:v  output.mp4";
+        let codecs = complete_at(codec, 21);
+        assert_eq!(detail(&codecs, "copy").as_deref(), Some("Copy the streams without re-encoding"));
+        assert_eq!(detail(&codecs, "libx264").as_deref(), Some("Video encoder for h264"));
+        
+        let german = complete(map, &CodecDatabase::new(), Vec::new(), (1, 0), (1, 64), Locale::German);
+        assert_eq!(detail(&german, "0").as_deref(), Some("Alle Streams von a.mp4"));
+        assert_eq!(detail(&german, "small").as_deref(), Some("Ausgang des Filters scale (Video-Stream)"));
+        let french = complete(codec, &CodecDatabase::new(), Vec::new(), (1, 0), (1, 21), Locale::French);
+        assert_eq!(detail(&french, "libx264").as_deref(), Some("encodeur vidéo pour h264"));
+    }
+    
+    #[test]
+    fn test_complete_options() {
+        let output = labels_at("ffmpeg -i input.mp4 -c:v libx264 -pr output.mp4", 36);
+        assert!(output.contains(&"-preset".to_string()));
+        assert!(output.contains(&"-progress".to_string()));
+        assert!(!output.contains(&"-probesize".to_string()));
+        
+        let input = labels_at("ffmpeg -pr -i input.mp4 output.mp4", 10);
+        assert!(input.contains(&"-probesize".to_string()));
+        assert!(!input.contains(&"-preset".to_string()));
+        
+        let after_switch = labels_at("ffmpeg -y -i input.mp4 -map 0 -s", 32);
+        assert!(after_switch.contains(&"-shortest".to_string()));
+        assert!(!after_switch.contains(&"-sseof".to_string()));
+        assert!(labels_at("ffmp", 4).is_empty());
+        assert!(labels_at("ffmpeg -i input.mp4 -crf ", 25).is_empty());
+    }
+}
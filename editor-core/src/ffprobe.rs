@@ -0,0 +1,161 @@
+use serde::Deserialize;
+use shared_types::{MediaFile, MediaStream, StreamType};
+
+/// Output of `ffprobe -v quiet -print_format json -show_streams -show_format`
+#[derive(Deserialize)]
+struct ProbeOutput {
+    #[serde(default)]
+    streams: Vec<ProbeStream>,
+    format: Option<ProbeFormat>,
+}
+
+/// ffprobe prints most numbers as strings (`"sample_rate": "48000"`), so those stay strings here
+#[derive(Deserialize)]
+struct ProbeStream {
+    codec_type: Option<String>,
+    codec_name: Option<String>,
+    width: Option<usize>,
+    height: Option<usize>,
+    avg_frame_rate: Option<String>,
+    r_frame_rate: Option<String>,
+    sample_rate: Option<String>,
+    channels: Option<usize>,
+    duration: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ProbeFormat {
+    filename: Option<String>,
+    duration: Option<String>,
+}
+
+/// Convert ffprobe JSON output into a media manifest entry for [`crate::analyzer::AnalysisOptions`].
+/// The entry's path is the probed `format.filename`; callers can replace it with the path used in the command.
+pub fn parse_ffprobe_json(json: &str) -> Result<MediaFile, String> {
+    let probe: ProbeOutput = serde_json::from_str(json).map_err(|e| format!("Invalid ffprobe JSON: {}", e))?;
+    if probe.streams.is_empty() && probe.format.is_none() {
+        return Err("ffprobe JSON has neither 'streams' nor 'format'; run ffprobe with -show_streams -show_format".to_string());
+    }
+    
+    let format_duration = probe.format.as_ref().and_then(|f| f.duration.as_deref()).and_then(parse_seconds);
+    let stream_duration = probe
+        .streams
+        .iter()
+        .filter_map(|s| s.duration.as_deref().and_then(parse_seconds))
+        .reduce(f64::max);
+    
+    Ok(MediaFile {
+        path: probe.format.as_ref().and_then(|f| f.filename.clone()).unwrap_or_default(),
+        duration: format_duration.or(stream_duration),
+        streams: probe.streams.iter().map(convert_stream).collect(),
+    })
+}
+
+fn convert_stream(stream: &ProbeStream) -> MediaStream {
+    let stream_type = match stream.codec_type.as_deref() {
+        Some("video") => StreamType::Video,
+        Some("audio") => StreamType::Audio,
+        Some("subtitle") => StreamType::Subtitle,
+        Some("data") => StreamType::Data,
+        _ => StreamType::Unknown,
+    };
+    // `avg_frame_rate` is "0/0" for streams without a constant rate; fall back to `r_frame_rate`
+    let frame_rate = [&stream.avg_frame_rate, &stream.r_frame_rate]
+        .into_iter()
+        .find_map(|rate| rate.as_deref().and_then(parse_rational));
+    
+    MediaStream {
+        stream_type,
+        codec: stream.codec_name.clone(),
+        width: stream.width.filter(|w| *w > 0),
+        height: stream.height.filter(|h| *h > 0),
+        frame_rate,
+        sample_rate: stream.sample_rate.as_deref().and_then(|r| r.parse().ok()).filter(|r| *r > 0),
+        channels: stream.channels.filter(|c| *c > 0),
+    }
+}
+
+fn parse_seconds(value: &str) -> Option<f64> {
+    value.parse::<f64>().ok().filter(|s| s.is_finite() && *s >= 0.0)
+}
+
+/// Parse an ffprobe rational such as `30000/1001`; `0/0` means unknown
+fn parse_rational(value: &str) -> Option<f64> {
+    let (num, den) = value.split_once('/').unwrap_or((value, "1"));
+    let rate = num.parse::<f64>().ok()? / den.parse::<f64>().ok()?;
+    (rate.is_finite() && rate > 0.0).then_some(rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const PROBE: &str = r#"{
+        "streams": [
+            {
+                "index": 0,
+                "codec_name": "h264",
+                "codec_type": "video",
+                "width": 1920,
+                "height": 1080,
+                "r_frame_rate": "30000/1001",
+                "avg_frame_rate": "30000/1001",
+                "duration": "60.060000"
+            },
+            {
+                "index": 1,
+                "codec_name": "aac",
+                "codec_type": "audio",
+                "sample_rate": "48000",
+                "channels": 2,
+                "r_frame_rate": "0/0",
+                "avg_frame_rate": "0/0",
+                "duration": "60.000000"
+            }
+        ],
+        "format": {
+            "filename": "assets/interview.mp4",
+            "nb_streams": 2,
+            "format_name": "mov,mp4,m4a,3gp,3g2,mj2",
+            "duration": "60.060000"
+        }
+    }"#;
+    
+    #[test]
+    fn test_parse_ffprobe_json() {
+        let media = parse_ffprobe_json(PROBE).unwrap();
+        assert_eq!(media.path, "assets/interview.mp4");
+        assert_eq!(media.duration, Some(60.06));
+        assert_eq!(media.streams.len(), 2);
+        
+        let video = &media.streams[0];
+        assert_eq!(video.stream_type, StreamType::Video);
+        assert_eq!(video.codec.as_deref(), Some("h264"));
+        assert_eq!((video.width, video.height), (Some(1920), Some(1080)));
+        assert!((video.frame_rate.unwrap() - 29.97).abs() < 0.01);
+        
+        let audio = &media.streams[1];
+        assert_eq!(audio.stream_type, StreamType::Audio);
+        assert_eq!((audio.sample_rate, audio.channels, audio.frame_rate), (Some(48000), Some(2), None));
+    }
+    
+    #[test]
+    fn test_invalid_ffprobe_json() {
+        assert!(parse_ffprobe_json("not json").is_err());
+        assert!(parse_ffprobe_json("{}").is_err());
+    }
+    
+    #[test]
+    fn test_analyze_against_probe() {
+        use crate::analyzer::{analyze_command_with_options, AnalysisOptions};
+        use crate::parser::parse_command;
+        
+        let media = parse_ffprobe_json(PROBE).unwrap();
+        let cmd = parse_command("ffmpeg -ss 90 -i assets/interview.mp4 -c:v libx264 -b:v 300k out.mp4", 0, 0).unwrap();
+        let options = AnalysisOptions { media: vec![media], ..AnalysisOptions::default() };
+        let codes: Vec<_> = analyze_command_with_options(cmd, options).messages.into_iter().map(|d| d.code).collect();
+        // Seek past the probed duration, and too few bits for 1080p at 29.97 fps
+        assert!(codes.contains(&"W411".to_string()));
+        assert!(codes.contains(&"W107".to_string()));
+    }
+}
@@ -1,11 +1,19 @@
 use crate::parser::parse_command;
-use crate::analyzer::analyze_command;
+use crate::analyzer::{analyze_command_with_options, AnalysisOptions};
+use crate::catalog::{explain, lookup};
+use crate::code_actions::{code_actions, Document};
+use crate::completion::complete;
+use crate::codec_db::{CodecDatabase, FfmpegVersion};
+use crate::hover::hover;
+use crate::messages::{Locale, Message};
+use crate::suppressions::{apply_suppressions, parse_suppressions};
 use shared_types::context::Context;
 use shared_types::router::{CallHandler, ObserverImpl};
 use shared_types::storage::Storage;
 use shared_types::{
-    AnalyzeCodeParams, AnalyzerDiagnostics, DiagnosticKind, DiagnosticMessage, Severity,
-    SourceCodeSpan, DiagnosticSpan, SpanRole,
+    AnalyzeCodeParams, AnalyzerDiagnostics, CodeActionParams, CodeActions, CodeExplanation, CompletionList,
+    CompletionParams, DiagnosticKind, DiagnosticMessage, ExplainCodeParams, HoverParams, HoverResult, LintConfig,
+    Severity, SetLintConfigParams, SourceCodeSpan, DiagnosticSpan, SpanRole,
 };
 use std::sync::Arc;
 
@@ -19,22 +27,137 @@ impl<S: Storage> EditorHandler<S> {
     pub fn new(storage: Option<Arc<S>>) -> Self {
         Self { storage }
     }
+
+    /// Lint configuration stored for the session by `set_lint_config`
+    fn session_lint_config(&self, ctx: &Context) -> Option<LintConfig> {
+        let bytes = self.storage.as_ref()?.get(&lint_config_key(ctx))?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    fn analysis_options(&self, ctx: &Context, params: &AnalyzeCodeParams) -> AnalysisOptions {
+        AnalysisOptions {
+            media: params.media.clone().unwrap_or_default(),
+            ffmpeg_version: params.ffmpeg_version.as_deref().and_then(FfmpegVersion::parse),
+            lint: params.lint.clone().or_else(|| self.session_lint_config(ctx)),
+            locale: params.locale.as_deref().and_then(Locale::from_tag).unwrap_or_default(),
+        }
+    }
+}
+
+fn lint_config_key(ctx: &Context) -> String {
+    format!("lint-config:{}", ctx.session_id)
 }
 
 impl<S: Storage> CallHandler for EditorHandler<S> {
     fn analyze_code(
         &self,
-        _ctx: &Context,
+        ctx: &Context,
         params: AnalyzeCodeParams,
         tx: ObserverImpl<AnalyzerDiagnostics>,
     ) {
         // Parse and analyze FFmpeg command with offsets
-        let analysis_result = analyze_content(&params.content, params.line_offset, params.column_offset);
+        let options = self.analysis_options(ctx, &params);
+        let analysis_result = analyze_content(&params.content, params.line_offset, params.column_offset, options);
 
         tx.next(analysis_result);
         tx.complete("Analysis complete".to_string());
     }
 
+    fn code_actions(
+        &self,
+        ctx: &Context,
+        params: CodeActionParams,
+        tx: ObserverImpl<CodeActions>,
+    ) {
+        let analysis = &params.analysis;
+        let options = self.analysis_options(ctx, analysis);
+        let locale = options.locale;
+        let diagnostics = analyze_content(&analysis.content, analysis.line_offset, analysis.column_offset, options);
+        let document = Document::new(&analysis.content, analysis.line_offset, analysis.column_offset);
+        let actions = code_actions(&document, &diagnostics.messages, &params.range, params.code.as_deref(), locale);
+
+        tx.next(CodeActions { actions });
+        tx.complete("Code actions computed".to_string());
+    }
+
+    fn hover(
+        &self,
+        ctx: &Context,
+        params: HoverParams,
+        tx: ObserverImpl<HoverResult>,
+    ) {
+        let analysis = &params.analysis;
+        let options = self.analysis_options(ctx, analysis);
+        let hover = command_at(analysis, params.line)
+            .and_then(|(command, line, column)| parse_command(command, line, column).ok())
+            .and_then(|command| hover(&command, &CodecDatabase::new(), options.media, params.line, params.column, options.locale));
+
+        tx.next(HoverResult { hover });
+        tx.complete("Hover computed".to_string());
+    }
+
+    fn complete(
+        &self,
+        ctx: &Context,
+        params: CompletionParams,
+        tx: ObserverImpl<CompletionList>,
+    ) {
+        let options = self.analysis_options(ctx, &params.analysis);
+        let list = complete_content(&params, options);
+
+        tx.next(list);
+        tx.complete("Completions computed".to_string());
+    }
+
+    fn explain_code(
+        &self,
+        _ctx: &Context,
+        params: ExplainCodeParams,
+        tx: ObserverImpl<CodeExplanation>,
+    ) {
+        let Some(info) = lookup(params.code.trim()) else {
+            tx.error(format!("Unknown diagnostic code '{}'", params.code));
+            return;
+        };
+
+        tx.next(CodeExplanation {
+            code: info.code.to_string(),
+            severity: info.severity.clone(),
+            title: info.title.to_string(),
+            rich: explain(info),
+        });
+        tx.complete("Code explained".to_string());
+    }
+
+    fn set_lint_config(
+        &self,
+        ctx: &Context,
+        params: SetLintConfigParams,
+        tx: ObserverImpl<LintConfig>,
+    ) {
+        let Some(storage) = &self.storage else {
+            tx.error("Lint configuration needs session storage; pass it with each analyze_code call instead".to_string());
+            return;
+        };
+        let key = lint_config_key(ctx);
+        let Some(config) = params.config else {
+            storage.delete(&key);
+            tx.next(LintConfig { codes: Vec::new(), categories: Vec::new() });
+            tx.complete("Lint configuration cleared".to_string());
+            return;
+        };
+        match serde_json::to_vec(&config) {
+            Ok(bytes) => storage.set(&key, bytes),
+            Err(e) => {
+                tx.error(format!("Could not store lint configuration: {}", e));
+                return;
+            }
+        }
+
+        tx.next(config);
+        tx.complete("Lint configuration stored".to_string());
+    }
+
     // Keep the pathfinder methods for now (we'll remove them during cleanup)
     fn find_shortest_path(
         &self,
@@ -55,16 +178,90 @@ impl<S: Storage> CallHandler for EditorHandler<S> {
     }
 }
 
-/// Analyze FFmpeg command and return diagnostics with offset support
-fn analyze_content(content: &str, line_offset: usize, column_offset: usize) -> AnalyzerDiagnostics {
+/// Analyze FFmpeg commands and return diagnostics with offset support.
+/// Blank lines and `#` comments are skipped; `# ffmpeg-lint:` pragma comments suppress diagnostics
+pub(crate) fn analyze_content(content: &str, line_offset: usize, column_offset: usize, options: AnalysisOptions) -> AnalyzerDiagnostics {
+    let locale = options.locale;
+    let mut messages = Vec::new();
+    for (command, line, column) in split_commands(content, line_offset, column_offset) {
+        messages.extend(analyze_command_line(command, line, column, options.clone()).messages);
+    }
+
+    let suppressions = parse_suppressions(content, line_offset, column_offset);
+    AnalyzerDiagnostics { messages: apply_suppressions(messages, &suppressions, locale) }
+}
+
+/// The command wrapped over the given line, with the line and column it starts at
+fn command_at(analysis: &AnalyzeCodeParams, line: usize) -> Option<(&str, usize, usize)> {
+    split_commands(&analysis.content, analysis.line_offset, analysis.column_offset)
+        .into_iter()
+        .find(|(command, start, _)| (*start..=start + command.matches('\n').count()).contains(&line))
+}
+
+/// Completions at the cursor, within the whole command the cursor's line belongs to so that
+/// words on the lines it is wrapped over count. Blank lines and comments complete to nothing
+fn complete_content(params: &CompletionParams, options: AnalysisOptions) -> CompletionList {
+    let analysis = &params.analysis;
+    let cursor = (params.line, params.column);
+    let Some((command, line, column)) = command_at(analysis, params.line) else {
+        return complete("", &CodecDatabase::new(), options.media, cursor, cursor, options.locale);
+    };
+    // The command's last line is trimmed, but the cursor may follow trailing whitespace
+    let last_line = line - analysis.line_offset + command.matches('\n').count();
+    let last_line = analysis.content.split('\n').nth(last_line).unwrap_or("").trim_end_matches('\r');
+    let text = format!("{}{}", command, &last_line[last_line.trim_end().len()..]);
+    complete(&text, &CodecDatabase::new(), options.media, (line, column), cursor, options.locale)
+}
+
+/// Whether a line holds command text rather than being blank or a `#` comment
+pub(crate) fn is_command_line(line: &str) -> bool {
+    let text = line.trim();
+    !text.is_empty() && !text.starts_with('#')
+}
+
+/// Whether a line continues the command on the line before it. A long command may be wrapped
+/// over several lines; it ends at a blank line, a comment or a line starting with `ffmpeg`
+pub(crate) fn continues_command(line: &str) -> bool {
+    is_command_line(line) && line.split_whitespace().next() != Some("ffmpeg")
+}
+
+/// The commands of the content with the line and column they start at. Each command is
+/// the text from its first to its last line, so the parser numbers wrapped lines correctly
+fn split_commands(content: &str, line_offset: usize, column_offset: usize) -> Vec<(&str, usize, usize)> {
+    let mut commands = Vec::new();
+    // Byte range, line and column of the command being read
+    let mut current: Option<(usize, usize, usize, usize)> = None;
+    let mut line_start = 0;
+    for (idx, line) in content.split('\n').enumerate() {
+        let end = line_start + line.trim_end().len();
+        match &mut current {
+            Some((_, command_end, _, _)) if continues_command(line) => *command_end = end,
+            _ => {
+                commands.extend(current.take().map(|(start, end, line, column)| (&content[start..end], line, column)));
+                if is_command_line(line) {
+                    let indent = line.len() - line.trim_start().len();
+                    let column = indent + if idx == 0 { column_offset } else { 0 };
+                    current = Some((line_start + indent, end, line_offset + idx, column));
+                }
+            }
+        }
+        line_start += line.len() + 1;
+    }
+    commands.extend(current.map(|(start, end, line, column)| (&content[start..end], line, column)));
+    commands
+}
+
+/// Analyze a single FFmpeg command
+fn analyze_command_line(content: &str, line_offset: usize, column_offset: usize, options: AnalysisOptions) -> AnalyzerDiagnostics {
     // Parse the FFmpeg command with offsets
     match parse_command(content, line_offset, column_offset) {
         Ok(command) => {
             // Run semantic analysis
-            analyze_command(command)
+            analyze_command_with_options(command, options)
         }
         Err(parse_error) => {
             // Return parse error as diagnostic
+            let locale = options.locale;
             AnalyzerDiagnostics {
                 messages: vec![DiagnosticMessage {
                     code: "E000".to_string(),
@@ -72,7 +269,7 @@ fn analyze_content(content: &str, line_offset: usize, column_offset: usize) -> A
                     kind: DiagnosticKind::ParseError {
                         message: parse_error.clone(),
                     },
-                    message: format!("Failed to parse FFmpeg command: {}", parse_error),
+                    message: Message::new("parse-failed").arg("error", &parse_error).render(locale),
                     spans: vec![DiagnosticSpan {
                         span: SourceCodeSpan {
                             start_line: line_offset,
@@ -81,7 +278,7 @@ fn analyze_content(content: &str, line_offset: usize, column_offset: usize) -> A
                             end_column: column_offset + content.len().min(100),
                         },
                         role: SpanRole::Target,
-                        message: "parse error here".to_string(),
+                        message: locale.text("span-parse-error"),
                     }],
                     rich: None,
                 }],
@@ -97,7 +294,7 @@ mod tests {
 
     #[test]
     fn test_analyze_valid_command() {
-        let result = analyze_content("ffmpeg -i input.mp4 output.mp4", 0, 0);
+        let result = analyze_content("ffmpeg -i input.mp4 output.mp4", 0, 0, AnalysisOptions::default());
         // Valid command should have no errors (may have warnings)
         let has_errors = result.messages.iter().any(|m| matches!(m.severity, Severity::Error));
         assert!(!has_errors);
@@ -105,7 +302,7 @@ mod tests {
 
     #[test]
     fn test_analyze_video_codec_on_audio() {
-        let result = analyze_content("ffmpeg -i audio.mp3 -c:v libx264 output.mp4", 0, 0);
+        let result = analyze_content("ffmpeg -i audio.mp3 -c:v libx264 output.mp4", 0, 0, AnalysisOptions::default());
         // Should detect video codec on audio-only input
         let has_error = result.messages.iter().any(|m| m.code == "E104");
         assert!(has_error);
@@ -113,7 +310,7 @@ mod tests {
 
     #[test]
     fn test_analyze_invalid_resolution() {
-        let result = analyze_content("ffmpeg -i input.mp4 -s 1920 output.mp4", 0, 0);
+        let result = analyze_content("ffmpeg -i input.mp4 -s 1920 output.mp4", 0, 0, AnalysisOptions::default());
         // Should detect invalid resolution format
         let has_error = result.messages.iter().any(|m| m.code == "E401");
         assert!(has_error);
@@ -121,10 +318,78 @@ mod tests {
 
     #[test]
     fn test_analyze_codec_format_incompatibility() {
-        let result = analyze_content("ffmpeg -i input.mp4 -c:v vp9 output.mp4", 0, 0);
+        let result = analyze_content("ffmpeg -i input.mp4 -c:v vp9 output.mp4", 0, 0, AnalysisOptions::default());
         // VP9 is not compatible with MP4 container
         let has_error = result.messages.iter().any(|m| m.code == "E201");
         assert!(has_error);
+
+        // Encoder libraries are stored as the codec they implement
+        for command in ["ffmpeg -i in.wav -c:a libopus out.mkv", "ffmpeg -i in.wav -c:a libmp3lame out.mkv", "ffmpeg -i in.mp4 -c:a libfdk_aac out.mp4"] {
+            let result = analyze_content(command, 0, 0, AnalysisOptions::default());
+            assert!(!result.messages.iter().any(|m| m.code == "E201"), "{}", command);
+        }
+    }
+
+    #[test]
+    fn test_suppression_pragmas() {
+        let content = "# ffmpeg-lint: disable-next-line=E401\nffmpeg -i input.mp4 -s 1920 output.mp4";
+        let result = analyze_content(content, 1, 0, AnalysisOptions::default());
+        assert!(!result.messages.iter().any(|m| m.code == "E401"));
+
+        // A pragma covering several commands is unused only if none of them needed it
+        let content = "# ffmpeg-lint: disable=W101\nffmpeg -i input.mp4 out.mp4\n  ffmpeg -i input.mp4 -b:v 80M out.mp4\nffmpeg -i";
+        let result = analyze_content(content, 1, 0, AnalysisOptions::default());
+        assert!(!result.messages.iter().any(|m| m.code == "W101" || m.code == "I003"));
+        let parse_error = result.messages.iter().find(|m| m.code == "E000").unwrap();
+        assert_eq!((parse_error.spans[0].span.start_line, parse_error.spans[0].span.start_column), (4, 0));
+        let overview_span = &result.messages.iter().find(|m| m.code == "I001" && m.spans[0].span.start_line == 3).unwrap().spans[0].span;
+        assert_eq!(overview_span.start_column, 2);
+    }
+
+    #[test]
+    fn test_wrapped_command() {
+        let content = "ffmpeg -i input.mp4 \\\n  -c:v libx264 -s 1920\n  output.mp4\n\nffmpeg -i input.mp4\n  output.mkv";
+        let commands = split_commands(content, 1, 0);
+        assert_eq!(commands.iter().map(|(_, line, _)| *line).collect::<Vec<_>>(), vec![1, 5]);
+        assert_eq!(commands[1].0, "ffmpeg -i input.mp4\n  output.mkv");
+
+        let result = analyze_content(content, 1, 0, AnalysisOptions::default());
+        assert!(!result.messages.iter().any(|m| m.code == "E000"), "{:?}", result.messages);
+        let resolution = result.messages.iter().find(|m| m.code == "E401").unwrap();
+        assert_eq!((resolution.spans[0].span.start_line, resolution.spans[0].span.start_column), (2, 18));
+    }
+
+    #[test]
+    fn test_complete_wrapped_command() {
+        let complete_in = |content: &str, line: usize, column: usize| {
+            let analysis = AnalyzeCodeParams {
+                content: content.to_string(),
+                file_path: None,
+                line_offset: 1,
+                column_offset: 0,
+                media: None,
+                ffmpeg_version: None,
+                lint: None,
+                locale: None,
+            };
+            complete_content(&CompletionParams { analysis, line, column }, AnalysisOptions::default())
+        };
+        let labels = |list: &CompletionList| list.items.iter().map(|i| i.label.clone()).collect::<Vec<_>>();
+
+        // The -i on the first line makes -c:v an output option, so encoders are offered
+        let list = complete_in("ffmpeg -i input.mp4 \\\n  -c:v lib\n  output.mp4", 2, 10);
+        let x264 = list.items.iter().find(|i| i.label == "libx264").expect("libx264 offered");
+        assert_eq!(x264.detail.as_deref(), Some("Video encoder for h264"));
+        assert_eq!((list.range.start_line, list.range.start_column, list.range.end_column), (2, 7, 10));
+
+        // Encoder options come from the codec written before the cursor on the wrapped line
+        let list = complete_in("ffmpeg -i input.mp4\n  -c:v libx264 -cr\n  output.mp4", 2, 19);
+        assert_eq!(labels(&list), vec!["-crf"]);
+
+        // The cursor may follow trailing whitespace of the last line; comments complete to nothing
+        let list = complete_in("# encode\nffmpeg -i input.mp4\n  -c:a ", 3, 7);
+        assert!(labels(&list).contains(&"aac".to_string()));
+        assert!(complete_in("# encode\nffmpeg -i input.mp4", 1, 5).items.is_empty());
     }
 
     #[test]
@@ -144,10 +409,33 @@ mod tests {
             file_path: None,
             line_offset: 0,
             column_offset: 0,
+            media: None,
+            ffmpeg_version: Some("6.1".to_string()),
+            lint: None,
+            locale: None,
         };
 
         handler.analyze_code(&ctx, params, tx);
         // If we get here without panicking, the handler works
     }
+
+    #[test]
+    fn test_session_lint_config() {
+        struct MockSender;
+        impl shared_types::router::WireResponseSender for MockSender {
+            fn send_response(&self, _response: shared_types::router::WireResponse) {}
+        }
+
+        let handler = EditorHandler::new(Some(Arc::new(InMemoryStorage::new())));
+        let ctx = Context::new("lint-session".to_string(), 1);
+        let config = crate::lint::parse_lint_config("[codes]\nW101 = \"allow\"").unwrap();
+        handler.set_lint_config(&ctx, SetLintConfigParams { config: Some(config) }, ObserverImpl::new(1, Box::new(MockSender)));
+        assert_eq!(handler.session_lint_config(&ctx).map(|c| c.codes.len()), Some(1));
+        // Other sessions keep the defaults
+        assert!(handler.session_lint_config(&Context::new("other".to_string(), 2)).is_none());
+
+        handler.set_lint_config(&ctx, SetLintConfigParams { config: None }, ObserverImpl::new(2, Box::new(MockSender)));
+        assert!(handler.session_lint_config(&ctx).is_none());
+    }
 }
 
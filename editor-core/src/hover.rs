@@ -0,0 +1,468 @@
+//! Documentation of the option, codec, filter, input path or link label under the cursor
+
+use crate::ast::{FfmpegCommand, Filter, InputSpec, OptionNode, OutputSpec};
+use crate::codec_db::{CodecDatabase, CodecInfo, OptionValues, KNOWN_ENCODER_OPTIONS, STREAM_COPY};
+use crate::messages::{stream_type_name, Locale, Message};
+use crate::option_rules::option_description;
+use crate::stream_tracker::StreamTracker;
+use shared_types::{DiagnosticRich, Hover, MediaFile, RichBlock, SourceCodeSpan, StreamType};
+
+/// Where an option is written
+#[derive(Clone, Copy)]
+enum Scope<'a> {
+    Global,
+    Input(usize, &'a InputSpec),
+    Output(&'a OutputSpec),
+}
+
+/// Whether a position is inside a span; the position right after the span counts, so the
+/// cursor at the end of a word still hovers it
+fn contains(span: &SourceCodeSpan, position: (usize, usize)) -> bool {
+    (span.start_line, span.start_column) <= position && position <= (span.end_line, span.end_column)
+}
+
+/// What a codec entry does, as "Video encoder"; shared with completion details
+pub(crate) fn codec_role(info: &CodecInfo) -> Message {
+    let id = match (info.is_encoder, info.is_decoder) {
+        (true, true) => "codec-role-both",
+        (true, false) => "codec-role-encoder",
+        (false, true) => "codec-role-decoder",
+        (false, false) => "codec-role-codec",
+    };
+    Message::new(id).arg("stream", stream_type_name(&info.stream_type))
+}
+
+/// Names as inline code, comma-separated
+fn code_list<S: AsRef<str>>(names: &[S]) -> String {
+    names.iter().map(|n| format!("`{}`", n.as_ref())).collect::<Vec<_>>().join(", ")
+}
+
+/// Format an output is written in: its `-f`, or the one its extension implies
+pub(crate) fn output_format(output: &OutputSpec, db: &CodecDatabase) -> Option<String> {
+    output
+        .options
+        .iter()
+        .find_map(|o| match o {
+            OptionNode::Format { format, .. } => Some(format.clone()),
+            _ => None,
+        })
+        .or_else(|| db.infer_format_from_filename(&output.file_path))
+}
+
+/// Encoder an output selects for a stream type with `-c:v`/`-c:a`/`-c:s`, or with `-c`
+/// when the codec is of that type
+pub(crate) fn output_encoder<'a>(output: &'a OutputSpec, db: &CodecDatabase, stream_type: &StreamType) -> Option<&'a str> {
+    output.options.iter().find_map(|o| match o {
+        OptionNode::VideoCodec { codec, .. } if *stream_type == StreamType::Video => Some(codec.as_str()),
+        OptionNode::AudioCodec { codec, .. } if *stream_type == StreamType::Audio => Some(codec.as_str()),
+        OptionNode::SubtitleCodec { codec, .. } if *stream_type == StreamType::Subtitle => Some(codec.as_str()),
+        OptionNode::Codec { codec, .. } if db.get_codec(codec).is_some_and(|c| c.stream_type == *stream_type) => Some(codec.as_str()),
+        _ => None,
+    })
+}
+
+/// Documentation for the token at `line`/`column` of a command; None when nothing documented
+/// is there. `media` describes the input files, as for analysis
+pub fn hover(command: &FfmpegCommand, db: &CodecDatabase, media: Vec<MediaFile>, line: usize, column: usize, locale: Locale) -> Option<Hover> {
+    let mut tracker = StreamTracker::with_database(db.clone(), media);
+    tracker.analyze_inputs(&command.inputs);
+    let context = HoverContext { command, db, tracker: &tracker, position: (line, column), locale };
+    
+    let options = command
+        .global_options
+        .iter()
+        .map(|o| (o, Scope::Global))
+        .chain(command.inputs.iter().enumerate().flat_map(|(idx, input)| input.options.iter().map(move |o| (o, Scope::Input(idx, input)))))
+        .chain(command.outputs.iter().flat_map(|output| output.options.iter().map(move |o| (o, Scope::Output(output)))));
+    for (option, scope) in options {
+        if contains(option.span(), context.position) {
+            return context.option_node(option, scope);
+        }
+    }
+    
+    let (input_idx, input) = command.inputs.iter().enumerate().find(|(_, i)| contains(&i.file_path_span, context.position))?;
+    Some(context.input(input_idx, input))
+}
+
+struct HoverContext<'a> {
+    command: &'a FfmpegCommand,
+    db: &'a CodecDatabase,
+    tracker: &'a StreamTracker,
+    position: (usize, usize),
+    locale: Locale,
+}
+
+impl HoverContext<'_> {
+    fn hover(&self, span: &SourceCodeSpan, markdown: String) -> Hover {
+        Hover { span: span.clone(), rich: DiagnosticRich { blocks: vec![RichBlock::MarkdownGfm { markdown }] } }
+    }
+    
+    /// The codec, filter or label under the cursor, or else the option itself
+    fn option_node(&self, option: &OptionNode, scope: Scope) -> Option<Hover> {
+        match option {
+            OptionNode::VideoCodec { codec, codec_span, .. }
+            | OptionNode::AudioCodec { codec, codec_span, .. }
+            | OptionNode::SubtitleCodec { codec, codec_span, .. }
+            | OptionNode::Codec { codec, codec_span, .. }
+                if contains(codec_span, self.position) =>
+            {
+                return self.codec(codec, codec_span, matches!(scope, Scope::Input(..)));
+            }
+            OptionNode::VideoFilter { filter, .. } | OptionNode::AudioFilter { filter, .. } | OptionNode::FilterComplex { filter, .. } => {
+                for f in filter.filters() {
+                    let labels = f.input_labels.iter().zip(&f.input_label_spans).chain(f.output_labels.iter().zip(&f.output_label_spans));
+                    for (label, span) in labels {
+                        if contains(span, self.position) {
+                            return Some(self.label(label, span));
+                        }
+                    }
+                    if contains(&f.span, self.position) {
+                        return self.filter(f);
+                    }
+                }
+            }
+            OptionNode::Map { mapping, mapping_span, .. } if contains(mapping_span, self.position) => {
+                if let Some(label) = mapping.strip_prefix('[').and_then(|m| m.strip_suffix(']')) {
+                    return Some(self.label(label, mapping_span));
+                }
+            }
+            _ => {}
+        }
+        self.option(option, scope)
+    }
+    
+    fn option(&self, option: &OptionNode, scope: Scope) -> Option<Hover> {
+        let name = match option {
+            OptionNode::Generic { name, .. } => name.as_str(),
+            _ => option.name(),
+        };
+        let description = option_description(name);
+        let values = self.accepted_values(option, scope);
+        if description.is_none() && values.is_none() {
+            return None;
+        }
+        
+        let locale = self.locale;
+        let mut markdown = format!("## `{}`\n\n", name);
+        if let Some(description) = description {
+            markdown.push_str(&format!("{}\n\n", description));
+        }
+        let context = match scope {
+            _ if option.is_global() => Message::new("hover-global-command"),
+            Scope::Global => Message::new("hover-global"),
+            Scope::Input(idx, input) => Message::new("hover-input-option").arg("index", idx).arg("path", &input.file_path),
+            Scope::Output(output) => Message::new("hover-output-option").arg("path", &output.file_path),
+        };
+        markdown.push_str(&Message::new("hover-option-context").arg("context", context).render(locale));
+        let streams = match name.split(':').nth(1) {
+            Some(spec) if spec.starts_with('v') => Some(StreamType::Video),
+            Some(spec) if spec.starts_with('a') => Some(StreamType::Audio),
+            Some(spec) if spec.starts_with('s') => Some(StreamType::Subtitle),
+            _ => None,
+        };
+        if let Some(streams) = streams {
+            markdown.push_str(", ");
+            markdown.push_str(&Message::new("hover-option-streams").arg("stream", stream_type_name(&streams)).render(locale));
+        }
+        if let Some(values) = values {
+            markdown.push_str("\n\n");
+            markdown.push_str(&Message::new("hover-accepted-values").arg("values", values).render(locale));
+        }
+        Some(self.hover(option.span(), markdown))
+    }
+    
+    /// Values the output's encoder or container accepts for an option
+    fn accepted_values(&self, option: &OptionNode, scope: Scope) -> Option<String> {
+        let Scope::Output(output) = scope else { return None };
+        let stream_type = match option {
+            OptionNode::VideoCodec { .. } => StreamType::Video,
+            OptionNode::AudioCodec { .. } => StreamType::Audio,
+            OptionNode::SubtitleCodec { .. } => StreamType::Subtitle,
+            OptionNode::PixelFormat { .. } => {
+                let encoder = output_encoder(output, self.db, &StreamType::Video)?;
+                let formats = self.db.get_encoder_pixel_formats(encoder)?;
+                return Some(format!("{} ({})", code_list(formats), encoder));
+            }
+            OptionNode::SampleRate { .. } => {
+                let encoder = output_encoder(output, self.db, &StreamType::Audio)?;
+                let constraints = self.db.get_audio_constraints(encoder)?;
+                let rates = if constraints.sample_rates.is_empty() {
+                    Message::new("hover-up-to-hz").arg("rate", constraints.max_sample_rate?).render(self.locale)
+                } else {
+                    code_list(&constraints.sample_rates.iter().map(|r| r.to_string()).collect::<Vec<_>>())
+                };
+                return Some(format!("{} ({})", rates, encoder));
+            }
+            OptionNode::AudioChannels { .. } => {
+                let encoder = output_encoder(output, self.db, &StreamType::Audio)?;
+                let constraints = self.db.get_audio_constraints(encoder)?;
+                let channels = Message::new("hover-range").arg("min", 1u32).arg("max", constraints.max_channels).render(self.locale);
+                return Some(format!("{} ({})", channels, encoder));
+            }
+            OptionNode::Generic { name, .. } => {
+                let base = name.split(':').next().unwrap_or(name).trim_start_matches('-');
+                if !KNOWN_ENCODER_OPTIONS.contains(&base) {
+                    return None;
+                }
+                let encoder = output_encoder(output, self.db, &StreamType::Video)?;
+                let values = match &self.db.get_encoder_options(encoder)?.get_option(base)?.values {
+                    OptionValues::Range { min, max } => Message::new("hover-range").arg("min", *min).arg("max", *max).render(self.locale),
+                    OptionValues::OneOf(values) => code_list(values),
+                };
+                return Some(format!("{} ({})", values, encoder));
+            }
+            _ => return None,
+        };
+        let format = output_format(output, self.db)?;
+        let encoders = self.db.encoders_for_format(&format, &stream_type);
+        if encoders.is_empty() {
+            return None;
+        }
+        let values = Message::new("hover-storable-encoders")
+            .arg("encoders", code_list(&encoders))
+            .arg("copy", STREAM_COPY)
+            .arg("format", format);
+        Some(values.render(self.locale))
+    }
+    
+    /// A codec's type, its encoder family and the containers that can store it; `decoder` for
+    /// a codec written before `-i`
+    fn codec(&self, codec: &str, span: &SourceCodeSpan, decoder: bool) -> Option<Hover> {
+        let locale = self.locale;
+        if codec == STREAM_COPY {
+            let markdown = format!("## `copy`\n\n{}. {}", locale.text("copy-description"), locale.text("hover-copy-details"));
+            return Some(self.hover(span, markdown));
+        }
+        let info = self.db.get_codec(codec)?;
+        let identity = self.db.codec_identity(codec);
+        let role = match identity != codec {
+            true => Message::new("codec-role-for").arg("role", codec_role(info)).arg("codec", format!("**{}**", identity)),
+            false => codec_role(info),
+        };
+        let mut markdown = format!("## `{}`\n\n{}", codec, capitalize(&role.render(locale)));
+        
+        let (others_id, family) = if decoder {
+            ("hover-other-decoders", self.db.decoders_for(identity))
+        } else {
+            ("hover-other-encoders", self.db.encoders_for(identity))
+        };
+        let others: Vec<&str> = family.into_iter().filter(|name| *name != codec).collect();
+        if !others.is_empty() {
+            markdown.push_str("\n\n");
+            markdown.push_str(&Message::new(others_id).arg("codec", identity).arg("names", code_list(&others)).render(locale));
+        }
+        if let Some(family) = self.db.hardware_family_of(codec) {
+            markdown.push_str("\n\n");
+            markdown.push_str(&Message::new("hover-hardware").arg("family", &family.name).arg("platform", &family.platform).render(locale));
+        }
+        if info.experimental {
+            markdown.push_str("\n\n");
+            markdown.push_str(&locale.text("hover-experimental"));
+        }
+        
+        let containers = self.db.get_compatible_formats(codec);
+        if !decoder && !containers.is_empty() {
+            markdown.push_str("\n\n");
+            markdown.push_str(&Message::new("hover-containers").arg("formats", code_list(&containers)).render(locale));
+        }
+        Some(self.hover(span, markdown))
+    }
+    
+    /// A filter's description, pads and parameters
+    fn filter(&self, filter: &Filter) -> Option<Hover> {
+        let locale = self.locale;
+        let info = self.db.get_filter(&filter.name)?;
+        let input_type = stream_type_name(&info.input_type);
+        let inputs = match info.input_pads {
+            Some(0) => Message::new("hover-source-filter"),
+            Some(count) => Message::new("hover-pad-count").arg("count", count).arg("stream", input_type),
+            None => Message::new("hover-any-pads").arg("stream", input_type),
+        };
+        let pads = Message::new("hover-filter-pads").arg("inputs", inputs).arg("output", stream_type_name(&info.output_type));
+        let mut markdown = format!("## `{}`\n\n{}\n\n{}", info.name, info.description, pads.render(locale));
+        if let Some(family) = self.db.hardware_family_of(&info.name) {
+            markdown.push_str("\n\n");
+            markdown.push_str(&Message::new("hover-hardware").arg("family", &family.name).arg("platform", &family.platform).render(locale));
+        }
+        if !info.params.is_empty() {
+            markdown.push_str("\n\n");
+            markdown.push_str(&locale.text("hover-filter-parameters"));
+            for param in &info.params {
+                markdown.push_str(&format!("\n| `{}` | {} | {} |", param.name, param.value.replace('|', "\\|"), param.description));
+            }
+        }
+        Some(self.hover(&filter.name_span, markdown))
+    }
+    
+    /// The filter producing a link label, or the input streams an `[N:v]` label selects
+    fn label(&self, label: &str, span: &SourceCodeSpan) -> Hover {
+        let graphs = self
+            .command
+            .global_options
+            .iter()
+            .chain(self.command.outputs.iter().flat_map(|o| o.options.iter()))
+            .filter_map(|option| match option {
+                OptionNode::FilterComplex { filter, .. } | OptionNode::VideoFilter { filter, .. } | OptionNode::AudioFilter { filter, .. } => Some(filter),
+                _ => None,
+            });
+        let producer = graphs.flat_map(|spec| spec.filters()).find(|f| f.output_labels.iter().any(|l| l == label));
+        
+        let description = match producer {
+            Some(filter) => {
+                let stream_type = self.db.get_filter(&filter.name).map(|f| f.output_type.clone()).unwrap_or(StreamType::Unknown);
+                let filter_name = format!("`{}`", filter.name);
+                Message::new("filter-output").arg("filter", filter_name).arg("stream", stream_type_name(&stream_type)).render(self.locale)
+            }
+            None => self.input_label(label).unwrap_or_else(|| self.locale.text("hover-unknown-label")),
+        };
+        self.hover(span, format!("## `[{}]`\n\n{}", label, description))
+    }
+    
+    /// The streams a stream specifier label (`0:v`, `1:a:0`, `0`) selects
+    fn input_label(&self, label: &str) -> Option<String> {
+        let mut parts = label.split(':');
+        let input_idx: usize = parts.next()?.parse().ok()?;
+        let input = self.command.inputs.get(input_idx)?;
+        let stream_type = match parts.next() {
+            Some("v" | "V") => Some(StreamType::Video),
+            Some("a") => Some(StreamType::Audio),
+            Some("s") => Some(StreamType::Subtitle),
+            Some("d") => Some(StreamType::Data),
+            _ => None,
+        };
+        let locale = self.locale;
+        let streams: Vec<String> = self
+            .tracker
+            .input_streams
+            .iter()
+            .filter(|s| s.input_index == input_idx && stream_type.as_ref().is_none_or(|t| s.stream_type == *t))
+            .map(|s| format!("`{}:{}` ({})", input_idx, s.index, stream_type_name(&s.stream_type).render(locale)))
+            .collect();
+        let selected = if streams.is_empty() { locale.text("hover-no-known-streams") } else { streams.join(", ") };
+        let path = format!("`{}`", input.file_path);
+        let specifier = match stream_type {
+            Some(stream_type) => Message::new("specifier-type-streams").arg("stream", stream_type_name(&stream_type)).arg("path", path),
+            None => Message::new("specifier-all-streams").arg("path", path),
+        };
+        Some(Message::new("hover-label-streams").arg("specifier", specifier).arg("streams", selected).render(locale))
+    }
+    
+    /// The streams of an input, from its media information or guessed from its extension
+    fn input(&self, input_idx: usize, input: &InputSpec) -> Hover {
+        let locale = self.locale;
+        let mut markdown = Message::new("hover-input-title").arg("index", input_idx).arg("path", &input.file_path).render(locale);
+        markdown.push_str("\n\n");
+        if let Some(duration) = self.tracker.input_durations.get(input_idx).copied().flatten() {
+            markdown.push_str(&Message::new("hover-duration").arg("seconds", duration).render(locale));
+            markdown.push_str("\n\n");
+        }
+        
+        let streams: Vec<_> = self.tracker.input_streams.iter().filter(|s| s.input_index == input_idx).collect();
+        if streams.is_empty() {
+            markdown.push_str(&locale.text("hover-streams-unknown"));
+            return self.hover(&input.file_path_span, markdown);
+        }
+        let source = if self.tracker.media_for(&input.file_path).is_some() { "hover-streams-probed" } else { "hover-streams-guessed" };
+        markdown.push_str(&locale.text(source));
+        markdown.push_str("\n\n");
+        markdown.push_str(&locale.text("hover-stream-table"));
+        for stream in streams {
+            let mut details = Vec::new();
+            if let (Some(width), Some(height)) = (stream.width, stream.height) {
+                details.push(format!("{}x{}", width, height));
+            }
+            if let Some(fps) = stream.frame_rate {
+                details.push(Message::new("hover-fps").arg("fps", fps).render(locale));
+            }
+            if let Some(rate) = stream.sample_rate {
+                details.push(format!("{} Hz", rate));
+            }
+            if let Some(channels) = stream.channels {
+                details.push(Message::new("hover-channels").arg("channels", channels).render(locale));
+            }
+            let codec = stream.codec.clone().unwrap_or_else(|| locale.text("hover-unknown-codec"));
+            markdown.push_str(&format!(
+                "\n| `{}:{}` | {} | {} | {} |",
+                input_idx,
+                stream.index,
+                stream_type_name(&stream.stream_type).render(locale),
+                codec,
+                details.join(", ")
+            ));
+        }
+        self.hover(&input.file_path_span, markdown)
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_command;
+    
+    /// Markdown of the hover at a column of a one-line command
+    fn hover_at(command: &str, column: usize) -> Option<String> {
+        hover_in(command, column, Locale::English)
+    }
+    
+    fn hover_in(command: &str, column: usize, locale: Locale) -> Option<String> {
+        let command = parse_command(command, 1, 0).unwrap();
+        let hover = hover(&command, &CodecDatabase::new(), Vec::new(), 1, column, locale)?;
+        let RichBlock::MarkdownGfm { markdown } = &hover.rich.blocks[0] else { panic!("expected markdown") };
+        Some(markdown.clone())
+    }
+    
+    #[test]
+    fn test_hover_option() {
+        let command = "ffmpeg -i input.mp4 -c:v libx264 -crf 23 -preset fast output.mp4";
+        let crf = hover_at(command, 35).unwrap();
+        assert!(crf.starts_with("## `-crf`\n\nConstant rate factor"));
+        assert!(crf.contains("**Context:** option of output `output.mp4`"));
+        assert!(crf.contains("**Accepted values:** 0 to 51 (libx264)"));
+        let codec_option = hover_at(command, 21).unwrap();
+        assert!(codec_option.contains("`libx264`, `libx265`"));
+        assert!(hover_at("ffmpeg -y -i input.mp4 output.mp4", 8).unwrap().contains("global option"));
+    }
+    
+    #[test]
+    fn test_hover_codec_and_filter() {
+        let codec = hover_at("ffmpeg -i input.mp4 -c:v libx264 output.mp4", 28).unwrap();
+        assert!(codec.starts_with("## `libx264`\n\nVideo encoder for **h264**"));
+        assert!(codec.contains("`h264_nvenc`"));
+        assert!(codec.contains("### Compatible containers"));
+        assert!(hover_at("ffmpeg -i input.mp4 -c:v nosuchcodec output.mp4", 28).is_none());
+        
+        let filter = hover_at("ffmpeg -i input.mp4 -vf scale=1280:-2 output.mp4", 25).unwrap();
+        assert!(filter.contains("Resize video\n\n**Inputs:** 1 Video · **Output:** Video"));
+        assert!(filter.contains("| `flags` | bilinear\\|bicubic\\|lanczos\\|... | Scaling algorithm |"));
+    }
+    
+    #[test]
+    fn test_hover_input_and_labels() {
+        let command = "ffmpeg -i input.mp4 -i logo.png -filter_complex [0:v][1:v]overlay=10:10[out] -map [out] output.mp4";
+        let input = hover_at(command, 12).unwrap();
+        assert!(input.starts_with("## Input 0: `input.mp4`\n\nStreams guessed from the file name"));
+        assert!(input.contains("| `0:1` | Audio | unknown |  |"));
+        
+        assert_eq!(hover_at(command, 55).unwrap(), "## `[1:v]`\n\nVideo streams of `logo.png`: `1:0` (Video)");
+        assert_eq!(hover_at(command, 73).unwrap(), "## `[out]`\n\nOutput of filter `overlay` (Video stream)");
+        assert_eq!(hover_at(command, 84).unwrap(), "## `[out]`\n\nOutput of filter `overlay` (Video stream)");
+        assert!(hover_at(command, 60).unwrap().contains("**Inputs:** 2 Video"));
+    }
+    
+    #[test]
+    fn test_hover_locale() {
+        let command = "ffmpeg -i input.mp4 -c:v libx264 -crf 23 output.mp4";
+        let codec = hover_in(command, 28, Locale::German).unwrap();
+        assert!(codec.starts_with("## `libx264`\n\nVideo-Encoder für **h264**"));
+        assert!(codec.contains("### Kompatible Container"));
+        assert!(hover_in(command, 12, Locale::French).unwrap().starts_with("## Entrée 0 : `input.mp4`"));
+        let crf = hover_in(command, 35, Locale::German).unwrap();
+        assert!(crf.contains("**Kontext:** Option der Ausgabe `output.mp4`"));
+        assert!(crf.contains("**Zulässige Werte:** 0 bis 51 (libx264)"));
+    }
+}
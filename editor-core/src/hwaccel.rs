@@ -0,0 +1,482 @@
+//! Hardware acceleration: follow frames between system and GPU memory through each pipeline
+
+use crate::ast::{FfmpegCommand, Filter, FilterSpec, InputSpec, OptionNode, OutputSpec};
+use crate::codec_db::{CodecDatabase, STREAM_COPY};
+use crate::messages::{Locale, Message};
+use shared_types::{DiagnosticKind, DiagnosticMessage, DiagnosticSpan, Severity, SourceCodeSpan, SpanRole};
+use std::collections::HashMap;
+
+/// Where decoded video frames live
+#[derive(Debug, Clone, PartialEq)]
+enum FrameLocation {
+    Cpu,
+    /// GPU memory of a hardware family (None when the device is not known statically), with the
+    /// software pixel format `hwdownload` yields when a filter chose it
+    Gpu { family: Option<String>, sw_format: Option<String> },
+}
+
+impl FrameLocation {
+    fn describe(&self) -> Message {
+        match self {
+            FrameLocation::Cpu => Message::new("system-memory"),
+            FrameLocation::Gpu { family: Some(family), .. } => Message::new("family-gpu-memory").arg("family", family),
+            FrameLocation::Gpu { family: None, .. } => Message::new("gpu-memory"),
+        }
+    }
+}
+
+/// Check `-hwaccel` options and that every filter and encoder receives frames where it expects them
+pub fn check_hardware_pipelines(command: &FfmpegCommand, db: &CodecDatabase, locale: Locale) -> Vec<DiagnosticMessage> {
+    let mut diagnostics = Vec::new();
+    
+    let input_locations: Vec<FrameLocation> = command
+        .inputs
+        .iter()
+        .map(|input| input_frame_location(input, db, locale, &mut diagnostics))
+        .collect();
+    // hwupload uses the device of the hwaccel input when there is exactly one family in play
+    let mut families: Vec<&String> = input_locations
+        .iter()
+        .filter_map(|l| match l {
+            FrameLocation::Gpu { family: Some(family), .. } => Some(family),
+            _ => None,
+        })
+        .collect();
+    families.dedup();
+    let upload_family = match families.as_slice() {
+        [family] => Some(family.to_string()),
+        _ => initialized_device_family(command, db),
+    };
+    let mut walker = Walker { db, upload_family, cpu_format: None, locale, diagnostics: &mut diagnostics };
+    
+    // Filter graph outputs by label, and the unlabelled output that goes to the first output file
+    let mut labels: HashMap<String, FrameLocation> = HashMap::new();
+    let mut unlabelled = None;
+    for option in &command.global_options {
+        let OptionNode::FilterComplex { filter, .. } = option else { continue };
+        let Some(graph) = &filter.parsed else { continue };
+        for chain in &graph.chains {
+            let Some(first) = chain.filters.first() else { continue };
+            let start = first
+                .input_labels
+                .first()
+                .and_then(|label| labels.get(label).cloned().or_else(|| input_label_location(label, &input_locations)))
+                .unwrap_or(FrameLocation::Cpu);
+            let end = walker.walk(chain.filters.iter(), start);
+            match chain.filters.last() {
+                Some(last) if !last.output_labels.is_empty() => {
+                    for label in &last.output_labels {
+                        labels.insert(label.clone(), end.clone());
+                    }
+                }
+                _ => unlabelled = Some(end),
+            }
+        }
+    }
+    
+    for (index, output) in command.outputs.iter().enumerate() {
+        let unlabelled = if index == 0 { unlabelled.as_ref() } else { None };
+        let Some(start) = output_source_location(output, &labels, unlabelled, &input_locations) else { continue };
+        let mut location = start;
+        for option in &output.options {
+            if let OptionNode::VideoFilter { filter, .. } = option {
+                location = walker.walk_spec(filter, location);
+            }
+        }
+        walker.check_encoder(output, &location);
+    }
+    
+    diagnostics
+}
+
+/// Frames stay in GPU memory only when `-hwaccel_output_format` names the hardware frame format
+fn input_frame_location(
+    input: &InputSpec,
+    db: &CodecDatabase,
+    locale: Locale,
+    diagnostics: &mut Vec<DiagnosticMessage>,
+) -> FrameLocation {
+    let option = |wanted: &str| {
+        input.options.iter().find_map(|option| match option {
+            OptionNode::Generic { name, value: Some(value), value_span: Some(value_span), .. } if name == wanted => {
+                Some((value.as_str(), value_span))
+            }
+            _ => None,
+        })
+    };
+    let hwaccel = option("-hwaccel");
+    let output_format = option("-hwaccel_output_format");
+    
+    let family = match hwaccel {
+        Some(("auto" | "none", _)) | None => None,
+        Some((method, span)) => match db.hardware_family_for_hwaccel(method) {
+            Some(family) => Some(family),
+            None => {
+                if !KNOWN_HWACCELS.contains(&method) {
+                    diagnostics.push(DiagnosticMessage {
+                        code: "W702".to_string(),
+                        severity: Severity::Warning,
+                        kind: DiagnosticKind::InvalidParameter {
+                            option: "-hwaccel".to_string(),
+                            value: method.to_string(),
+                            reason: "unknown hardware acceleration method".to_string(),
+                        },
+                        message: Message::new("unknown-hwaccel").arg("method", method).render(locale),
+                        spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-unknown-method") }],
+                        rich: None,
+                    });
+                }
+                None
+            }
+        },
+    };
+    
+    let Some((format, format_span)) = output_format else {
+        return FrameLocation::Cpu;
+    };
+    if hwaccel.is_none() {
+        diagnostics.push(DiagnosticMessage {
+            code: "W701".to_string(),
+            severity: Severity::Warning,
+            kind: DiagnosticKind::MissingRequiredOption {
+                option: "-hwaccel".to_string(),
+                context: "-hwaccel_output_format".to_string(),
+            },
+            message: locale.text("hwaccel-output-format-without-hwaccel"),
+            spans: vec![DiagnosticSpan { span: format_span.clone(), role: SpanRole::Target, message: locale.text("span-ignored") }],
+            rich: None,
+        });
+        return FrameLocation::Cpu;
+    }
+    
+    match family {
+        Some(family) if family.frame_format == format => FrameLocation::Gpu { family: Some(family.name.clone()), sw_format: None },
+        _ => FrameLocation::Cpu,
+    }
+}
+
+/// `-hwaccel` methods without a modeled hardware family
+const KNOWN_HWACCELS: &[&str] = &["dxva2", "d3d12va", "vdpau", "vulkan", "drm", "opencl", "mediacodec"];
+
+/// Resolve a filter graph input label such as `0:v` or `1`
+fn input_label_location(label: &str, inputs: &[FrameLocation]) -> Option<FrameLocation> {
+    let index = label.split(':').next()?.parse::<usize>().ok()?;
+    inputs.get(index).cloned()
+}
+
+/// Hardware family of a device created with `-init_hw_device type=name` or `-vaapi_device`
+fn initialized_device_family(command: &FfmpegCommand, db: &CodecDatabase) -> Option<String> {
+    let options = command
+        .global_options
+        .iter()
+        .chain(command.inputs.iter().flat_map(|input| input.options.iter()));
+    options.into_iter().find_map(|option| match option {
+        OptionNode::Generic { name, value: Some(value), .. } => match name.as_str() {
+            "-init_hw_device" => {
+                let device_type = value.split(['=', ':']).next()?;
+                Some(db.hardware_family_for_hwaccel(device_type)?.name.clone())
+            }
+            "-vaapi_device" => Some("vaapi".to_string()),
+            "-qsv_device" => Some("qsv".to_string()),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Location of the frames the output's video encoder reads, or None when it has no decoded video
+fn output_source_location(
+    output: &OutputSpec,
+    labels: &HashMap<String, FrameLocation>,
+    unlabelled: Option<&FrameLocation>,
+    inputs: &[FrameLocation],
+) -> Option<FrameLocation> {
+    let is_copy = output.options.iter().any(|option| match option {
+        OptionNode::VideoCodec { codec, .. } | OptionNode::Codec { codec, .. } => codec == STREAM_COPY,
+        _ => false,
+    });
+    if is_copy || output.options.iter().any(|o| o.name() == "-vn") {
+        return None;
+    }
+    
+    let mapped = output.options.iter().find_map(|option| match option {
+        OptionNode::Map { mapping, .. } => {
+            let mapping = mapping.trim_start_matches('[').trim_end_matches(']');
+            if matches!(mapping.split(':').nth(1), Some("a" | "s" | "d")) {
+                return None;
+            }
+            labels.get(mapping).cloned().or_else(|| input_label_location(mapping, inputs))
+        }
+        _ => None,
+    });
+    // Without -map ffmpeg uses the unlabelled graph output or the first input's video
+    mapped.or_else(|| unlabelled.cloned()).or_else(|| inputs.first().cloned())
+}
+
+struct Walker<'a> {
+    db: &'a CodecDatabase,
+    upload_family: Option<String>,
+    /// Pixel format set by a `format=` filter on system-memory frames, which `hwupload` keeps
+    cpu_format: Option<String>,
+    locale: Locale,
+    diagnostics: &'a mut Vec<DiagnosticMessage>,
+}
+
+impl Walker<'_> {
+    fn walk_spec(&mut self, spec: &FilterSpec, start: FrameLocation) -> FrameLocation {
+        self.walk(spec.filters(), start)
+    }
+    
+    fn walk<'f>(&mut self, filters: impl Iterator<Item = &'f Filter>, start: FrameLocation) -> FrameLocation {
+        let mut location = start;
+        self.cpu_format = None;
+        for filter in filters {
+            location = self.apply(filter, location);
+        }
+        location
+    }
+    
+    fn apply(&mut self, filter: &Filter, location: FrameLocation) -> FrameLocation {
+        match filter.name.as_str() {
+            "hwdownload" => {
+                if location == FrameLocation::Cpu {
+                    self.needs_gpu_frames(&filter.name, &filter.name_span, None);
+                }
+                FrameLocation::Cpu
+            }
+            "hwupload" | "hwupload_cuda" => {
+                if let FrameLocation::Gpu { .. } = location {
+                    return location;
+                }
+                let family = if filter.name == "hwupload_cuda" { Some("cuda".to_string()) } else { self.upload_family.clone() };
+                FrameLocation::Gpu { family, sw_format: self.cpu_format.clone() }
+            }
+            "hwmap" => location,
+            name => match self.db.hardware_family_of(name) {
+                Some(family) => {
+                    let family = family.name.clone();
+                    let mut sw_format = None;
+                    match &location {
+                        FrameLocation::Cpu => self.needs_gpu_frames(name, &filter.name_span, Some("hwupload")),
+                        FrameLocation::Gpu { family: Some(found), .. } if *found != family => {
+                            self.family_mismatch(name, &family, found, &filter.name_span)
+                        }
+                        FrameLocation::Gpu { sw_format: previous, .. } => sw_format = previous.clone(),
+                    }
+                    // scale_cuda, scale_vaapi and vpp_qsv convert with `format=`
+                    if let Some(param) = filter.params.iter().find(|p| p.key.as_deref() == Some("format")) {
+                        sw_format = Some(param.value.clone());
+                    }
+                    FrameLocation::Gpu { family: Some(family), sw_format }
+                }
+                None => {
+                    if let FrameLocation::Gpu { sw_format, .. } = &location {
+                        // hwdownload must name the frames' own format, so leave it to negotiation when unknown
+                        let download = match sw_format {
+                            Some(format) => format!("hwdownload,format={},{}", format, name),
+                            None => format!("hwdownload,{}", name),
+                        };
+                        self.needs_cpu_frames(
+                            Message::new("operation-filter").arg("filter", name),
+                            &location,
+                            &filter.name_span,
+                            Some(download),
+                        );
+                    } else if name == "format" {
+                        self.cpu_format = filter.param("pix_fmts", 0).map(|p| p.value.clone()).filter(|f| !f.contains('|'));
+                    }
+                    FrameLocation::Cpu
+                }
+            },
+        }
+    }
+    
+    fn check_encoder(&mut self, output: &OutputSpec, location: &FrameLocation) {
+        let encoder = output.options.iter().find_map(|option| match option {
+            OptionNode::VideoCodec { codec, codec_span, .. } => Some((codec.as_str(), codec_span)),
+            OptionNode::Codec { codec, codec_span, .. }
+                if self.db.get_codec(codec).is_some_and(|c| c.stream_type == shared_types::StreamType::Video) =>
+            {
+                Some((codec.as_str(), codec_span))
+            }
+            _ => None,
+        });
+        let family = encoder.and_then(|(codec, _)| self.db.hardware_family_of(codec)).cloned();
+        
+        match (location, family) {
+            (FrameLocation::Gpu { .. }, None) => {
+                let (operation, span) = match encoder {
+                    Some((codec, span)) => (Message::new("operation-encoder").arg("encoder", codec), span),
+                    None => (Message::new("operation-default-encoder"), &output.file_path_span),
+                };
+                self.needs_cpu_frames(operation, location, span, None);
+            }
+            (FrameLocation::Gpu { family: Some(found), .. }, Some(family)) => {
+                if let Some((codec, span)) = encoder.filter(|(codec, _)| !self.db.encoder_reads_family(codec, found)) {
+                    self.family_mismatch(codec, &family.name, found, span);
+                }
+            }
+            (FrameLocation::Cpu, Some(family)) if !family.encoders_accept_cpu_frames => {
+                if let Some((codec, span)) = encoder {
+                    self.needs_gpu_frames(codec, span, None);
+                }
+            }
+            _ => {}
+        }
+    }
+    
+    fn needs_cpu_frames(&mut self, operation: Message, location: &FrameLocation, span: &SourceCodeSpan, fix: Option<String>) {
+        let locale = self.locale;
+        let mut spans = vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-needs-system-memory") }];
+        if let Some(replacement) = fix {
+            spans.push(DiagnosticSpan { span: span.clone(), role: SpanRole::Suggestion { replacement }, message: locale.text("fix-download-frames") });
+        }
+        self.diagnostics.push(DiagnosticMessage {
+            code: "E701".to_string(),
+            severity: Severity::Error,
+            kind: DiagnosticKind::HardwareFrameMismatch {
+                operation: operation.render(Locale::English),
+                expected: Message::new("system-memory").render(Locale::English),
+                found: location.describe().render(Locale::English),
+            },
+            message: Message::new("gpu-frames-into-cpu")
+                .arg("operation", capitalize(&operation.render(locale)))
+                .arg("location", location.describe())
+                .render(locale),
+            spans,
+            rich: None,
+        });
+    }
+    
+    fn needs_gpu_frames(&mut self, name: &str, span: &SourceCodeSpan, upload: Option<&str>) {
+        let locale = self.locale;
+        let mut spans = vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-needs-gpu-memory") }];
+        if let Some(upload) = upload {
+            spans.push(DiagnosticSpan {
+                span: span.clone(),
+                role: SpanRole::Suggestion { replacement: format!("{},{}", upload, name) },
+                message: locale.text("fix-upload-frames"),
+            });
+        }
+        self.diagnostics.push(DiagnosticMessage {
+            code: "E702".to_string(),
+            severity: Severity::Error,
+            kind: DiagnosticKind::HardwareFrameMismatch {
+                operation: name.to_string(),
+                expected: Message::new("gpu-memory").render(Locale::English),
+                found: Message::new("system-memory").render(Locale::English),
+            },
+            message: Message::new("cpu-frames-into-gpu").arg("component", name).render(locale),
+            spans,
+            rich: None,
+        });
+    }
+    
+    fn family_mismatch(&mut self, name: &str, family: &str, found: &str, span: &SourceCodeSpan) {
+        self.diagnostics.push(DiagnosticMessage {
+            code: "E703".to_string(),
+            severity: Severity::Error,
+            kind: DiagnosticKind::HardwareFrameMismatch {
+                operation: name.to_string(),
+                expected: Message::new("family-gpu-memory").arg("family", family).render(Locale::English),
+                found: Message::new("family-gpu-memory").arg("family", found).render(Locale::English),
+            },
+            message: Message::new("gpu-family-mismatch").arg("component", name).arg("family", family).arg("found", found).render(self.locale),
+            spans: vec![DiagnosticSpan {
+                span: span.clone(),
+                role: SpanRole::Target,
+                message: Message::new("span-family-component").arg("family", family).render(self.locale),
+            }],
+            rich: None,
+        });
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_command;
+    
+    fn codes(input: &str) -> Vec<String> {
+        let cmd = parse_command(input, 0, 0).unwrap();
+        check_hardware_pipelines(&cmd, &CodecDatabase::new(), Locale::English).into_iter().map(|d| d.code).collect()
+    }
+    
+    #[test]
+    fn test_valid_gpu_pipelines() {
+        assert!(codes("ffmpeg -hwaccel cuda -hwaccel_output_format cuda -i in.mp4 -vf scale_cuda=1280:720 -c:v h264_nvenc out.mp4").is_empty());
+        assert!(codes("ffmpeg -hwaccel cuda -hwaccel_output_format cuda -i in.mp4 -vf hwdownload,format=nv12,drawtext=text=hi -c:v libx264 out.mp4").is_empty());
+        assert!(codes("ffmpeg -vaapi_device /dev/dri/renderD128 -i in.mp4 -vf format=nv12,hwupload -c:v h264_vaapi out.mp4").is_empty());
+        // Without -hwaccel_output_format frames are downloaded automatically
+        assert!(codes("ffmpeg -hwaccel cuda -i in.mp4 -vf drawtext=text=hi -c:v libx264 out.mp4").is_empty());
+        assert!(codes("ffmpeg -i in.mp4 -c:v h264_nvenc out.mp4").is_empty());
+    }
+    
+    #[test]
+    fn test_gpu_frames_into_cpu_components() {
+        assert_eq!(codes("ffmpeg -hwaccel cuda -hwaccel_output_format cuda -i in.mp4 -vf drawtext=text=hi -c:v h264_nvenc out.mp4"), vec!["E701"]);
+        assert_eq!(codes("ffmpeg -hwaccel cuda -hwaccel_output_format cuda -i in.mp4 -c:v libx264 out.mp4"), vec!["E701"]);
+        assert_eq!(
+            codes("ffmpeg -hwaccel cuda -hwaccel_output_format cuda -i in.mp4 -filter_complex [0:v]scale=640:360[v] -map [v] -c:v h264_nvenc out.mp4"),
+            vec!["E701"]
+        );
+    }
+    
+    #[test]
+    fn test_download_fix_keeps_known_formats() {
+        let fix = |input: &str| {
+            let cmd = parse_command(input, 0, 0).unwrap();
+            let diags = check_hardware_pipelines(&cmd, &CodecDatabase::new(), Locale::English);
+            diags.into_iter().find(|d| d.code == "E701")?.spans.into_iter().find_map(|s| match s.role {
+                SpanRole::Suggestion { replacement } => Some(replacement),
+                _ => None,
+            })
+        };
+        // The decoder's format depends on the source, which is not known
+        assert_eq!(
+            fix("ffmpeg -hwaccel cuda -hwaccel_output_format cuda -i in.mp4 -vf drawtext=text=hi -c:v libx264 out.mp4"),
+            Some("hwdownload,drawtext".to_string())
+        );
+        assert_eq!(
+            fix("ffmpeg -hwaccel cuda -hwaccel_output_format cuda -i in.mp4 -vf scale_cuda=format=p010,drawtext=text=hi -c:v libx265 out.mp4"),
+            Some("hwdownload,format=p010,drawtext".to_string())
+        );
+        assert_eq!(
+            fix("ffmpeg -vaapi_device /dev/dri/renderD128 -i in.mp4 -vf format=p010,hwupload,drawtext=text=hi -c:v libx265 out.mp4"),
+            Some("hwdownload,format=p010,drawtext".to_string())
+        );
+    }
+    
+    #[test]
+    fn test_cpu_frames_into_gpu_components() {
+        assert_eq!(codes("ffmpeg -i in.mp4 -vf scale_cuda=1280:720 -c:v h264_nvenc out.mp4"), vec!["E702"]);
+        assert_eq!(codes("ffmpeg -i in.mp4 -c:v h264_vaapi out.mp4"), vec!["E702"]);
+        assert_eq!(codes("ffmpeg -hwaccel cuda -hwaccel_output_format cuda -i in.mp4 -c:v hevc_vaapi out.mp4"), vec!["E703"]);
+    }
+    
+    #[test]
+    fn test_d3d11_frames_into_windows_encoders() {
+        for encoder in ["h264_amf", "h264_nvenc", "hevc_qsv"] {
+            let input = format!("ffmpeg -hwaccel d3d11va -hwaccel_output_format d3d11 -i in.mp4 -c:v {} out.mp4", encoder);
+            assert!(codes(&input).is_empty(), "{}: {:?}", encoder, codes(&input));
+        }
+        assert_eq!(codes("ffmpeg -hwaccel d3d11va -hwaccel_output_format d3d11 -i in.mp4 -c:v h264_vaapi out.mp4"), vec!["E703"]);
+        assert_eq!(codes("ffmpeg -hwaccel d3d11va -hwaccel_output_format d3d11 -i in.mp4 -c:v libx264 out.mp4"), vec!["E701"]);
+        // Frames of the encoder's own family do not come back from D3D11
+        assert_eq!(codes("ffmpeg -hwaccel cuda -hwaccel_output_format cuda -i in.mp4 -c:v h264_amf out.mp4"), vec!["E703"]);
+    }
+    
+    #[test]
+    fn test_hwaccel_options() {
+        assert_eq!(codes("ffmpeg -hwaccel_output_format cuda -i in.mp4 out.mp4"), vec!["W701"]);
+        assert_eq!(codes("ffmpeg -hwaccel nvidia -i in.mp4 out.mp4"), vec!["W702"]);
+    }
+}
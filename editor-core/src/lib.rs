@@ -5,6 +5,21 @@ pub mod codec_db;
 pub mod stream_tracker;
 pub mod handler;
 pub mod rich_content;
+pub mod paths;
+pub mod option_rules;
+pub mod hwaccel;
+pub mod ffprobe;
+pub mod codec_import;
+pub mod versions;
+pub mod rules;
+pub mod lint;
+pub mod suppressions;
+pub mod code_actions;
+pub mod suggest;
+pub mod catalog;
+pub mod messages;
+pub mod hover;
+pub mod completion;
 
 pub use handler::EditorHandler;
 
@@ -0,0 +1,154 @@
+use crate::rules::RuleCategory;
+use serde::Deserialize;
+use shared_types::{DiagnosticMessage, LintConfig, LintLevel, LintSetting, Severity};
+use std::collections::BTreeMap;
+
+/// Lint configuration file, e.g. `ffmpeg-lint.toml`:
+///
+/// ```toml
+/// [codes]
+/// W101 = "allow"
+/// W201 = "deny"
+///
+/// [categories]
+/// quality = "warn"
+/// ```
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LintFile {
+    #[serde(default)]
+    codes: BTreeMap<String, String>,
+    #[serde(default)]
+    categories: BTreeMap<String, String>,
+}
+
+/// Read a lint configuration from TOML, for use outside the editor
+pub fn parse_lint_config(toml_text: &str) -> Result<LintConfig, String> {
+    let file: LintFile = toml::from_str(toml_text).map_err(|e| format!("Invalid lint configuration: {}", e))?;
+    
+    let mut codes = Vec::new();
+    for (code, level) in &file.codes {
+        codes.push(LintSetting { name: code.clone(), level: parse_level(code, level)? });
+    }
+    let mut categories = Vec::new();
+    for (category, level) in &file.categories {
+        if RuleCategory::from_name(category).is_none() {
+            let known: Vec<_> = RuleCategory::ALL.iter().map(|c| c.name()).collect();
+            return Err(format!("Unknown rule category '{}'; expected one of {}", category, known.join(", ")));
+        }
+        categories.push(LintSetting { name: category.clone(), level: parse_level(category, level)? });
+    }
+    Ok(LintConfig { codes, categories })
+}
+
+fn parse_level(name: &str, level: &str) -> Result<LintLevel, String> {
+    match level {
+        "allow" => Ok(LintLevel::Allow),
+        "warn" => Ok(LintLevel::Warn),
+        "deny" => Ok(LintLevel::Deny),
+        _ => Err(format!("Invalid level '{}' for '{}'; expected allow, warn or deny", level, name)),
+    }
+}
+
+/// Configured level of a diagnostic code; the code's own setting wins over its category's.
+/// A built-in code belongs to the category of its range, any other code to the category of
+/// the rule that reported it.
+pub fn level_for<'a>(config: &'a LintConfig, code: &str, rule_category: RuleCategory) -> Option<&'a LintLevel> {
+    let find = |settings: &'a [LintSetting], name: &str| settings.iter().rev().find(|s| s.name == name).map(|s| &s.level);
+    let category = RuleCategory::of_code(code).unwrap_or(rule_category);
+    find(&config.codes, code).or_else(|| find(&config.categories, category.name()))
+}
+
+/// Drop allowed diagnostics and set the severity of the others to their configured level
+pub fn apply_lint(config: &LintConfig, rule_category: RuleCategory, diagnostics: Vec<DiagnosticMessage>) -> Vec<DiagnosticMessage> {
+    diagnostics
+        .into_iter()
+        .filter_map(|mut diagnostic| {
+            match level_for(config, &diagnostic.code, rule_category) {
+                Some(LintLevel::Allow) => return None,
+                Some(LintLevel::Warn) => diagnostic.severity = Severity::Warning,
+                Some(LintLevel::Deny) => diagnostic.severity = Severity::Error,
+                None => {}
+            }
+            Some(diagnostic)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::{analyze_command_with_options, AnalysisOptions};
+    use crate::parser::parse_command;
+    
+    const CONFIG: &str = r#"
+        [codes]
+        W101 = "allow"
+        W201 = "deny"
+        
+        [categories]
+        quality = "allow"
+    "#;
+    
+    fn analyze(input: &str, lint: Option<LintConfig>) -> Vec<DiagnosticMessage> {
+        let cmd = parse_command(input, 0, 0).unwrap();
+        analyze_command_with_options(cmd, AnalysisOptions { lint, ..AnalysisOptions::default() }).messages
+    }
+    
+    #[test]
+    fn test_parse_lint_config() {
+        let config = parse_lint_config(CONFIG).unwrap();
+        assert!(matches!(level_for(&config, "W201", RuleCategory::Codecs), Some(LintLevel::Deny)));
+        assert!(matches!(level_for(&config, "W103", RuleCategory::Quality), Some(LintLevel::Allow)));
+        assert!(level_for(&config, "E201", RuleCategory::Codecs).is_none());
+        // The code's range decides, not the rule that reported it
+        assert!(matches!(level_for(&config, "W102", RuleCategory::Options), Some(LintLevel::Allow)));
+        assert!(matches!(level_for(&config, "X001", RuleCategory::Quality), Some(LintLevel::Allow)));
+        
+        assert!(parse_lint_config("[codes]\nW101 = \"off\"").is_err());
+        assert!(parse_lint_config("[categories]\nspeed = \"allow\"").is_err());
+        assert!(parse_lint_config("[rules]").is_err());
+    }
+    
+    #[test]
+    fn test_severity_overrides() {
+        let config = parse_lint_config(CONFIG).unwrap();
+        let input = "ffmpeg -i input.mp4 -c:v libx264 -b:v 80M -c:a mystery out.mp4";
+        
+        let default = analyze(input, None);
+        assert!(default.iter().any(|d| d.code == "W101"));
+        assert!(default.iter().any(|d| d.code == "W201" && matches!(d.severity, Severity::Warning)));
+        
+        let linted = analyze(input, Some(config));
+        assert!(!linted.iter().any(|d| d.code == "W101"));
+        assert!(linted.iter().any(|d| d.code == "W201" && matches!(d.severity, Severity::Error)));
+    }
+    
+    #[test]
+    fn test_category_of_code() {
+        assert_eq!(RuleCategory::of_code("W101"), Some(RuleCategory::Quality));
+        assert_eq!(RuleCategory::of_code("E105"), Some(RuleCategory::Streams));
+        assert_eq!(RuleCategory::of_code("W200"), Some(RuleCategory::Streams));
+        assert_eq!(RuleCategory::of_code("E201"), Some(RuleCategory::Codecs));
+        assert_eq!(RuleCategory::of_code("W401"), Some(RuleCategory::Options));
+        assert_eq!(RuleCategory::of_code("E414"), Some(RuleCategory::Compatibility));
+        assert_eq!(RuleCategory::of_code("W702"), Some(RuleCategory::Hardware));
+        assert_eq!(RuleCategory::of_code("I001"), Some(RuleCategory::Info));
+        assert_eq!(RuleCategory::of_code("E000"), None);
+        assert_eq!(RuleCategory::of_code("X001"), None);
+    }
+    
+    #[test]
+    fn test_quality_category_from_toml() {
+        let config = parse_lint_config("[categories]\nquality = \"allow\"\n").unwrap();
+        // W101 comes from the rate-control rule, W102 from encoder-options
+        let input = "ffmpeg -i input.mp4 -c:v libx264 small.mp4 -c:v libx264 -b:v 80M large.mp4";
+        
+        let default: Vec<_> = analyze(input, None).into_iter().map(|d| d.code).collect();
+        assert!(default.contains(&"W101".to_string()) && default.contains(&"W102".to_string()), "{:?}", default);
+        
+        let linted: Vec<_> = analyze(input, Some(config)).into_iter().map(|d| d.code).collect();
+        assert!(!linted.iter().any(|code| code.starts_with("W1")), "{:?}", linted);
+        assert!(linted.contains(&"I001".to_string()));
+    }
+}
@@ -0,0 +1,1108 @@
+//! Diagnostic text built from message IDs and typed arguments, rendered from the catalog of
+//! the request's locale. Messages missing from a locale's catalog fall back to English
+
+use shared_types::StreamType;
+
+/// Language of the diagnostic text
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    English,
+    German,
+    French,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 3] = [Locale::English, Locale::German, Locale::French];
+    
+    /// Locale of a language tag such as "de", "fr-CA" or "de_AT"; None for unsupported languages
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language = tag.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Some(Locale::English),
+            "de" => Some(Locale::German),
+            "fr" => Some(Locale::French),
+            _ => None,
+        }
+    }
+    
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::English => EN,
+            Locale::German => DE,
+            Locale::French => FR,
+        }
+    }
+    
+    fn decimal_separator(self) -> char {
+        match self {
+            Locale::English => '.',
+            Locale::German | Locale::French => ',',
+        }
+    }
+    
+    /// A message without arguments
+    pub fn text(self, id: &'static str) -> String {
+        Message::new(id).render(self)
+    }
+}
+
+/// Value substituted for a `{name}` placeholder
+#[derive(Debug, Clone, PartialEq)]
+pub enum Arg {
+    /// Shown as is: names, paths and values from the command
+    Text(String),
+    /// Shown with the locale's decimal separator
+    Number(f64),
+    /// A number rounded to a count of decimals
+    Fixed(f64, usize),
+    /// Alternatives quoted and joined as "'a', 'b' or 'c'"
+    Choices(Vec<String>),
+    /// Another message, rendered in the same locale
+    Message(Box<Message>),
+}
+
+impl From<&str> for Arg {
+    fn from(value: &str) -> Self {
+        Arg::Text(value.to_string())
+    }
+}
+
+impl From<String> for Arg {
+    fn from(value: String) -> Self {
+        Arg::Text(value)
+    }
+}
+
+impl From<&String> for Arg {
+    fn from(value: &String) -> Self {
+        Arg::Text(value.clone())
+    }
+}
+
+impl From<f64> for Arg {
+    fn from(value: f64) -> Self {
+        Arg::Number(value)
+    }
+}
+
+impl From<u8> for Arg {
+    fn from(value: u8) -> Self {
+        Arg::Number(value.into())
+    }
+}
+
+impl From<u32> for Arg {
+    fn from(value: u32) -> Self {
+        Arg::Number(value.into())
+    }
+}
+
+impl From<usize> for Arg {
+    fn from(value: usize) -> Self {
+        Arg::Number(value as f64)
+    }
+}
+
+impl From<Message> for Arg {
+    fn from(value: Message) -> Self {
+        Arg::Message(Box::new(value))
+    }
+}
+
+/// A message ID with the arguments of its placeholders
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    pub id: &'static str,
+    pub args: Vec<(&'static str, Arg)>,
+}
+
+impl Message {
+    pub fn new(id: &'static str) -> Self {
+        Message { id, args: Vec::new() }
+    }
+    
+    pub fn arg(mut self, name: &'static str, value: impl Into<Arg>) -> Self {
+        self.args.push((name, value.into()));
+        self
+    }
+    
+    /// The locale's template with the arguments substituted; English when the locale has no
+    /// template, and the ID itself when no catalog has one
+    pub fn render(&self, locale: Locale) -> String {
+        let template = template(locale, self.id).or_else(|| template(Locale::English, self.id)).unwrap_or(self.id);
+        let mut result = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            result.push_str(&rest[..start]);
+            let placeholder = &rest[start + 1..];
+            let value = placeholder
+                .find('}')
+                .and_then(|end| Some((end, &self.args.iter().find(|(name, _)| *name == &placeholder[..end])?.1)));
+            match value {
+                Some((end, value)) => {
+                    result.push_str(&render_arg(value, locale));
+                    rest = &placeholder[end + 1..];
+                }
+                None => {
+                    result.push('{');
+                    rest = placeholder;
+                }
+            }
+        }
+        result.push_str(rest);
+        result
+    }
+}
+
+fn template(locale: Locale, id: &str) -> Option<&'static str> {
+    locale.catalog().iter().find(|(message_id, _)| *message_id == id).map(|(_, template)| *template)
+}
+
+fn render_arg(arg: &Arg, locale: Locale) -> String {
+    let localize_number = |number: String| number.replace('.', &locale.decimal_separator().to_string());
+    match arg {
+        Arg::Text(text) => text.clone(),
+        Arg::Number(number) => localize_number(number.to_string()),
+        Arg::Fixed(number, decimals) => localize_number(format!("{:.*}", decimals, number)),
+        Arg::Choices(choices) => {
+            let quoted: Vec<String> = choices.iter().map(|c| format!("'{}'", c)).collect();
+            match quoted.as_slice() {
+                [] => String::new(),
+                [only] => only.clone(),
+                [first @ .., last] => Message::new("choices").arg("first", first.join(", ")).arg("last", last.as_str()).render(locale),
+            }
+        }
+        Arg::Message(message) => message.render(locale),
+    }
+}
+
+/// Name of a stream type, as an argument of other messages
+pub fn stream_type_name(stream_type: &StreamType) -> Message {
+    Message::new(match stream_type {
+        StreamType::Video => "stream-video",
+        StreamType::Audio => "stream-audio",
+        StreamType::Subtitle => "stream-subtitle",
+        StreamType::Data => "stream-data",
+        StreamType::Unknown => "stream-unknown",
+    })
+}
+
+/// Placeholder names of a template
+#[cfg(test)]
+fn placeholders(template: &str) -> Vec<&str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+        .filter(|name| !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c == '_'))
+        .collect()
+}
+
+static EN: &[(&str, &str)] = &[
+    ("choices", "{first} or {last}"),
+    ("unknown-input-streams", "Unknown stream types for input: {path}"),
+    ("span-unknown-streams", "unknown streams"),
+    ("pipeline-overview", "FFmpeg pipeline flow"),
+    ("span-entire-command", "entire command"),
+    ("rich-pipeline-overview", "## Pipeline Overview\n\nThis diagram shows how data flows through your FFmpeg command."),
+    ("video-codec-without-video", "Video codec specified but no video stream available in inputs"),
+    ("span-codec-requires-video", "codec requires video"),
+    ("audio-codec-without-audio", "Audio codec specified but no audio stream available in inputs"),
+    ("span-codec-requires-audio", "codec requires audio"),
+    ("subtitle-codec-without-subtitles", "Subtitle codec specified but no subtitle stream available in inputs"),
+    ("span-codec-requires-subtitles", "codec requires subtitles"),
+    ("rich-filter-expects-video", "Filter '{filter}' expects video input."),
+    ("fix-encode-with", "encode with {encoder}"),
+    ("fix-write-container", "write {format} instead"),
+    ("span-stream-copy", "stream copy"),
+    ("span-codec-audio", "{codec} audio"),
+    ("audio-copy-unsupported", "Cannot copy {codec} audio into '{format}'; encode it with '-c:a {encoder}'"),
+    ("audio-copy-unsupported-drop", "Cannot copy {codec} audio into '{format}'; drop it with '-an'"),
+    ("span-codec-subtitles", "{codec} subtitles"),
+    ("fix-convert-to", "convert to {codec}"),
+    ("subtitle-copy-unsupported", "Cannot copy {codec} subtitles into '{format}'; convert them with '-c:s {encoder}'"),
+    ("subtitle-copy-unsupported-drop", "Cannot copy {codec} subtitles into '{format}', which has no subtitle support; drop them with '-sn'"),
+    ("subtitle-kind-bitmap", "bitmap"),
+    ("subtitle-kind-text", "text"),
+    ("subtitle-conversion", "Cannot convert {codec} subtitles ({kind}) to {target} ({target_kind})"),
+    ("span-codec-encoder", "{codec} encoder"),
+    ("span-re-encode", "re-encode"),
+    ("span-source", "{source} source"),
+    ("same-codec-re-encode", "Re-encoding {source} to {codec} without filters only loses quality; copy the stream instead"),
+    ("fix-generous-bitrate", "use a generous bitrate"),
+    ("lossy-audio-transcode", "Transcoding lossy {source} audio to {codec} compounds compression artifacts"),
+    ("fix-raise-bitrate", "raise the bitrate"),
+    ("fix-lower-crf", "lower the CRF"),
+    ("lossless-low-quality", "Lossless {source} source is being compressed to {codec} at low quality"),
+    ("option-ignored-with-copy", "Option '{option}' has no effect with '-c:v copy'"),
+    ("span-ignored-option", "ignored option"),
+    ("option-unsupported-by-encoder", "Encoder '{encoder}' does not support option '{option}'; it will be ignored"),
+    ("span-unsupported-option", "unsupported option"),
+    ("span-encoder", "encoder {encoder}"),
+    ("option-out-of-range", "Value {value} for '{option}' is out of range for encoder '{encoder}' (expected {min} to {max})"),
+    ("span-expected-range", "expected {min} to {max}"),
+    ("option-not-a-number", "Invalid value '{value}' for '{option}' (expected a number)"),
+    ("span-expected-number", "expected a number"),
+    ("option-invalid-value", "Invalid value '{value}' for '{option}' with encoder '{encoder}' (accepted: {accepted})"),
+    ("span-invalid-option", "invalid {option}"),
+    ("profile-pixel-format", "Profile '{profile}' of '{encoder}' does not support pixel format '{format}' ({depth}-bit {chroma})"),
+    ("span-profile", "profile"),
+    ("span-pixel-format", "pixel format"),
+    ("no-quality-setting", "No bitrate or quality setting for '{encoder}'; encoder defaults will be used"),
+    ("span-add-quality-setting", "add -b:v or -crf"),
+    ("pixel-format-not-web", "Pixel format '{format}' ({depth}-bit {chroma}) is not decodable by common browsers; use yuv420p for web delivery"),
+    ("span-not-web-compatible", "not web compatible"),
+    ("span-web-output", "web output"),
+    ("unknown-pixel-format", "Unknown pixel format: '{format}'"),
+    ("span-unknown-pixel-format", "unknown pixel format"),
+    ("unsupported-pixel-format", "Encoder '{encoder}' does not support pixel format '{format}'; ffmpeg will convert to another format"),
+    ("span-unsupported-pixel-format", "unsupported pixel format"),
+    ("rich-supported-pixel-formats", "## Supported Pixel Formats for `{encoder}`\n\n{formats}"),
+    ("span-unsupported-sample-rate", "unsupported sample rate"),
+    ("fix-use-sample-rate", "use {rate} Hz"),
+    ("unsupported-sample-rate", "Encoder '{encoder}' does not support a sample rate of {rate} Hz"),
+    ("rich-supported-sample-rates", "## Supported Sample Rates for `{encoder}`\n\n{rates}"),
+    ("rich-any-sample-rate", "Any rate up to {max} Hz"),
+    ("too-many-channels", "Encoder '{encoder}' supports at most {max} channels, but '{value}' requests {count}"),
+    ("span-channels", "{count} channels"),
+    ("duration-not-positive", "Duration '-t {time}' must be greater than zero"),
+    ("span-non-positive-duration", "non-positive duration"),
+    ("sseof-on-output", "'-sseof' only applies to inputs; place it before '-i'"),
+    ("span-not-followed-by-input", "not followed by an input"),
+    ("sseof-not-negative", "'-sseof {time}' must be negative (seconds before end of file)"),
+    ("span-must-be-negative", "must be negative"),
+    ("start-after-end", "Start time '-ss {start}' is not before end time '-to {end}'"),
+    ("span-start", "start"),
+    ("span-end", "end"),
+    ("seek-past-end", "Seek position {time} is past the end of the input ({duration}s); the output will be empty"),
+    ("span-past-end-of-input", "past end of input"),
+    ("span-seconds-long", "{duration}s long"),
+    ("input-seek-with-copy", "Input seeking with stream copy starts at the nearest keyframe before the seek point, so the cut may begin earlier than requested"),
+    ("output-seek-with-copy", "Output seeking with stream copy drops packets before the seek point, so video may be broken until the next keyframe"),
+    ("span-seek", "seek"),
+    ("span-copy-snaps-to-keyframes", "stream copy snaps to keyframes"),
+    ("rich-seek-with-copy", "## Seeking with `-c copy`\n\nStream copy cannot cut between keyframes. For a frame-accurate cut, re-encode (e.g. `-c:v libx264`) or accept keyframe-aligned boundaries.\n\n- `-ss` **before** `-i` (input seeking) is fast and snaps to the previous keyframe\n- `-ss` **after** `-i` (output seeking) decodes and discards until the seek point"),
+    ("output-overwrites-input", "Output '{output}' is the same file as input '{input}'; ffmpeg cannot edit files in place"),
+    ("span-output", "output"),
+    ("span-same-file-as-input", "same file as input"),
+    ("duplicate-output", "Output '{output}' is written more than once"),
+    ("span-duplicate-output", "duplicate output"),
+    ("span-first-written-here", "first written here"),
+    ("glob-without-pattern-type", "Wildcards in '{input}' are only expanded with '-pattern_type glob'"),
+    ("span-wildcard-pattern", "wildcard pattern"),
+    ("image-sequence-input-rate", "Use '-framerate' to set the rate of an image sequence input; '-r' on an input overrides timestamps instead"),
+    ("span-input-frame-rate", "input frame rate"),
+    ("fix-use-framerate", "use -framerate"),
+    ("image-sequence-default-rate", "Image sequence '{input}' has no '-framerate'; it will be read at 25 fps"),
+    ("span-add-framerate", "add -framerate before -i"),
+    ("image-output-without-pattern", "Image output '{output}' has no frame number pattern; use a name like 'frame%04d.png', or '-frames:v 1' for a single image"),
+    ("span-missing-pattern", "missing %d pattern"),
+    ("span-unknown-format", "unknown format"),
+    ("unknown-output-format", "Unknown output format '{format}'{hint}"),
+    ("span-invalid-resolution-format", "invalid resolution format"),
+    ("fix-16-9-frame-size", "16:9 frame size"),
+    ("invalid-resolution-format", "Invalid resolution format '{value}' (expected format: WIDTHxHEIGHT)"),
+    ("invalid-resolution-numbers", "Invalid resolution '{value}' (width and height must be numbers)"),
+    ("span-resolution-numbers", "width/height must be numbers"),
+    ("invalid-bitrate", "Invalid bitrate format '{value}'"),
+    ("span-invalid-bitrate", "invalid bitrate"),
+    ("high-bitrate", "Extremely high bitrate specified: {value}"),
+    ("span-high-bitrate", "high bitrate"),
+    ("span-bits-per-second", "{bps} bit/s"),
+    ("fix-add-k-suffix", "add the k suffix"),
+    ("low-bitrate", "Extremely low bitrate '{value}' ({bps} bit/s); bitrates without a suffix are in bits per second"),
+    ("invalid-option-bitrate", "Invalid bitrate format '{value}' for '{option}'"),
+    ("maxrate-without-bufsize", "'-maxrate' has no effect without '-bufsize'; encoders ignore the VBV limit"),
+    ("span-needs-bufsize", "needs -bufsize"),
+    ("fix-add-bufsize", "add -bufsize"),
+    ("minrate-above-maxrate", "'-minrate {minrate}' is above '-maxrate {maxrate}'"),
+    ("span-above-maxrate", "above maxrate"),
+    ("span-maxrate", "maxrate"),
+    ("bitrate-above-maxrate", "Target bitrate '-b:v {bitrate}' is above '-maxrate {maxrate}' and cannot be reached"),
+    ("frame-size-at-rate", "{width}x{height} at {fps} fps"),
+    ("frame-size-at-assumed-rate", "{width}x{height} at an assumed 30 fps"),
+    ("bitrate-too-low-for-frame", "Bitrate {bitrate} is too low for {encoder} {frame}; expect heavy compression artifacts"),
+    ("span-bits-per-pixel", "{bpp} bits per pixel"),
+    ("fix-minimum-for-resolution", "minimum for this resolution"),
+    ("bitrate-too-high-for-frame", "Bitrate {bitrate} is more than {encoder} needs for {frame}; the extra bits add size without visible quality"),
+    ("fix-enough-for-resolution", "enough for this resolution"),
+    ("upscaling", "Output {to} is larger than the {from} source; upscaling adds size but no detail"),
+    ("span-upscales", "upscales"),
+    ("frame-rate-out-of-range", "Invalid frame rate '{value}' (must be between 0 and 1000)"),
+    ("span-invalid-frame-rate", "invalid frame rate"),
+    ("invalid-frame-rate", "Invalid frame rate format '{value}'"),
+    ("span-invalid-frame-rate-format", "invalid frame rate format"),
+    ("unknown-filter-label", "Referenced filter output '{label}' does not exist"),
+    ("span-unknown-label", "unknown label"),
+    ("unknown-input-index", "Input index {index} does not exist"),
+    ("span-unknown-input-index", "non-existent input index"),
+    ("rich-codec-format-incompatibility", "## Codec/Container Incompatibility\n\nThe **{codec}** codec cannot be used with **{format}** containers.\n\n### Compatible Containers\n{formats}\n\n### Solution\nChange the output file extension to use a compatible container format."),
+    ("rich-missing-stream", "## Missing {stream} Stream\n\nThe operation **{operation}** requires a {stream} stream, but none is available in the inputs.\n\n### Available Streams\n{streams}\n\n### Solution\n- Use an input file that contains a {stream} stream, or\n- Remove the {stream}-specific option from the command"),
+    ("rich-no-streams", "None"),
+    ("rich-subtitle-modes", "## Burned-in vs Soft Subtitles\n\n| | Burn-in (`-vf subtitles=subs.srt`) | Soft track (`-c:s`) |\n|---|---|---|\n| Needs | a video stream to draw on | a container that can store the subtitle codec |\n| Video | re-encoded with text drawn into frames | untouched |\n| Viewer | always visible | can be toggled off |\n\nUse `subtitles=`/`ass=` when the player cannot show subtitle tracks, otherwise prefer `-c:s mov_text` (MP4/MOV), `-c:s webvtt` (WebM) or `-c:s copy` (MKV)."),
+    ("stream-video", "Video"),
+    ("stream-audio", "Audio"),
+    ("stream-subtitle", "Subtitle"),
+    ("stream-data", "Data"),
+    ("stream-unknown", "Unknown"),
+    ("span-missing-required-stream", "missing required stream"),
+    ("span-no-stream-in-input", "no {stream} stream in input"),
+    ("operation-filter", "filter '{filter}'"),
+    ("filter-without-stream", "Filter '{filter}' requires {stream} stream, but no {stream} stream is available"),
+    ("filter-stream-mismatch", "Filter '{filter}' expects {stream} stream but is being used in {context} context"),
+    ("unknown-filter", "Unknown filter: '{filter}'"),
+    ("span-unknown-filter", "unknown filter"),
+    ("codec-stream-mismatch", "Codec '{codec}' is a {stream} codec but is being used for {context} stream"),
+    ("span-invalid-codec-for-stream", "invalid codec for stream"),
+    ("span-unknown-codec", "unknown codec"),
+    ("unknown-codec", "Unknown codec: '{codec}'{hint}"),
+    ("no-encoder", "ffmpeg can decode {stream} codec '{codec}' but has no encoder for it"),
+    ("span-no-encoder", "no encoder"),
+    ("span-decoder-name", "decoder name"),
+    ("decoder-as-encoder", "'{codec}' is a decoder; ffmpeg encodes with its default {codec} encoder instead{hint}"),
+    ("span-encoder-name", "encoder name"),
+    ("encoder-as-decoder", "'{codec}' is an encoder and cannot decode input {identity}{hint}"),
+    ("codec-not-in-container", "Codec '{codec}' is not supported in '{format}' container"),
+    ("span-codec", "codec"),
+    ("span-container", "{format} container"),
+    ("fix-did-you-mean", "did you mean {name}?"),
+    ("hint-did-you-mean", ". Did you mean {choices}?"),
+    ("system-memory", "system memory"),
+    ("family-gpu-memory", "{family} GPU memory"),
+    ("gpu-memory", "GPU memory"),
+    ("unknown-hwaccel", "Unknown hardware acceleration method '{method}'"),
+    ("span-unknown-method", "unknown method"),
+    ("hwaccel-output-format-without-hwaccel", "'-hwaccel_output_format' has no effect without '-hwaccel'"),
+    ("span-ignored", "ignored"),
+    ("operation-encoder", "encoder '{encoder}'"),
+    ("operation-default-encoder", "the default software encoder"),
+    ("span-needs-system-memory", "needs frames in system memory"),
+    ("fix-download-frames", "download frames first"),
+    ("gpu-frames-into-cpu", "{operation} receives frames in {location}; add 'hwdownload' before it or drop '-hwaccel_output_format'"),
+    ("span-needs-gpu-memory", "needs frames in GPU memory"),
+    ("fix-upload-frames", "upload frames first"),
+    ("cpu-frames-into-gpu", "'{component}' needs hardware frames but receives frames in system memory; upload them with 'hwupload'"),
+    ("gpu-family-mismatch", "'{component}' uses {family} but receives {found} frames"),
+    ("span-family-component", "{family} component"),
+    ("conflict-overwrite-flags", "ffmpeg exits when both -y and -n are supplied"),
+    ("conflict-vn-video-codec", "-vn disables video, so the video codec is never used"),
+    ("conflict-an-audio-codec", "-an disables audio, so the audio codec is never used"),
+    ("conflict-duration-end", "ffmpeg uses -t and ignores -to"),
+    ("conflict-crf-bitrate", "-crf selects constant quality mode, so the target bitrate is ignored"),
+    ("span-unknown-option", "unknown option"),
+    ("unknown-option", "Unknown option '{option}'{hint}"),
+    ("option-conflict", "'{second}' conflicts with '{first}': {reason}"),
+    ("span-conflicts-with", "conflicts with {option}"),
+    ("span-first-set-here", "first set here"),
+    ("fix-remove", "remove {option}"),
+    ("option-repeated", "'{option}' is given more than once"),
+    ("span-redundant", "redundant"),
+    ("fix-remove-it", "remove it"),
+    ("kind-codec", "Codec"),
+    ("kind-option", "Option"),
+    ("kind-filter", "Filter"),
+    ("release", "ffmpeg {version}"),
+    ("current-releases", "current ffmpeg releases"),
+    ("experimental-encoder", "Encoder '{encoder}' is experimental in {release}; ffmpeg refuses it without '-strict experimental'"),
+    ("span-experimental-encoder", "experimental encoder"),
+    ("fix-allow-experimental", "allow experimental encoders"),
+    ("fix-use", "use {replacement}"),
+    ("use-instead", "; use {replacement}"),
+    ("added-in", "added in ffmpeg {version}"),
+    ("removed-in", "removed in ffmpeg {version}{instead}"),
+    ("not-available", "not available"),
+    ("unavailable-in-version", "{what} '{name}' is not available in ffmpeg {version}: {reason}"),
+    ("span-not-in-version", "not in ffmpeg {version}"),
+    ("and-removed-in", " and removed in {version}"),
+    ("deprecated", "{what} '{name}' is deprecated since ffmpeg {version}{removal}{instead}"),
+    ("span-deprecated", "deprecated"),
+    ("unused-suppression", "Unused suppression of {codes}: no matching diagnostic"),
+    ("span-unused-suppression", "unused suppression"),
+    ("fix-remove-pragma", "remove the pragma"),
+    ("parse-failed", "Failed to parse FFmpeg command: {error}"),
+    ("span-parse-error", "parse error here"),
+    ("action-remove", "Remove `{text}`"),
+    ("action-insert", "Insert `{text}`"),
+    ("action-replace", "Replace `{text}` with `{replacement}`"),
+    ("copy-description", "Copy the streams without re-encoding"),
+    ("hover-copy-details", "Packets pass through without decoding or encoding, so filters and encoder options do not apply"),
+    ("codec-role-encoder", "{stream} encoder"),
+    ("codec-role-decoder", "{stream} decoder"),
+    ("codec-role-both", "{stream} encoder and decoder"),
+    ("codec-role-codec", "{stream} codec"),
+    ("codec-role-for", "{role} for {codec}"),
+    ("filter-output", "Output of filter {filter} ({stream} stream)"),
+    ("specifier-all-streams", "All streams of {path}"),
+    ("specifier-type-streams", "{stream} streams of {path}"),
+    ("specifier-stream", "{stream} stream {index} of {path}"),
+    ("hover-option-context", "**Context:** {context}"),
+    ("hover-global-command", "global option, applies to the whole command"),
+    ("hover-global", "global option"),
+    ("hover-input-option", "option of input {index} (`{path}`)"),
+    ("hover-output-option", "option of output `{path}`"),
+    ("hover-option-streams", "{stream} streams"),
+    ("hover-accepted-values", "**Accepted values:** {values}"),
+    ("hover-up-to-hz", "up to {rate} Hz"),
+    ("hover-range", "{min} to {max}"),
+    ("hover-storable-encoders", "{encoders}, `{copy}` (encoders {format} can store)"),
+    ("hover-other-encoders", "**Other encoders of {codec}:** {names}"),
+    ("hover-other-decoders", "**Other decoders of {codec}:** {names}"),
+    ("hover-hardware", "**Hardware:** {family} ({platform})"),
+    ("hover-experimental", "**Experimental:** needs `-strict experimental`"),
+    ("hover-containers", "### Compatible containers\n{formats}"),
+    ("hover-source-filter", "none (source)"),
+    ("hover-pad-count", "{count} {stream}"),
+    ("hover-any-pads", "any number of {stream}"),
+    ("hover-filter-pads", "**Inputs:** {inputs} · **Output:** {output}"),
+    ("hover-filter-parameters", "### Parameters\nGiven by name or in this order:\n\n| Parameter | Value | Description |\n|---|---|---|"),
+    ("hover-unknown-label", "No filter produces this label"),
+    ("hover-label-streams", "{specifier}: {streams}"),
+    ("hover-no-known-streams", "no known streams"),
+    ("hover-input-title", "## Input {index}: `{path}`"),
+    ("hover-duration", "**Duration:** {seconds} s"),
+    ("hover-streams-unknown", "Stream types unknown: the input has no media information, and neither its extension nor its format implies them"),
+    ("hover-streams-probed", "Streams from the media information:"),
+    ("hover-streams-guessed", "Streams guessed from the file name:"),
+    ("hover-stream-table", "| Stream | Type | Codec | Details |\n|---|---|---|---|"),
+    ("hover-unknown-codec", "unknown"),
+    ("hover-fps", "{fps} fps"),
+    ("hover-channels", "{channels} channels"),
+    ("filter-graph-link", "Filter graph link"),
+];
+
+static DE: &[(&str, &str)] = &[
+    ("choices", "{first} oder {last}"),
+    ("unknown-input-streams", "Unbekannte Stream-Typen für Eingabe: {path}"),
+    ("span-unknown-streams", "unbekannte Streams"),
+    ("pipeline-overview", "FFmpeg-Verarbeitungsablauf"),
+    ("span-entire-command", "gesamter Befehl"),
+    ("rich-pipeline-overview", "## Pipeline-Übersicht\n\nDieses Diagramm zeigt, wie Daten durch Ihren FFmpeg-Befehl fließen."),
+    ("video-codec-without-video", "Video-Codec angegeben, aber die Eingaben enthalten keinen Video-Stream"),
+    ("span-codec-requires-video", "Codec benötigt Video"),
+    ("audio-codec-without-audio", "Audio-Codec angegeben, aber die Eingaben enthalten keinen Audio-Stream"),
+    ("span-codec-requires-audio", "Codec benötigt Audio"),
+    ("subtitle-codec-without-subtitles", "Untertitel-Codec angegeben, aber die Eingaben enthalten keinen Untertitel-Stream"),
+    ("span-codec-requires-subtitles", "Codec benötigt Untertitel"),
+    ("rich-filter-expects-video", "Filter '{filter}' erwartet eine Video-Eingabe."),
+    ("fix-encode-with", "mit {encoder} kodieren"),
+    ("fix-write-container", "stattdessen {format} schreiben"),
+    ("span-stream-copy", "Stream-Kopie"),
+    ("span-codec-audio", "{codec}-Audio"),
+    ("audio-copy-unsupported", "{codec}-Audio kann nicht nach '{format}' kopiert werden; kodieren Sie es mit '-c:a {encoder}'"),
+    ("audio-copy-unsupported-drop", "{codec}-Audio kann nicht nach '{format}' kopiert werden; entfernen Sie es mit '-an'"),
+    ("span-codec-subtitles", "{codec}-Untertitel"),
+    ("fix-convert-to", "in {codec} umwandeln"),
+    ("subtitle-copy-unsupported", "{codec}-Untertitel können nicht nach '{format}' kopiert werden; wandeln Sie sie mit '-c:s {encoder}' um"),
+    ("subtitle-copy-unsupported-drop", "{codec}-Untertitel können nicht nach '{format}' kopiert werden, da das Format keine Untertitel unterstützt; entfernen Sie sie mit '-sn'"),
+    ("subtitle-kind-bitmap", "Bitmap"),
+    ("subtitle-kind-text", "Text"),
+    ("subtitle-conversion", "{codec}-Untertitel ({kind}) können nicht in {target} ({target_kind}) umgewandelt werden"),
+    ("span-codec-encoder", "{codec}-Encoder"),
+    ("span-re-encode", "Neukodierung"),
+    ("span-source", "{source}-Quelle"),
+    ("same-codec-re-encode", "Die Neukodierung von {source} nach {codec} ohne Filter kostet nur Qualität; kopieren Sie den Stream stattdessen"),
+    ("fix-generous-bitrate", "großzügige Bitrate verwenden"),
+    ("lossy-audio-transcode", "Die Umwandlung von verlustbehaftetem {source}-Audio nach {codec} verstärkt Kompressionsartefakte"),
+    ("fix-raise-bitrate", "Bitrate erhöhen"),
+    ("fix-lower-crf", "CRF senken"),
+    ("lossless-low-quality", "Verlustfreie {source}-Quelle wird mit niedriger Qualität nach {codec} komprimiert"),
+    ("option-ignored-with-copy", "Option '{option}' hat mit '-c:v copy' keine Wirkung"),
+    ("span-ignored-option", "ignorierte Option"),
+    ("option-unsupported-by-encoder", "Encoder '{encoder}' unterstützt die Option '{option}' nicht; sie wird ignoriert"),
+    ("span-unsupported-option", "nicht unterstützte Option"),
+    ("span-encoder", "Encoder {encoder}"),
+    ("option-out-of-range", "Wert {value} für '{option}' liegt außerhalb des Bereichs von Encoder '{encoder}' (erwartet {min} bis {max})"),
+    ("span-expected-range", "erwartet {min} bis {max}"),
+    ("option-not-a-number", "Ungültiger Wert '{value}' für '{option}' (Zahl erwartet)"),
+    ("span-expected-number", "Zahl erwartet"),
+    ("option-invalid-value", "Ungültiger Wert '{value}' für '{option}' mit Encoder '{encoder}' (zulässig: {accepted})"),
+    ("span-invalid-option", "ungültiges {option}"),
+    ("profile-pixel-format", "Profil '{profile}' von '{encoder}' unterstützt das Pixelformat '{format}' ({depth} Bit {chroma}) nicht"),
+    ("span-profile", "Profil"),
+    ("span-pixel-format", "Pixelformat"),
+    ("no-quality-setting", "Keine Bitrate- oder Qualitätseinstellung für '{encoder}'; die Standardwerte des Encoders werden verwendet"),
+    ("span-add-quality-setting", "-b:v oder -crf hinzufügen"),
+    ("pixel-format-not-web", "Pixelformat '{format}' ({depth} Bit {chroma}) wird von gängigen Browsern nicht dekodiert; verwenden Sie yuv420p für die Web-Auslieferung"),
+    ("span-not-web-compatible", "nicht webkompatibel"),
+    ("span-web-output", "Web-Ausgabe"),
+    ("unknown-pixel-format", "Unbekanntes Pixelformat: '{format}'"),
+    ("span-unknown-pixel-format", "unbekanntes Pixelformat"),
+    ("unsupported-pixel-format", "Encoder '{encoder}' unterstützt das Pixelformat '{format}' nicht; ffmpeg wandelt in ein anderes Format um"),
+    ("span-unsupported-pixel-format", "nicht unterstütztes Pixelformat"),
+    ("rich-supported-pixel-formats", "## Unterstützte Pixelformate für `{encoder}`\n\n{formats}"),
+    ("span-unsupported-sample-rate", "nicht unterstützte Abtastrate"),
+    ("fix-use-sample-rate", "{rate} Hz verwenden"),
+    ("unsupported-sample-rate", "Encoder '{encoder}' unterstützt keine Abtastrate von {rate} Hz"),
+    ("rich-supported-sample-rates", "## Unterstützte Abtastraten für `{encoder}`\n\n{rates}"),
+    ("rich-any-sample-rate", "Jede Rate bis {max} Hz"),
+    ("too-many-channels", "Encoder '{encoder}' unterstützt höchstens {max} Kanäle, aber '{value}' verlangt {count}"),
+    ("span-channels", "{count} Kanäle"),
+    ("duration-not-positive", "Dauer '-t {time}' muss größer als null sein"),
+    ("span-non-positive-duration", "nicht positive Dauer"),
+    ("sseof-on-output", "'-sseof' gilt nur für Eingaben; setzen Sie es vor '-i'"),
+    ("span-not-followed-by-input", "keine Eingabe folgt"),
+    ("sseof-not-negative", "'-sseof {time}' muss negativ sein (Sekunden vor Dateiende)"),
+    ("span-must-be-negative", "muss negativ sein"),
+    ("start-after-end", "Startzeit '-ss {start}' liegt nicht vor der Endzeit '-to {end}'"),
+    ("span-start", "Start"),
+    ("span-end", "Ende"),
+    ("seek-past-end", "Suchposition {time} liegt hinter dem Ende der Eingabe ({duration} s); die Ausgabe wird leer sein"),
+    ("span-past-end-of-input", "hinter dem Ende der Eingabe"),
+    ("span-seconds-long", "{duration} s lang"),
+    ("input-seek-with-copy", "Eingabeseitiges Suchen mit Stream-Kopie beginnt am nächsten Keyframe vor der Suchposition, der Schnitt kann also früher als gewünscht beginnen"),
+    ("output-seek-with-copy", "Ausgabeseitiges Suchen mit Stream-Kopie verwirft Pakete vor der Suchposition, das Video kann also bis zum nächsten Keyframe gestört sein"),
+    ("span-seek", "Suche"),
+    ("span-copy-snaps-to-keyframes", "Stream-Kopie rastet an Keyframes ein"),
+    ("rich-seek-with-copy", "## Suchen mit `-c copy`\n\nEine Stream-Kopie kann nicht zwischen Keyframes schneiden. Für einen bildgenauen Schnitt kodieren Sie neu (z. B. `-c:v libx264`) oder akzeptieren Schnittgrenzen an Keyframes.\n\n- `-ss` **vor** `-i` (Eingabesuche) ist schnell und rastet am vorherigen Keyframe ein\n- `-ss` **nach** `-i` (Ausgabesuche) dekodiert und verwirft bis zur Suchposition"),
+    ("output-overwrites-input", "Ausgabe '{output}' ist dieselbe Datei wie Eingabe '{input}'; ffmpeg kann Dateien nicht direkt bearbeiten"),
+    ("span-output", "Ausgabe"),
+    ("span-same-file-as-input", "dieselbe Datei wie die Eingabe"),
+    ("duplicate-output", "Ausgabe '{output}' wird mehrfach geschrieben"),
+    ("span-duplicate-output", "doppelte Ausgabe"),
+    ("span-first-written-here", "zuerst hier geschrieben"),
+    ("glob-without-pattern-type", "Platzhalter in '{input}' werden nur mit '-pattern_type glob' aufgelöst"),
+    ("span-wildcard-pattern", "Platzhaltermuster"),
+    ("image-sequence-input-rate", "Verwenden Sie '-framerate', um die Rate einer Bildsequenz-Eingabe festzulegen; '-r' an einer Eingabe überschreibt stattdessen die Zeitstempel"),
+    ("span-input-frame-rate", "Eingabe-Bildrate"),
+    ("fix-use-framerate", "-framerate verwenden"),
+    ("image-sequence-default-rate", "Bildsequenz '{input}' hat kein '-framerate'; sie wird mit 25 fps gelesen"),
+    ("span-add-framerate", "-framerate vor -i hinzufügen"),
+    ("image-output-without-pattern", "Bildausgabe '{output}' hat kein Bildnummernmuster; verwenden Sie einen Namen wie 'frame%04d.png' oder '-frames:v 1' für ein einzelnes Bild"),
+    ("span-missing-pattern", "%d-Muster fehlt"),
+    ("span-unknown-format", "unbekanntes Format"),
+    ("unknown-output-format", "Unbekanntes Ausgabeformat '{format}'{hint}"),
+    ("span-invalid-resolution-format", "ungültiges Auflösungsformat"),
+    ("fix-16-9-frame-size", "16:9-Bildgröße"),
+    ("invalid-resolution-format", "Ungültiges Auflösungsformat '{value}' (erwartet: BREITExHÖHE)"),
+    ("invalid-resolution-numbers", "Ungültige Auflösung '{value}' (Breite und Höhe müssen Zahlen sein)"),
+    ("span-resolution-numbers", "Breite/Höhe müssen Zahlen sein"),
+    ("invalid-bitrate", "Ungültiges Bitrate-Format '{value}'"),
+    ("span-invalid-bitrate", "ungültige Bitrate"),
+    ("high-bitrate", "Extrem hohe Bitrate angegeben: {value}"),
+    ("span-high-bitrate", "hohe Bitrate"),
+    ("span-bits-per-second", "{bps} Bit/s"),
+    ("fix-add-k-suffix", "Suffix k ergänzen"),
+    ("low-bitrate", "Extrem niedrige Bitrate '{value}' ({bps} Bit/s); Bitraten ohne Suffix sind in Bit pro Sekunde angegeben"),
+    ("invalid-option-bitrate", "Ungültiges Bitrate-Format '{value}' für '{option}'"),
+    ("maxrate-without-bufsize", "'-maxrate' hat ohne '-bufsize' keine Wirkung; Encoder ignorieren die VBV-Grenze"),
+    ("span-needs-bufsize", "benötigt -bufsize"),
+    ("fix-add-bufsize", "-bufsize hinzufügen"),
+    ("minrate-above-maxrate", "'-minrate {minrate}' liegt über '-maxrate {maxrate}'"),
+    ("span-above-maxrate", "über maxrate"),
+    ("span-maxrate", "maxrate"),
+    ("bitrate-above-maxrate", "Zielbitrate '-b:v {bitrate}' liegt über '-maxrate {maxrate}' und kann nicht erreicht werden"),
+    ("frame-size-at-rate", "{width}x{height} bei {fps} fps"),
+    ("frame-size-at-assumed-rate", "{width}x{height} bei angenommenen 30 fps"),
+    ("bitrate-too-low-for-frame", "Bitrate {bitrate} ist zu niedrig für {encoder} mit {frame}; rechnen Sie mit starken Kompressionsartefakten"),
+    ("span-bits-per-pixel", "{bpp} Bit pro Pixel"),
+    ("fix-minimum-for-resolution", "Minimum für diese Auflösung"),
+    ("bitrate-too-high-for-frame", "Bitrate {bitrate} ist mehr, als {encoder} für {frame} benötigt; die zusätzlichen Bits vergrößern die Datei ohne sichtbaren Qualitätsgewinn"),
+    ("fix-enough-for-resolution", "ausreichend für diese Auflösung"),
+    ("upscaling", "Ausgabe {to} ist größer als die {from}-Quelle; Hochskalieren vergrößert die Datei, fügt aber keine Details hinzu"),
+    ("span-upscales", "skaliert hoch"),
+    ("frame-rate-out-of-range", "Ungültige Bildrate '{value}' (muss zwischen 0 und 1000 liegen)"),
+    ("span-invalid-frame-rate", "ungültige Bildrate"),
+    ("invalid-frame-rate", "Ungültiges Bildratenformat '{value}'"),
+    ("span-invalid-frame-rate-format", "ungültiges Bildratenformat"),
+    ("unknown-filter-label", "Referenzierte Filterausgabe '{label}' existiert nicht"),
+    ("span-unknown-label", "unbekanntes Label"),
+    ("unknown-input-index", "Eingabeindex {index} existiert nicht"),
+    ("span-unknown-input-index", "nicht vorhandener Eingabeindex"),
+    ("rich-codec-format-incompatibility", "## Codec/Container-Inkompatibilität\n\nDer Codec **{codec}** kann nicht mit **{format}**-Containern verwendet werden.\n\n### Kompatible Container\n{formats}\n\n### Lösung\nÄndern Sie die Dateiendung der Ausgabe auf ein kompatibles Containerformat."),
+    ("rich-missing-stream", "## Fehlender {stream}-Stream\n\nDer Vorgang **{operation}** benötigt einen {stream}-Stream, aber die Eingaben enthalten keinen.\n\n### Verfügbare Streams\n{streams}\n\n### Lösung\n- Verwenden Sie eine Eingabedatei mit einem {stream}-Stream, oder\n- Entfernen Sie die {stream}-spezifische Option aus dem Befehl"),
+    ("rich-no-streams", "Keine"),
+    ("rich-subtitle-modes", "## Eingebrannte und umschaltbare Untertitel\n\n| | Einbrennen (`-vf subtitles=subs.srt`) | Untertitelspur (`-c:s`) |\n|---|---|---|\n| Benötigt | einen Video-Stream zum Zeichnen | einen Container, der den Untertitel-Codec speichern kann |\n| Video | neu kodiert, Text in die Bilder gezeichnet | unverändert |\n| Anzeige | immer sichtbar | abschaltbar |\n\nVerwenden Sie `subtitles=`/`ass=`, wenn der Player keine Untertitelspuren anzeigen kann, sonst `-c:s mov_text` (MP4/MOV), `-c:s webvtt` (WebM) oder `-c:s copy` (MKV)."),
+    ("stream-video", "Video"),
+    ("stream-audio", "Audio"),
+    ("stream-subtitle", "Untertitel"),
+    ("stream-data", "Daten"),
+    ("stream-unknown", "Unbekannt"),
+    ("span-missing-required-stream", "benötigter Stream fehlt"),
+    ("span-no-stream-in-input", "kein {stream}-Stream in der Eingabe"),
+    ("operation-filter", "Filter '{filter}'"),
+    ("filter-without-stream", "Filter '{filter}' benötigt einen {stream}-Stream, aber es ist kein {stream}-Stream verfügbar"),
+    ("filter-stream-mismatch", "Filter '{filter}' erwartet einen {stream}-Stream, wird aber im {context}-Kontext verwendet"),
+    ("unknown-filter", "Unbekannter Filter: '{filter}'"),
+    ("span-unknown-filter", "unbekannter Filter"),
+    ("codec-stream-mismatch", "Codec '{codec}' ist ein {stream}-Codec, wird aber für einen {context}-Stream verwendet"),
+    ("span-invalid-codec-for-stream", "ungültiger Codec für den Stream"),
+    ("span-unknown-codec", "unbekannter Codec"),
+    ("unknown-codec", "Unbekannter Codec: '{codec}'{hint}"),
+    ("no-encoder", "ffmpeg kann den {stream}-Codec '{codec}' dekodieren, hat aber keinen Encoder dafür"),
+    ("span-no-encoder", "kein Encoder"),
+    ("span-decoder-name", "Decoder-Name"),
+    ("decoder-as-encoder", "'{codec}' ist ein Decoder; ffmpeg kodiert stattdessen mit dem Standard-Encoder für {codec}{hint}"),
+    ("span-encoder-name", "Encoder-Name"),
+    ("encoder-as-decoder", "'{codec}' ist ein Encoder und kann die Eingabe {identity} nicht dekodieren{hint}"),
+    ("codec-not-in-container", "Codec '{codec}' wird im Container '{format}' nicht unterstützt"),
+    ("span-codec", "Codec"),
+    ("span-container", "{format}-Container"),
+    ("fix-did-you-mean", "meinten Sie {name}?"),
+    ("hint-did-you-mean", ". Meinten Sie {choices}?"),
+    ("system-memory", "Arbeitsspeicher"),
+    ("family-gpu-memory", "{family}-GPU-Speicher"),
+    ("gpu-memory", "GPU-Speicher"),
+    ("unknown-hwaccel", "Unbekannte Hardwarebeschleunigungsmethode '{method}'"),
+    ("span-unknown-method", "unbekannte Methode"),
+    ("hwaccel-output-format-without-hwaccel", "'-hwaccel_output_format' hat ohne '-hwaccel' keine Wirkung"),
+    ("span-ignored", "ignoriert"),
+    ("operation-encoder", "Encoder '{encoder}'"),
+    ("operation-default-encoder", "der Standard-Software-Encoder"),
+    ("span-needs-system-memory", "benötigt Bilder im Arbeitsspeicher"),
+    ("fix-download-frames", "Bilder zuerst herunterladen"),
+    ("gpu-frames-into-cpu", "{operation} erhält Bilder im {location}; fügen Sie davor 'hwdownload' ein oder entfernen Sie '-hwaccel_output_format'"),
+    ("span-needs-gpu-memory", "benötigt Bilder im GPU-Speicher"),
+    ("fix-upload-frames", "Bilder zuerst hochladen"),
+    ("cpu-frames-into-gpu", "'{component}' benötigt Hardware-Bilder, erhält aber Bilder im Arbeitsspeicher; laden Sie sie mit 'hwupload' hoch"),
+    ("gpu-family-mismatch", "'{component}' verwendet {family}, erhält aber {found}-Bilder"),
+    ("span-family-component", "{family}-Komponente"),
+    ("conflict-overwrite-flags", "ffmpeg bricht ab, wenn sowohl -y als auch -n angegeben sind"),
+    ("conflict-vn-video-codec", "-vn deaktiviert Video, der Video-Codec wird also nie verwendet"),
+    ("conflict-an-audio-codec", "-an deaktiviert Audio, der Audio-Codec wird also nie verwendet"),
+    ("conflict-duration-end", "ffmpeg verwendet -t und ignoriert -to"),
+    ("conflict-crf-bitrate", "-crf wählt den Modus mit konstanter Qualität, die Zielbitrate wird also ignoriert"),
+    ("span-unknown-option", "unbekannte Option"),
+    ("unknown-option", "Unbekannte Option '{option}'{hint}"),
+    ("option-conflict", "'{second}' steht im Konflikt mit '{first}': {reason}"),
+    ("span-conflicts-with", "Konflikt mit {option}"),
+    ("span-first-set-here", "zuerst hier gesetzt"),
+    ("fix-remove", "{option} entfernen"),
+    ("option-repeated", "'{option}' ist mehrfach angegeben"),
+    ("span-redundant", "überflüssig"),
+    ("fix-remove-it", "entfernen"),
+    ("kind-codec", "Codec"),
+    ("kind-option", "Option"),
+    ("kind-filter", "Filter"),
+    ("release", "ffmpeg {version}"),
+    ("current-releases", "aktuellen ffmpeg-Versionen"),
+    ("experimental-encoder", "Encoder '{encoder}' ist in {release} experimentell; ffmpeg lehnt ihn ohne '-strict experimental' ab"),
+    ("span-experimental-encoder", "experimenteller Encoder"),
+    ("fix-allow-experimental", "experimentelle Encoder erlauben"),
+    ("fix-use", "{replacement} verwenden"),
+    ("use-instead", "; verwenden Sie {replacement}"),
+    ("added-in", "hinzugefügt in ffmpeg {version}"),
+    ("removed-in", "entfernt in ffmpeg {version}{instead}"),
+    ("not-available", "nicht verfügbar"),
+    ("unavailable-in-version", "{what} '{name}' ist in ffmpeg {version} nicht verfügbar: {reason}"),
+    ("span-not-in-version", "nicht in ffmpeg {version}"),
+    ("and-removed-in", " und entfernt in {version}"),
+    ("deprecated", "{what} '{name}' ist seit ffmpeg {version} veraltet{removal}{instead}"),
+    ("span-deprecated", "veraltet"),
+    ("unused-suppression", "Unbenutzte Unterdrückung von {codes}: keine passende Diagnose"),
+    ("span-unused-suppression", "unbenutzte Unterdrückung"),
+    ("fix-remove-pragma", "Pragma entfernen"),
+    ("parse-failed", "FFmpeg-Befehl konnte nicht geparst werden: {error}"),
+    ("span-parse-error", "Parserfehler hier"),
+    ("action-remove", "`{text}` entfernen"),
+    ("action-insert", "`{text}` einfügen"),
+    ("action-replace", "`{text}` durch `{replacement}` ersetzen"),
+    ("copy-description", "Streams ohne Neukodierung kopieren"),
+    ("hover-copy-details", "Pakete werden weder dekodiert noch kodiert, daher wirken Filter und Encoder-Optionen nicht"),
+    ("codec-role-encoder", "{stream}-Encoder"),
+    ("codec-role-decoder", "{stream}-Decoder"),
+    ("codec-role-both", "{stream}-Encoder und -Decoder"),
+    ("codec-role-codec", "{stream}-Codec"),
+    ("codec-role-for", "{role} für {codec}"),
+    ("filter-output", "Ausgang des Filters {filter} ({stream}-Stream)"),
+    ("specifier-all-streams", "Alle Streams von {path}"),
+    ("specifier-type-streams", "{stream}-Streams von {path}"),
+    ("specifier-stream", "{stream}-Stream {index} von {path}"),
+    ("hover-option-context", "**Kontext:** {context}"),
+    ("hover-global-command", "globale Option, gilt für den ganzen Befehl"),
+    ("hover-global", "globale Option"),
+    ("hover-input-option", "Option der Eingabe {index} (`{path}`)"),
+    ("hover-output-option", "Option der Ausgabe `{path}`"),
+    ("hover-option-streams", "{stream}-Streams"),
+    ("hover-accepted-values", "**Zulässige Werte:** {values}"),
+    ("hover-up-to-hz", "bis {rate} Hz"),
+    ("hover-range", "{min} bis {max}"),
+    ("hover-storable-encoders", "{encoders}, `{copy}` (Encoder, die {format} speichern kann)"),
+    ("hover-other-encoders", "**Weitere Encoder für {codec}:** {names}"),
+    ("hover-other-decoders", "**Weitere Decoder für {codec}:** {names}"),
+    ("hover-hardware", "**Hardware:** {family} ({platform})"),
+    ("hover-experimental", "**Experimentell:** benötigt `-strict experimental`"),
+    ("hover-containers", "### Kompatible Container\n{formats}"),
+    ("hover-source-filter", "keine (Quelle)"),
+    ("hover-pad-count", "{count} {stream}"),
+    ("hover-any-pads", "beliebig viele {stream}"),
+    ("hover-filter-pads", "**Eingänge:** {inputs} · **Ausgang:** {output}"),
+    ("hover-filter-parameters", "### Parameter\nPer Name oder in dieser Reihenfolge anzugeben:\n\n| Parameter | Wert | Beschreibung |\n|---|---|---|"),
+    ("hover-unknown-label", "Kein Filter erzeugt dieses Label"),
+    ("hover-label-streams", "{specifier}: {streams}"),
+    ("hover-no-known-streams", "keine bekannten Streams"),
+    ("hover-input-title", "## Eingabe {index}: `{path}`"),
+    ("hover-duration", "**Dauer:** {seconds} s"),
+    ("hover-streams-unknown", "Stream-Typen unbekannt: Für die Eingabe gibt es keine Medieninformationen, und weder ihre Endung noch ihr Format legt sie fest"),
+    ("hover-streams-probed", "Streams aus den Medieninformationen:"),
+    ("hover-streams-guessed", "Aus dem Dateinamen abgeleitete Streams:"),
+    ("hover-stream-table", "| Stream | Typ | Codec | Details |\n|---|---|---|---|"),
+    ("hover-unknown-codec", "unbekannt"),
+    ("hover-fps", "{fps} fps"),
+    ("hover-channels", "{channels} Kanäle"),
+    ("filter-graph-link", "Verbindung im Filtergraphen"),
+];
+
+static FR: &[(&str, &str)] = &[
+    ("choices", "{first} ou {last}"),
+    ("unknown-input-streams", "Types de flux inconnus pour l'entrée : {path}"),
+    ("span-unknown-streams", "flux inconnus"),
+    ("pipeline-overview", "Flux de traitement FFmpeg"),
+    ("span-entire-command", "commande entière"),
+    ("rich-pipeline-overview", "## Vue d'ensemble du pipeline\n\nCe diagramme montre le parcours des données dans votre commande FFmpeg."),
+    ("video-codec-without-video", "Codec vidéo indiqué, mais aucun flux vidéo dans les entrées"),
+    ("span-codec-requires-video", "le codec exige de la vidéo"),
+    ("audio-codec-without-audio", "Codec audio indiqué, mais aucun flux audio dans les entrées"),
+    ("span-codec-requires-audio", "le codec exige de l'audio"),
+    ("subtitle-codec-without-subtitles", "Codec de sous-titres indiqué, mais aucun flux de sous-titres dans les entrées"),
+    ("span-codec-requires-subtitles", "le codec exige des sous-titres"),
+    ("rich-filter-expects-video", "Le filtre '{filter}' attend une entrée vidéo."),
+    ("fix-encode-with", "encoder avec {encoder}"),
+    ("fix-write-container", "écrire du {format} à la place"),
+    ("span-stream-copy", "copie de flux"),
+    ("span-codec-audio", "audio {codec}"),
+    ("audio-copy-unsupported", "L'audio {codec} ne peut pas être copié dans '{format}' ; encodez-le avec '-c:a {encoder}'"),
+    ("audio-copy-unsupported-drop", "L'audio {codec} ne peut pas être copié dans '{format}' ; supprimez-le avec '-an'"),
+    ("span-codec-subtitles", "sous-titres {codec}"),
+    ("fix-convert-to", "convertir en {codec}"),
+    ("subtitle-copy-unsupported", "Les sous-titres {codec} ne peuvent pas être copiés dans '{format}' ; convertissez-les avec '-c:s {encoder}'"),
+    ("subtitle-copy-unsupported-drop", "Les sous-titres {codec} ne peuvent pas être copiés dans '{format}', qui ne prend pas en charge les sous-titres ; supprimez-les avec '-sn'"),
+    ("subtitle-kind-bitmap", "bitmap"),
+    ("subtitle-kind-text", "texte"),
+    ("subtitle-conversion", "Les sous-titres {codec} ({kind}) ne peuvent pas être convertis en {target} ({target_kind})"),
+    ("span-codec-encoder", "encodeur {codec}"),
+    ("span-re-encode", "réencodage"),
+    ("span-source", "source {source}"),
+    ("same-codec-re-encode", "Réencoder {source} en {codec} sans filtre ne fait que dégrader la qualité ; copiez plutôt le flux"),
+    ("fix-generous-bitrate", "utiliser un débit généreux"),
+    ("lossy-audio-transcode", "Transcoder un audio {source} avec pertes en {codec} accentue les artefacts de compression"),
+    ("fix-raise-bitrate", "augmenter le débit"),
+    ("fix-lower-crf", "baisser le CRF"),
+    ("lossless-low-quality", "La source {source} sans perte est compressée en {codec} avec une qualité faible"),
+    ("option-ignored-with-copy", "L'option '{option}' n'a aucun effet avec '-c:v copy'"),
+    ("span-ignored-option", "option ignorée"),
+    ("option-unsupported-by-encoder", "L'encodeur '{encoder}' ne prend pas en charge l'option '{option}' ; elle sera ignorée"),
+    ("span-unsupported-option", "option non prise en charge"),
+    ("span-encoder", "encodeur {encoder}"),
+    ("option-out-of-range", "La valeur {value} de '{option}' est hors de la plage de l'encodeur '{encoder}' ({min} à {max} attendu)"),
+    ("span-expected-range", "{min} à {max} attendu"),
+    ("option-not-a-number", "Valeur '{value}' invalide pour '{option}' (nombre attendu)"),
+    ("span-expected-number", "nombre attendu"),
+    ("option-invalid-value", "Valeur '{value}' invalide pour '{option}' avec l'encodeur '{encoder}' (valeurs acceptées : {accepted})"),
+    ("span-invalid-option", "{option} invalide"),
+    ("profile-pixel-format", "Le profil '{profile}' de '{encoder}' ne prend pas en charge le format de pixel '{format}' ({depth} bits {chroma})"),
+    ("span-profile", "profil"),
+    ("span-pixel-format", "format de pixel"),
+    ("no-quality-setting", "Aucun réglage de débit ni de qualité pour '{encoder}' ; les valeurs par défaut de l'encodeur seront utilisées"),
+    ("span-add-quality-setting", "ajouter -b:v ou -crf"),
+    ("pixel-format-not-web", "Le format de pixel '{format}' ({depth} bits {chroma}) n'est pas décodé par les navigateurs courants ; utilisez yuv420p pour la diffusion web"),
+    ("span-not-web-compatible", "non compatible web"),
+    ("span-web-output", "sortie web"),
+    ("unknown-pixel-format", "Format de pixel inconnu : '{format}'"),
+    ("span-unknown-pixel-format", "format de pixel inconnu"),
+    ("unsupported-pixel-format", "L'encodeur '{encoder}' ne prend pas en charge le format de pixel '{format}' ; ffmpeg convertira vers un autre format"),
+    ("span-unsupported-pixel-format", "format de pixel non pris en charge"),
+    ("rich-supported-pixel-formats", "## Formats de pixel pris en charge par `{encoder}`\n\n{formats}"),
+    ("span-unsupported-sample-rate", "fréquence d'échantillonnage non prise en charge"),
+    ("fix-use-sample-rate", "utiliser {rate} Hz"),
+    ("unsupported-sample-rate", "L'encodeur '{encoder}' ne prend pas en charge une fréquence d'échantillonnage de {rate} Hz"),
+    ("rich-supported-sample-rates", "## Fréquences d'échantillonnage prises en charge par `{encoder}`\n\n{rates}"),
+    ("rich-any-sample-rate", "Toute fréquence jusqu'à {max} Hz"),
+    ("too-many-channels", "L'encodeur '{encoder}' prend en charge au plus {max} canaux, mais '{value}' en demande {count}"),
+    ("span-channels", "{count} canaux"),
+    ("duration-not-positive", "La durée '-t {time}' doit être supérieure à zéro"),
+    ("span-non-positive-duration", "durée non positive"),
+    ("sseof-on-output", "'-sseof' ne s'applique qu'aux entrées ; placez-le avant '-i'"),
+    ("span-not-followed-by-input", "aucune entrée ne suit"),
+    ("sseof-not-negative", "'-sseof {time}' doit être négatif (secondes avant la fin du fichier)"),
+    ("span-must-be-negative", "doit être négatif"),
+    ("start-after-end", "L'instant de début '-ss {start}' n'est pas antérieur à l'instant de fin '-to {end}'"),
+    ("span-start", "début"),
+    ("span-end", "fin"),
+    ("seek-past-end", "La position {time} est au-delà de la fin de l'entrée ({duration} s) ; la sortie sera vide"),
+    ("span-past-end-of-input", "au-delà de la fin de l'entrée"),
+    ("span-seconds-long", "{duration} s de long"),
+    ("input-seek-with-copy", "Une recherche côté entrée avec copie de flux démarre à l'image clé précédant la position, la coupe peut donc commencer plus tôt que prévu"),
+    ("output-seek-with-copy", "Une recherche côté sortie avec copie de flux supprime les paquets avant la position, la vidéo peut donc être corrompue jusqu'à l'image clé suivante"),
+    ("span-seek", "recherche"),
+    ("span-copy-snaps-to-keyframes", "la copie de flux s'aligne sur les images clés"),
+    ("rich-seek-with-copy", "## Recherche avec `-c copy`\n\nUne copie de flux ne peut pas couper entre deux images clés. Pour une coupe à l'image près, réencodez (par ex. `-c:v libx264`) ou acceptez des limites alignées sur les images clés.\n\n- `-ss` **avant** `-i` (recherche en entrée) est rapide et s'aligne sur l'image clé précédente\n- `-ss` **après** `-i` (recherche en sortie) décode puis supprime tout jusqu'à la position"),
+    ("output-overwrites-input", "La sortie '{output}' est le même fichier que l'entrée '{input}' ; ffmpeg ne peut pas modifier un fichier sur place"),
+    ("span-output", "sortie"),
+    ("span-same-file-as-input", "même fichier que l'entrée"),
+    ("duplicate-output", "La sortie '{output}' est écrite plusieurs fois"),
+    ("span-duplicate-output", "sortie en double"),
+    ("span-first-written-here", "écrite une première fois ici"),
+    ("glob-without-pattern-type", "Les caractères génériques de '{input}' ne sont développés qu'avec '-pattern_type glob'"),
+    ("span-wildcard-pattern", "motif générique"),
+    ("image-sequence-input-rate", "Utilisez '-framerate' pour fixer la fréquence d'une séquence d'images en entrée ; '-r' sur une entrée réécrit les horodatages"),
+    ("span-input-frame-rate", "fréquence d'images d'entrée"),
+    ("fix-use-framerate", "utiliser -framerate"),
+    ("image-sequence-default-rate", "La séquence d'images '{input}' n'a pas de '-framerate' ; elle sera lue à 25 ips"),
+    ("span-add-framerate", "ajouter -framerate avant -i"),
+    ("image-output-without-pattern", "La sortie d'images '{output}' n'a pas de motif de numérotation ; utilisez un nom comme 'frame%04d.png' ou '-frames:v 1' pour une seule image"),
+    ("span-missing-pattern", "motif %d manquant"),
+    ("span-unknown-format", "format inconnu"),
+    ("unknown-output-format", "Format de sortie inconnu '{format}'{hint}"),
+    ("span-invalid-resolution-format", "format de résolution invalide"),
+    ("fix-16-9-frame-size", "taille d'image 16:9"),
+    ("invalid-resolution-format", "Format de résolution invalide '{value}' (attendu : LARGEURxHAUTEUR)"),
+    ("invalid-resolution-numbers", "Résolution invalide '{value}' (la largeur et la hauteur doivent être des nombres)"),
+    ("span-resolution-numbers", "la largeur et la hauteur doivent être des nombres"),
+    ("invalid-bitrate", "Format de débit invalide '{value}'"),
+    ("span-invalid-bitrate", "débit invalide"),
+    ("high-bitrate", "Débit extrêmement élevé indiqué : {value}"),
+    ("span-high-bitrate", "débit élevé"),
+    ("span-bits-per-second", "{bps} bit/s"),
+    ("fix-add-k-suffix", "ajouter le suffixe k"),
+    ("low-bitrate", "Débit extrêmement faible '{value}' ({bps} bit/s) ; un débit sans suffixe est en bits par seconde"),
+    ("invalid-option-bitrate", "Format de débit invalide '{value}' pour '{option}'"),
+    ("maxrate-without-bufsize", "'-maxrate' n'a aucun effet sans '-bufsize' ; les encodeurs ignorent la limite VBV"),
+    ("span-needs-bufsize", "nécessite -bufsize"),
+    ("fix-add-bufsize", "ajouter -bufsize"),
+    ("minrate-above-maxrate", "'-minrate {minrate}' est supérieur à '-maxrate {maxrate}'"),
+    ("span-above-maxrate", "supérieur à maxrate"),
+    ("span-maxrate", "maxrate"),
+    ("bitrate-above-maxrate", "Le débit cible '-b:v {bitrate}' est supérieur à '-maxrate {maxrate}' et ne peut pas être atteint"),
+    ("frame-size-at-rate", "{width}x{height} à {fps} ips"),
+    ("frame-size-at-assumed-rate", "{width}x{height} à 30 ips supposées"),
+    ("bitrate-too-low-for-frame", "Le débit {bitrate} est trop faible pour {encoder} en {frame} ; attendez-vous à de forts artefacts de compression"),
+    ("span-bits-per-pixel", "{bpp} bits par pixel"),
+    ("fix-minimum-for-resolution", "minimum pour cette résolution"),
+    ("bitrate-too-high-for-frame", "Le débit {bitrate} dépasse ce dont {encoder} a besoin en {frame} ; les bits supplémentaires alourdissent le fichier sans gain de qualité visible"),
+    ("fix-enough-for-resolution", "suffisant pour cette résolution"),
+    ("upscaling", "La sortie {to} est plus grande que la source {from} ; l'agrandissement alourdit le fichier sans ajouter de détails"),
+    ("span-upscales", "agrandit"),
+    ("frame-rate-out-of-range", "Fréquence d'images invalide '{value}' (doit être comprise entre 0 et 1000)"),
+    ("span-invalid-frame-rate", "fréquence d'images invalide"),
+    ("invalid-frame-rate", "Format de fréquence d'images invalide '{value}'"),
+    ("span-invalid-frame-rate-format", "format de fréquence d'images invalide"),
+    ("unknown-filter-label", "La sortie de filtre référencée '{label}' n'existe pas"),
+    ("span-unknown-label", "étiquette inconnue"),
+    ("unknown-input-index", "L'entrée d'index {index} n'existe pas"),
+    ("span-unknown-input-index", "index d'entrée inexistant"),
+    ("rich-codec-format-incompatibility", "## Incompatibilité codec/conteneur\n\nLe codec **{codec}** ne peut pas être utilisé dans un conteneur **{format}**.\n\n### Conteneurs compatibles\n{formats}\n\n### Solution\nChangez l'extension du fichier de sortie pour un format de conteneur compatible."),
+    ("rich-missing-stream", "## Flux {stream} manquant\n\nL'opération **{operation}** nécessite un flux {stream}, mais les entrées n'en contiennent pas.\n\n### Flux disponibles\n{streams}\n\n### Solution\n- Utilisez un fichier d'entrée contenant un flux {stream}, ou\n- Retirez de la commande l'option propre au flux {stream}"),
+    ("rich-no-streams", "Aucun"),
+    ("rich-subtitle-modes", "## Sous-titres incrustés ou activables\n\n| | Incrustation (`-vf subtitles=subs.srt`) | Piste de sous-titres (`-c:s`) |\n|---|---|---|\n| Nécessite | un flux vidéo où dessiner | un conteneur capable de stocker le codec de sous-titres |\n| Vidéo | réencodée, texte dessiné dans les images | inchangée |\n| Affichage | toujours visible | désactivable |\n\nUtilisez `subtitles=`/`ass=` si le lecteur ne sait pas afficher de piste de sous-titres, sinon `-c:s mov_text` (MP4/MOV), `-c:s webvtt` (WebM) ou `-c:s copy` (MKV)."),
+    ("stream-video", "vidéo"),
+    ("stream-audio", "audio"),
+    ("stream-subtitle", "sous-titres"),
+    ("stream-data", "données"),
+    ("stream-unknown", "inconnu"),
+    ("span-missing-required-stream", "flux requis manquant"),
+    ("span-no-stream-in-input", "aucun flux {stream} en entrée"),
+    ("operation-filter", "filtre '{filter}'"),
+    ("filter-without-stream", "Le filtre '{filter}' nécessite un flux {stream}, mais aucun flux {stream} n'est disponible"),
+    ("filter-stream-mismatch", "Le filtre '{filter}' attend un flux {stream} mais est utilisé dans un contexte {context}"),
+    ("unknown-filter", "Filtre inconnu : '{filter}'"),
+    ("span-unknown-filter", "filtre inconnu"),
+    ("codec-stream-mismatch", "Le codec '{codec}' est un codec {stream} mais est utilisé pour un flux {context}"),
+    ("span-invalid-codec-for-stream", "codec invalide pour ce flux"),
+    ("span-unknown-codec", "codec inconnu"),
+    ("unknown-codec", "Codec inconnu : '{codec}'{hint}"),
+    ("no-encoder", "ffmpeg sait décoder le codec {stream} '{codec}' mais n'a pas d'encodeur pour lui"),
+    ("span-no-encoder", "aucun encodeur"),
+    ("span-decoder-name", "nom de décodeur"),
+    ("decoder-as-encoder", "'{codec}' est un décodeur ; ffmpeg encodera avec l'encodeur par défaut de {codec}{hint}"),
+    ("span-encoder-name", "nom d'encodeur"),
+    ("encoder-as-decoder", "'{codec}' est un encodeur et ne peut pas décoder l'entrée {identity}{hint}"),
+    ("codec-not-in-container", "Le codec '{codec}' n'est pas pris en charge par le conteneur '{format}'"),
+    ("span-codec", "codec"),
+    ("span-container", "conteneur {format}"),
+    ("fix-did-you-mean", "vouliez-vous dire {name} ?"),
+    ("hint-did-you-mean", ". Vouliez-vous dire {choices} ?"),
+    ("system-memory", "mémoire système"),
+    ("family-gpu-memory", "mémoire GPU {family}"),
+    ("gpu-memory", "mémoire GPU"),
+    ("unknown-hwaccel", "Méthode d'accélération matérielle inconnue '{method}'"),
+    ("span-unknown-method", "méthode inconnue"),
+    ("hwaccel-output-format-without-hwaccel", "'-hwaccel_output_format' n'a aucun effet sans '-hwaccel'"),
+    ("span-ignored", "ignoré"),
+    ("operation-encoder", "l'encodeur '{encoder}'"),
+    ("operation-default-encoder", "l'encodeur logiciel par défaut"),
+    ("span-needs-system-memory", "nécessite des images en mémoire système"),
+    ("fix-download-frames", "télécharger d'abord les images"),
+    ("gpu-frames-into-cpu", "{operation} reçoit des images en {location} ; ajoutez 'hwdownload' avant ou retirez '-hwaccel_output_format'"),
+    ("span-needs-gpu-memory", "nécessite des images en mémoire GPU"),
+    ("fix-upload-frames", "envoyer d'abord les images"),
+    ("cpu-frames-into-gpu", "'{component}' nécessite des images matérielles mais reçoit des images en mémoire système ; envoyez-les avec 'hwupload'"),
+    ("gpu-family-mismatch", "'{component}' utilise {family} mais reçoit des images {found}"),
+    ("span-family-component", "composant {family}"),
+    ("conflict-overwrite-flags", "ffmpeg s'arrête quand -y et -n sont tous deux indiqués"),
+    ("conflict-vn-video-codec", "-vn désactive la vidéo, le codec vidéo n'est donc jamais utilisé"),
+    ("conflict-an-audio-codec", "-an désactive l'audio, le codec audio n'est donc jamais utilisé"),
+    ("conflict-duration-end", "ffmpeg utilise -t et ignore -to"),
+    ("conflict-crf-bitrate", "-crf choisit le mode à qualité constante, le débit cible est donc ignoré"),
+    ("span-unknown-option", "option inconnue"),
+    ("unknown-option", "Option inconnue '{option}'{hint}"),
+    ("option-conflict", "'{second}' est en conflit avec '{first}' : {reason}"),
+    ("span-conflicts-with", "en conflit avec {option}"),
+    ("span-first-set-here", "défini une première fois ici"),
+    ("fix-remove", "retirer {option}"),
+    ("option-repeated", "'{option}' est indiqué plusieurs fois"),
+    ("span-redundant", "redondant"),
+    ("fix-remove-it", "retirer"),
+    ("kind-codec", "Le codec"),
+    ("kind-option", "L'option"),
+    ("kind-filter", "Le filtre"),
+    ("release", "ffmpeg {version}"),
+    ("current-releases", "les versions actuelles de ffmpeg"),
+    ("experimental-encoder", "L'encodeur '{encoder}' est expérimental dans {release} ; ffmpeg le refuse sans '-strict experimental'"),
+    ("span-experimental-encoder", "encodeur expérimental"),
+    ("fix-allow-experimental", "autoriser les encodeurs expérimentaux"),
+    ("fix-use", "utiliser {replacement}"),
+    ("use-instead", " ; utilisez {replacement}"),
+    ("added-in", "ajouté dans ffmpeg {version}"),
+    ("removed-in", "retiré dans ffmpeg {version}{instead}"),
+    ("not-available", "non disponible"),
+    ("unavailable-in-version", "{what} '{name}' n'est pas disponible dans ffmpeg {version} : {reason}"),
+    ("span-not-in-version", "absent de ffmpeg {version}"),
+    ("and-removed-in", " et retiré dans {version}"),
+    ("deprecated", "{what} '{name}' est obsolète depuis ffmpeg {version}{removal}{instead}"),
+    ("span-deprecated", "obsolète"),
+    ("unused-suppression", "Suppression inutilisée de {codes} : aucun diagnostic correspondant"),
+    ("span-unused-suppression", "suppression inutilisée"),
+    ("fix-remove-pragma", "retirer le pragma"),
+    ("parse-failed", "Impossible d'analyser la commande FFmpeg : {error}"),
+    ("span-parse-error", "erreur d'analyse ici"),
+    ("action-remove", "Retirer `{text}`"),
+    ("action-insert", "Insérer `{text}`"),
+    ("action-replace", "Remplacer `{text}` par `{replacement}`"),
+    ("copy-description", "Copier les flux sans réencodage"),
+    ("hover-copy-details", "Les paquets passent sans décodage ni encodage, les filtres et les options d'encodeur ne s'appliquent donc pas"),
+    ("codec-role-encoder", "encodeur {stream}"),
+    ("codec-role-decoder", "décodeur {stream}"),
+    ("codec-role-both", "encodeur et décodeur {stream}"),
+    ("codec-role-codec", "codec {stream}"),
+    ("codec-role-for", "{role} pour {codec}"),
+    ("filter-output", "Sortie du filtre {filter} (flux {stream})"),
+    ("specifier-all-streams", "Tous les flux de {path}"),
+    ("specifier-type-streams", "Flux {stream} de {path}"),
+    ("specifier-stream", "Flux {stream} {index} de {path}"),
+    ("hover-option-context", "**Contexte :** {context}"),
+    ("hover-global-command", "option globale, s'applique à toute la commande"),
+    ("hover-global", "option globale"),
+    ("hover-input-option", "option de l'entrée {index} (`{path}`)"),
+    ("hover-output-option", "option de la sortie `{path}`"),
+    ("hover-option-streams", "flux {stream}"),
+    ("hover-accepted-values", "**Valeurs acceptées :** {values}"),
+    ("hover-up-to-hz", "jusqu'à {rate} Hz"),
+    ("hover-range", "{min} à {max}"),
+    ("hover-storable-encoders", "{encoders}, `{copy}` (encodeurs que {format} peut stocker)"),
+    ("hover-other-encoders", "**Autres encodeurs de {codec} :** {names}"),
+    ("hover-other-decoders", "**Autres décodeurs de {codec} :** {names}"),
+    ("hover-hardware", "**Matériel :** {family} ({platform})"),
+    ("hover-experimental", "**Expérimental :** nécessite `-strict experimental`"),
+    ("hover-containers", "### Conteneurs compatibles\n{formats}"),
+    ("hover-source-filter", "aucune (source)"),
+    ("hover-pad-count", "{count} {stream}"),
+    ("hover-any-pads", "{stream}, nombre quelconque"),
+    ("hover-filter-pads", "**Entrées :** {inputs} · **Sortie :** {output}"),
+    ("hover-filter-parameters", "### Paramètres\nÀ donner par nom ou dans cet ordre :\n\n| Paramètre | Valeur | Description |\n|---|---|---|"),
+    ("hover-unknown-label", "Aucun filtre ne produit ce label"),
+    ("hover-label-streams", "{specifier} : {streams}"),
+    ("hover-no-known-streams", "aucun flux connu"),
+    ("hover-input-title", "## Entrée {index} : `{path}`"),
+    ("hover-duration", "**Durée :** {seconds} s"),
+    ("hover-streams-unknown", "Types de flux inconnus : l'entrée n'a pas d'informations média, et ni son extension ni son format ne les indiquent"),
+    ("hover-streams-probed", "Flux issus des informations média :"),
+    ("hover-streams-guessed", "Flux déduits du nom de fichier :"),
+    ("hover-stream-table", "| Flux | Type | Codec | Détails |\n|---|---|---|---|"),
+    ("hover-unknown-codec", "inconnu"),
+    ("hover-fps", "{fps} i/s"),
+    ("hover-channels", "{channels} canaux"),
+    ("filter-graph-link", "Lien du graphe de filtres"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_render() {
+        let message = Message::new("choices").arg("first", "'a', 'b'").arg("last", "'c'");
+        assert_eq!(message.render(Locale::English), "'a', 'b' or 'c'");
+        assert_eq!(message.render(Locale::German), "'a', 'b' oder 'c'");
+        assert_eq!(Message::new("no-such-message").render(Locale::French), "no-such-message");
+        
+        let choices = |names: &[&str]| Arg::Choices(names.iter().map(|n| n.to_string()).collect());
+        assert_eq!(render_arg(&choices(&["scale"]), Locale::English), "'scale'");
+        assert_eq!(render_arg(&choices(&["a", "b", "c"]), Locale::French), "'a', 'b' ou 'c'");
+        assert_eq!(render_arg(&Arg::Number(2.5), Locale::German), "2,5");
+        assert_eq!(render_arg(&Arg::Fixed(0.0123, 3), Locale::English), "0.012");
+    }
+    
+    /// Sources of the modules that render messages
+    const SOURCES: &[&str] = &[
+        include_str!("analyzer.rs"),
+        include_str!("rich_content.rs"),
+        include_str!("stream_tracker.rs"),
+        include_str!("suggest.rs"),
+        include_str!("option_rules.rs"),
+        include_str!("hwaccel.rs"),
+        include_str!("versions.rs"),
+        include_str!("suppressions.rs"),
+        include_str!("code_actions.rs"),
+        include_str!("handler.rs"),
+        include_str!("hover.rs"),
+        include_str!("completion.rs"),
+    ];
+    
+    /// IDs passed to `Message::new` or `Locale::text` outside the tests of a source file
+    fn rendered_ids(source: &str) -> Vec<&str> {
+        let code = source.split("#[cfg(test)]").next().unwrap_or(source);
+        ["Message::new(\"", ".text(\""]
+            .iter()
+            .flat_map(|call| code.split(call).skip(1))
+            .filter_map(|rest| rest.split('"').next())
+            .collect()
+    }
+    
+    #[test]
+    fn test_rendered_ids_are_in_catalog() {
+        for id in SOURCES.iter().flat_map(|source| rendered_ids(source)) {
+            assert!(template(Locale::English, id).is_some(), "{} is rendered but has no English template", id);
+        }
+        for conflict in crate::option_rules::OPTION_CONFLICTS {
+            assert!(template(Locale::English, conflict.reason).is_some(), "{} has no English template", conflict.reason);
+        }
+        for (idx, (id, _)) in EN.iter().enumerate() {
+            assert!(!EN[..idx].iter().any(|(other, _)| other == id), "{} is in the catalog twice", id);
+        }
+    }
+    
+    #[test]
+    fn test_translations_match_english() {
+        for locale in Locale::ALL {
+            for (id, translated) in locale.catalog() {
+                let english = template(Locale::English, id).unwrap_or_else(|| panic!("{} is not an English message", id));
+                let mut expected = placeholders(english);
+                let mut found = placeholders(translated);
+                expected.sort_unstable();
+                found.sort_unstable();
+                assert_eq!(found, expected, "{:?} template of {} has other placeholders", locale, id);
+            }
+        }
+    }
+    
+    #[test]
+    fn test_localized_analysis() {
+        use crate::analyzer::AnalysisOptions;
+        use crate::handler::analyze_content;
+        
+        let options = AnalysisOptions { locale: Locale::German, ..AnalysisOptions::default() };
+        let messages = analyze_content("ffmpeg -i input.mp4 -c:v libx246 output.mp4", 1, 0, options).messages;
+        let unknown = messages.iter().find(|m| m.code == "W201").unwrap();
+        assert_eq!(unknown.message, "Unbekannter Codec: 'libx246'. Meinten Sie 'libx264'?");
+        assert_eq!(unknown.spans[1].message, "meinten Sie libx264?");
+    }
+    
+    #[test]
+    fn test_locale_tags() {
+        assert_eq!(Locale::from_tag("de-AT"), Some(Locale::German));
+        assert_eq!(Locale::from_tag("FR_ca"), Some(Locale::French));
+        assert_eq!(Locale::from_tag("en"), Some(Locale::English));
+        assert_eq!(Locale::from_tag("ja"), None);
+    }
+}
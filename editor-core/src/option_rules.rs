@@ -0,0 +1,415 @@
+use crate::ast::{FfmpegCommand, OptionNode, GLOBAL_OPTIONS};
+use crate::codec_db::KNOWN_ENCODER_OPTIONS;
+use crate::messages::{Locale, Message};
+use crate::suggest::{did_you_mean, option_suggestions};
+use shared_types::{DiagnosticKind, DiagnosticMessage, DiagnosticSpan, Severity, SourceCodeSpan, SpanRole};
+
+/// A pair of options that conflict when given together in the same scope
+/// (the global options, or the options of one input/output)
+pub struct OptionConflict {
+    pub first: &'static str,
+    pub second: &'static str,
+    pub code: &'static str,
+    pub severity: Severity,
+    /// Message ID of the reason the options conflict
+    pub reason: &'static str,
+    /// Only applies when the output's video encoder is one of these
+    pub encoders: Option<&'static [&'static str]>,
+}
+
+/// Conflicting option pairs
+pub static OPTION_CONFLICTS: &[OptionConflict] = &[
+    OptionConflict {
+        first: "-y",
+        second: "-n",
+        code: "E406",
+        severity: Severity::Error,
+        reason: "conflict-overwrite-flags",
+        encoders: None,
+    },
+    OptionConflict {
+        first: "-vn",
+        second: "-c:v",
+        code: "W403",
+        severity: Severity::Warning,
+        reason: "conflict-vn-video-codec",
+        encoders: None,
+    },
+    OptionConflict {
+        first: "-an",
+        second: "-c:a",
+        code: "W403",
+        severity: Severity::Warning,
+        reason: "conflict-an-audio-codec",
+        encoders: None,
+    },
+    OptionConflict {
+        first: "-t",
+        second: "-to",
+        code: "W410",
+        severity: Severity::Warning,
+        reason: "conflict-duration-end",
+        encoders: None,
+    },
+    OptionConflict {
+        first: "-b:v",
+        second: "-crf",
+        code: "W403",
+        severity: Severity::Warning,
+        reason: "conflict-crf-bitrate",
+        encoders: Some(&["libx264", "libx265"]),
+    },
+];
+
+/// Per-file options, without stream specifiers; with `GLOBAL_OPTIONS` and the encoder options,
+/// the names misspelled options are corrected to
+pub static FILE_OPTIONS: &[&str] = &[
+    "-i", "-f", "-c", "-codec", "-vcodec", "-acodec", "-scodec", "-dcodec", "-b", "-vb", "-ab", "-s", "-r",
+    "-rate", "-framerate", "-aspect", "-pix_fmt", "-ar", "-ac", "-sample_fmt", "-channel_layout", "-ss", "-sseof",
+    "-to", "-t", "-fs", "-map", "-map_metadata", "-map_chapters", "-metadata", "-disposition", "-vf", "-af",
+    "-filter", "-filter_script", "-filter_complex", "-an", "-vn", "-sn", "-dn", "-shortest", "-re", "-copyts",
+    "-start_at_zero", "-itsoffset", "-itsscale", "-stream_loop", "-loop", "-start_number", "-frames", "-vframes",
+    "-aframes", "-g", "-keyint_min", "-bf", "-refs", "-maxrate", "-minrate", "-bufsize", "-qscale", "-q",
+    "-qmin", "-qmax", "-threads", "-movflags", "-fflags", "-avoid_negative_ts", "-max_muxing_queue_size",
+    "-fps_mode", "-strict", "-tag", "-vtag", "-atag", "-hwaccel", "-hwaccel_device", "-hwaccel_output_format",
+    "-vaapi_device", "-timestamp", "-pass", "-passlogfile", "-x264-params", "-x265-params", "-x264opts",
+    "-force_key_frames", "-sc_threshold", "-row-mt", "-tile-columns", "-lag-in-frames", "-auto-alt-ref",
+    "-hls_time", "-hls_list_size", "-hls_playlist_type", "-hls_segment_filename", "-segment_time",
+    "-segment_format", "-pattern_type", "-reset_timestamps", "-seek_timestamp", "-accurate_seek", "-noaccurate_seek",
+    "-thread_queue_size", "-analyzeduration", "-probesize", "-attach", "-dump_attachment", "-map_channel",
+];
+
+/// Per-file options ffmpeg only accepts before an input
+pub static INPUT_ONLY_OPTIONS: &[&str] = &[
+    "-i", "-re", "-sseof", "-itsoffset", "-itsscale", "-stream_loop", "-hwaccel", "-hwaccel_device",
+    "-hwaccel_output_format", "-pattern_type", "-seek_timestamp", "-accurate_seek", "-noaccurate_seek",
+    "-thread_queue_size", "-analyzeduration", "-probesize", "-dump_attachment",
+];
+
+/// Per-file options ffmpeg only accepts before an output
+pub static OUTPUT_ONLY_OPTIONS: &[&str] = &[
+    "-fs", "-map", "-map_metadata", "-map_chapters", "-metadata", "-disposition", "-vf", "-af", "-filter",
+    "-filter_script", "-shortest", "-frames", "-vframes", "-aframes", "-g", "-keyint_min", "-bf", "-refs",
+    "-maxrate", "-minrate", "-bufsize", "-qscale", "-q", "-qmin", "-qmax", "-movflags", "-avoid_negative_ts",
+    "-max_muxing_queue_size", "-fps_mode", "-tag", "-vtag", "-atag", "-timestamp", "-pass", "-passlogfile",
+    "-x264-params", "-x265-params", "-x264opts", "-force_key_frames", "-sc_threshold", "-row-mt",
+    "-tile-columns", "-lag-in-frames", "-auto-alt-ref", "-hls_time", "-hls_list_size", "-hls_playlist_type",
+    "-hls_segment_filename", "-segment_time", "-segment_format", "-reset_timestamps", "-attach", "-map_channel",
+];
+
+/// What common options do, by name without stream specifier. Kept in English next to the
+/// imported filter descriptions, which come from ffmpeg's English `-filters` listing
+pub static OPTION_DESCRIPTIONS: &[(&str, &str)] = &[
+    // Global options
+    ("-y", "Overwrite output files without asking"),
+    ("-n", "Never overwrite output files; exit if one exists"),
+    ("-hide_banner", "Hide the copyright notice, build options and library versions"),
+    ("-loglevel", "Logging level: quiet, panic, fatal, error, warning, info, verbose, debug or trace"),
+    ("-v", "Logging level (same as `-loglevel`)"),
+    ("-stats", "Print encoding progress"),
+    ("-nostats", "Do not print encoding progress"),
+    ("-nostdin", "Do not read commands from standard input; needed in background jobs"),
+    ("-progress", "Write machine-readable progress to a URL or file"),
+    ("-init_hw_device", "Create a hardware device (`type=name:device`) for filters and encoders"),
+    ("-filter_hw_device", "Hardware device that `hwupload` and hardware filters use"),
+    ("-filter_complex", "Filter graph with any number of inputs and outputs; label its outputs and select them with `-map`"),
+    ("-lavfi", "Complex filter graph (same as `-filter_complex`)"),
+    ("-vsync", "Frame rate mode; deprecated in favor of `-fps_mode`"),
+    // Input and output options
+    ("-i", "Input file, URL, device or lavfi graph"),
+    ("-f", "Container format; on an input it skips probing, on an output it overrides the file extension"),
+    ("-c", "Codec: the encoder on an output, the decoder before `-i`; `copy` passes packets through without re-encoding"),
+    ("-codec", "Codec: the encoder on an output, the decoder before `-i`; `copy` passes packets through without re-encoding"),
+    ("-vcodec", "Video codec (same as `-c:v`)"),
+    ("-acodec", "Audio codec (same as `-c:a`)"),
+    ("-scodec", "Subtitle codec (same as `-c:s`)"),
+    ("-b", "Target bitrate, e.g. `5M` or `128k`"),
+    ("-vb", "Video bitrate; deprecated in favor of `-b:v`"),
+    ("-ab", "Audio bitrate; deprecated in favor of `-b:a`"),
+    ("-s", "Frame size as WIDTHxHEIGHT or a name such as `hd720`"),
+    ("-r", "Frame rate; on an input it overrides the timestamps, on an output it drops or duplicates frames"),
+    ("-framerate", "Frame rate of image sequences and devices"),
+    ("-aspect", "Display aspect ratio, e.g. `16:9`"),
+    ("-pix_fmt", "Pixel format of the encoded video, e.g. `yuv420p`"),
+    ("-vf", "Video filter chain for the output's video stream"),
+    ("-af", "Audio filter chain for the output's audio stream"),
+    ("-filter", "Filter chain for the selected streams"),
+    ("-map", "Select an input stream (`0:v:0`) or a labelled filter output (`[out]`) for the output"),
+    ("-map_metadata", "Copy global metadata from an input; -1 drops it"),
+    ("-metadata", "Set a metadata tag (`key=value`)"),
+    ("-ss", "Start time; before `-i` it seeks the input, after it drops decoded frames until the time"),
+    ("-t", "Duration to read or write"),
+    ("-to", "End time"),
+    ("-sseof", "Start time relative to the end of the input (negative)"),
+    ("-fs", "Stop writing when the file reaches this size in bytes"),
+    ("-itsoffset", "Shift the timestamps of an input"),
+    ("-stream_loop", "Number of times to loop an input; -1 loops forever"),
+    ("-loop", "Loop an image input (1) or an animated output"),
+    ("-re", "Read the input at its native frame rate, for live streaming"),
+    ("-copyts", "Keep the input timestamps instead of starting at zero"),
+    ("-shortest", "End the output with its shortest stream"),
+    ("-frames", "Number of frames to write"),
+    ("-vframes", "Number of video frames to write (same as `-frames:v`)"),
+    ("-an", "Drop audio streams"),
+    ("-vn", "Drop video streams"),
+    ("-sn", "Drop subtitle streams"),
+    ("-dn", "Drop data streams"),
+    ("-ar", "Audio sample rate in Hz"),
+    ("-ac", "Number of audio channels"),
+    ("-sample_fmt", "Audio sample format, e.g. `s16` or `fltp`"),
+    ("-channel_layout", "Audio channel layout, e.g. `stereo` or `5.1`"),
+    ("-g", "Largest number of frames between keyframes"),
+    ("-keyint_min", "Smallest number of frames between keyframes"),
+    ("-bf", "Largest number of consecutive B-frames"),
+    ("-maxrate", "Peak bitrate; needs `-bufsize`"),
+    ("-minrate", "Lowest bitrate"),
+    ("-bufsize", "Rate control buffer size, over which `-maxrate` is enforced"),
+    ("-q", "Fixed quality scale; lower is better"),
+    ("-qscale", "Fixed quality scale; lower is better"),
+    ("-threads", "Number of encoding or decoding threads; 0 picks automatically"),
+    ("-movflags", "MP4/MOV muxer flags, e.g. `+faststart` for web playback"),
+    ("-fps_mode", "Frame rate mode: passthrough, cfr, vfr or auto"),
+    ("-strict", "Standards compliance; `experimental` allows experimental encoders"),
+    ("-tag", "Codec tag (FourCC) written to the container, e.g. `hvc1`"),
+    ("-hwaccel", "Decode the input with a hardware API, e.g. `cuda`, `vaapi` or `qsv`"),
+    ("-hwaccel_device", "Device used for hardware decoding"),
+    ("-hwaccel_output_format", "Keep hardware-decoded frames in GPU memory in this format"),
+    ("-pattern_type", "How an image sequence path is matched: sequence or glob"),
+    ("-start_number", "First number of an image sequence"),
+    ("-force_key_frames", "Times or an expression at which to place keyframes"),
+    ("-pass", "Pass number of a two-pass encode"),
+    ("-disposition", "Stream disposition flags, e.g. `default` or `forced`"),
+    ("-x264-params", "libx264 options as `key=value:key=value`"),
+    ("-x265-params", "libx265 options as `key=value:key=value`"),
+    // Encoder private options
+    ("-crf", "Constant rate factor: quality-based rate control, lower is better"),
+    ("-preset", "Encoding speed preset; slower presets compress better"),
+    ("-tune", "Tune the encoder for a kind of content, e.g. `film` or `animation`"),
+    ("-profile", "Codec profile, e.g. `high` or `main10`"),
+    ("-level", "Codec level, e.g. `4.1`"),
+    ("-qp", "Constant quantizer; 0 is lossless for x264"),
+    ("-cq", "Constant quality level of NVENC encoders"),
+    ("-deadline", "libvpx speed/quality trade-off: best, good or realtime"),
+    ("-cpu-used", "libvpx/libaom speed; higher is faster"),
+];
+
+/// Description of an option; stream specifiers are ignored (`-c:v` is described as `-c`)
+pub fn option_description(name: &str) -> Option<&'static str> {
+    let base = name.split(':').next().unwrap_or(name);
+    OPTION_DESCRIPTIONS.iter().find(|(option, _)| *option == base).map(|(_, description)| *description)
+}
+
+/// Report options that are not in the option table but close to an option that is (W415).
+/// ffmpeg has far more options than the table, so unknown options without a near match and
+/// names of fewer than four letters are left alone
+pub fn check_unknown_options(command: &FfmpegCommand, locale: Locale) -> Vec<DiagnosticMessage> {
+    let known: Vec<&str> = GLOBAL_OPTIONS.iter().chain(FILE_OPTIONS).copied().collect();
+    command
+        .global_options
+        .iter()
+        .chain(command.inputs.iter().flat_map(|i| i.options.iter()))
+        .chain(command.outputs.iter().flat_map(|o| o.options.iter()))
+        .filter_map(|option| {
+            let OptionNode::Generic { name, span, .. } = option else { return None };
+            let base = name.split(':').next().unwrap_or(name);
+            let letters = base.trim_start_matches('-');
+            if letters.len() < 4 || known.contains(&base) || KNOWN_ENCODER_OPTIONS.contains(&letters) {
+                return None;
+            }
+            let suggestions = option_suggestions(base, &known);
+            if suggestions.is_empty() {
+                return None;
+            }
+            
+            let name_span = SourceCodeSpan { end_line: span.start_line, end_column: span.start_column + base.len(), ..span.clone() };
+            let (mut spans, hint) = did_you_mean(&suggestions, &name_span, locale);
+            spans.insert(0, DiagnosticSpan { span: name_span, role: SpanRole::Target, message: locale.text("span-unknown-option") });
+            Some(DiagnosticMessage {
+                code: "W415".to_string(),
+                severity: Severity::Warning,
+                kind: DiagnosticKind::UnknownOption { option: name.clone() },
+                message: Message::new("unknown-option").arg("option", base).arg("hint", hint).render(locale),
+                spans,
+                rich: None,
+            })
+        })
+        .collect()
+}
+
+/// Check every scope of the command for conflicting and redundant options
+pub fn check_option_conflicts(command: &FfmpegCommand, locale: Locale) -> Vec<DiagnosticMessage> {
+    let mut diagnostics = Vec::new();
+    
+    // Global options apply to the whole command wherever they are written
+    let global_scope: Vec<&OptionNode> = command
+        .global_options
+        .iter()
+        .chain(command.inputs.iter().flat_map(|i| i.options.iter()))
+        .chain(command.outputs.iter().flat_map(|o| o.options.iter()))
+        .filter(|o| o.is_global())
+        .collect();
+    diagnostics.extend(check_scope(&global_scope, None, locale));
+    
+    for input in &command.inputs {
+        let scope: Vec<&OptionNode> = input.options.iter().filter(|o| !o.is_global()).collect();
+        diagnostics.extend(check_scope(&scope, None, locale));
+    }
+    
+    for output in &command.outputs {
+        let scope: Vec<&OptionNode> = output.options.iter().filter(|o| !o.is_global()).collect();
+        let video_encoder = scope.iter().find_map(|o| match o {
+            OptionNode::VideoCodec { codec, .. } | OptionNode::Codec { codec, .. } => Some(codec.as_str()),
+            _ => None,
+        });
+        diagnostics.extend(check_scope(&scope, video_encoder, locale));
+    }
+    
+    diagnostics
+}
+
+fn check_scope(scope: &[&OptionNode], video_encoder: Option<&str>, locale: Locale) -> Vec<DiagnosticMessage> {
+    let mut diagnostics = Vec::new();
+    let find = |name: &str| scope.iter().position(|o| o.name() == name);
+    
+    for rule in OPTION_CONFLICTS {
+        if let Some(encoders) = rule.encoders {
+            if !video_encoder.is_some_and(|e| encoders.contains(&e)) {
+                continue;
+            }
+        }
+        let (Some(first_idx), Some(second_idx)) = (find(rule.first), find(rule.second)) else {
+            continue;
+        };
+        // The option written later is the target; the earlier one is the reference
+        let (earlier, later) = if first_idx < second_idx {
+            (scope[first_idx], scope[second_idx])
+        } else {
+            (scope[second_idx], scope[first_idx])
+        };
+        
+        diagnostics.push(DiagnosticMessage {
+            code: rule.code.to_string(),
+            severity: rule.severity.clone(),
+            kind: DiagnosticKind::MutuallyExclusiveOptions {
+                option1: rule.first.to_string(),
+                option2: rule.second.to_string(),
+            },
+            message: Message::new("option-conflict")
+                .arg("second", rule.second)
+                .arg("first", rule.first)
+                .arg("reason", Message::new(rule.reason))
+                .render(locale),
+            spans: vec![
+                DiagnosticSpan {
+                    span: later.span().clone(),
+                    role: SpanRole::Target,
+                    message: Message::new("span-conflicts-with").arg("option", earlier.name()).render(locale),
+                },
+                DiagnosticSpan { span: earlier.span().clone(), role: SpanRole::Reference, message: locale.text("span-first-set-here") },
+                DiagnosticSpan {
+                    span: later.span().clone(),
+                    role: SpanRole::Suggestion { replacement: String::new() },
+                    message: Message::new("fix-remove").arg("option", later.name()).render(locale),
+                },
+            ],
+            rich: None,
+        });
+    }
+    
+    // Value-less flags given twice are redundant
+    for (idx, option) in scope.iter().enumerate() {
+        let OptionNode::Generic { name, value: None, .. } = option else { continue };
+        if let Some(earlier) = scope[..idx].iter().find(|o| o.name() == name) {
+            diagnostics.push(DiagnosticMessage {
+                code: "W404".to_string(),
+                severity: Severity::Hint,
+                kind: DiagnosticKind::MutuallyExclusiveOptions {
+                    option1: name.clone(),
+                    option2: name.clone(),
+                },
+                message: Message::new("option-repeated").arg("option", name).render(locale),
+                spans: vec![
+                    DiagnosticSpan { span: option.span().clone(), role: SpanRole::Target, message: locale.text("span-redundant") },
+                    DiagnosticSpan { span: earlier.span().clone(), role: SpanRole::Reference, message: locale.text("span-first-set-here") },
+                    DiagnosticSpan {
+                        span: option.span().clone(),
+                        role: SpanRole::Suggestion { replacement: String::new() },
+                        message: locale.text("fix-remove-it"),
+                    },
+                ],
+                rich: None,
+            });
+        }
+    }
+    
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_command;
+    
+    fn codes(input: &str) -> Vec<String> {
+        let cmd = parse_command(input, 0, 0).unwrap();
+        check_option_conflicts(&cmd, Locale::English).into_iter().map(|d| d.code).collect()
+    }
+    
+    #[test]
+    fn test_overwrite_flags_conflict() {
+        assert_eq!(codes("ffmpeg -y -n -i input.mp4 output.mp4"), vec!["E406"]);
+        // -y after the input is still a global option
+        assert_eq!(codes("ffmpeg -n -i input.mp4 -y output.mp4"), vec!["E406"]);
+    }
+    
+    #[test]
+    fn test_per_file_conflicts() {
+        assert_eq!(codes("ffmpeg -i input.mp4 -vn -c:v libx264 output.mp4"), vec!["W403"]);
+        assert_eq!(codes("ffmpeg -i input.mp4 -t 5 -to 10 output.mp4"), vec!["W410"]);
+        // -vn on one output does not conflict with -c:v on another
+        assert!(codes("ffmpeg -i input.mp4 -vn a.m4a -c:v libx264 b.mp4").is_empty());
+    }
+    
+    #[test]
+    fn test_crf_overrides_bitrate() {
+        assert_eq!(codes("ffmpeg -i input.mp4 -c:v libx264 -b:v 2M -crf 23 output.mp4"), vec!["W403"]);
+        // libvpx-vp9 uses -b:v as the cap in constrained quality mode
+        assert!(codes("ffmpeg -i input.mp4 -c:v libvpx-vp9 -b:v 2M -crf 30 output.webm").is_empty());
+    }
+    
+    #[test]
+    fn test_misspelled_options() {
+        let cmd = parse_command("ffmpeg -i input.mp4 -vcodc libx264 -movflag +faststart output.mp4", 0, 0).unwrap();
+        let diagnostics = check_unknown_options(&cmd, Locale::English);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].message, "Unknown option '-vcodc'. Did you mean '-vcodec'?");
+        assert!(matches!(&diagnostics[1].spans[1].role, SpanRole::Suggestion { replacement } if replacement == "-movflags"));
+        
+        // Options outside the table are only reported when they look like a typo
+        let cmd = parse_command("ffmpeg -i input.mp4 -preset slow -aq 3 -write_tmcd 0 -crf 20 output.mp4", 0, 0).unwrap();
+        assert!(check_unknown_options(&cmd, Locale::English).is_empty());
+    }
+    
+    #[test]
+    fn test_option_descriptions() {
+        for (option, _) in OPTION_DESCRIPTIONS {
+            let known = GLOBAL_OPTIONS.contains(option) || FILE_OPTIONS.contains(option) || KNOWN_ENCODER_OPTIONS.contains(&&option[1..]);
+            assert!(known, "{} is described but not a known option", option);
+        }
+        assert_eq!(option_description("-c:v"), option_description("-c"));
+        assert!(option_description("-nosuchoption").is_none());
+    }
+    
+    #[test]
+    fn test_input_and_output_only_options() {
+        for option in INPUT_ONLY_OPTIONS.iter().chain(OUTPUT_ONLY_OPTIONS) {
+            assert!(FILE_OPTIONS.contains(option), "{} is not a per-file option", option);
+        }
+        assert!(!INPUT_ONLY_OPTIONS.iter().any(|o| OUTPUT_ONLY_OPTIONS.contains(o)));
+    }
+    
+    #[test]
+    fn test_redundant_flag() {
+        assert_eq!(codes("ffmpeg -hide_banner -hide_banner -i input.mp4 output.mp4"), vec!["W404"]);
+    }
+}
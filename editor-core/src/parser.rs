@@ -38,6 +38,23 @@ pub fn parse_command(input: &str, line_offset: usize, column_offset: usize) -> R
         }
     }
     
+    // The grammar collects every option before the first `-i` as global; per-file
+    // options among them (e.g. `-ss 10 -i in.mp4`) belong to the first input
+    let (mut global_options, first_input_options): (Vec<_>, Vec<_>) =
+        global_options.into_iter().partition(OptionNode::is_global);
+    if let Some(first_input) = inputs.first_mut() {
+        first_input.options.splice(0..0, first_input_options);
+    }
+    
+    // Global options apply to the whole command wherever they are written
+    // (e.g. `-filter_complex` or `-y` after the inputs)
+    let file_options = inputs.iter_mut().map(|i| &mut i.options).chain(outputs.iter_mut().map(|o| &mut o.options));
+    for options in file_options {
+        let (global, local): (Vec<_>, Vec<_>) = std::mem::take(options).into_iter().partition(OptionNode::is_global);
+        global_options.extend(global);
+        *options = local;
+    }
+    
     Ok(FfmpegCommand {
         global_options,
         inputs,
@@ -126,7 +143,7 @@ fn parse_option(pair: pest::iterators::Pair<Rule>, line_offset: usize, column_of
     
     match actual_pair.as_rule() {
         Rule::codec_option => {
-            let option_text = actual_pair.as_str().to_string();
+            let mut flag_rule = Rule::codec_flag;
             let mut codec = String::new();
             let mut codec_span = span.clone();
             
@@ -136,33 +153,40 @@ fn parse_option(pair: pest::iterators::Pair<Rule>, line_offset: usize, column_of
                         codec_span = span_from_pest(inner.as_span(), line_offset, column_offset);
                         codec = inner.as_str().to_string();
                     }
-                    _ => {}
+                    rule => flag_rule = rule,
                 }
             }
-            if option_text.contains(":v") || option_text.contains("vcodec") {
-                Some(OptionNode::VideoCodec { codec, codec_span, span })
-            } else if option_text.contains(":a") || option_text.contains("acodec") {
-                Some(OptionNode::AudioCodec { codec, codec_span, span })
-            } else {
-                Some(OptionNode::Codec { codec, codec_span, span })
+            match flag_rule {
+                Rule::video_codec_flag => Some(OptionNode::VideoCodec { codec, codec_span, span }),
+                Rule::audio_codec_flag => Some(OptionNode::AudioCodec { codec, codec_span, span }),
+                Rule::subtitle_codec_flag => Some(OptionNode::SubtitleCodec { codec, codec_span, span }),
+                _ => Some(OptionNode::Codec { codec, codec_span, span }),
             }
         }
         
         Rule::bitrate_option => {
-            let option_text = actual_pair.as_str().to_string();
+            let mut flag_rule = Rule::bitrate_flag;
+            let mut flag = String::new();
             let mut bitrate = String::new();
             let mut bitrate_span = span.clone();
             
             for inner in actual_pair.into_inner() {
-                if inner.as_rule() == Rule::bitrate {
-                    bitrate_span = span_from_pest(inner.as_span(), line_offset, column_offset);
-                    bitrate = inner.as_str().to_string();
+                match inner.as_rule() {
+                    Rule::bitrate => {
+                        bitrate_span = span_from_pest(inner.as_span(), line_offset, column_offset);
+                        bitrate = inner.as_str().to_string();
+                    }
+                    rule => {
+                        flag_rule = rule;
+                        flag = inner.as_str().to_string();
+                    }
                 }
             }
-            if option_text.contains(":v") || option_text.contains("-vb") {
-                Some(OptionNode::VideoBitrate { bitrate, bitrate_span, span })
+            // Plain `-b` is an alias for `-b:v`
+            if flag_rule == Rule::audio_bitrate_flag {
+                Some(OptionNode::AudioBitrate { flag, bitrate, bitrate_span, span })
             } else {
-                Some(OptionNode::AudioBitrate { bitrate, bitrate_span, span })
+                Some(OptionNode::VideoBitrate { flag, bitrate, bitrate_span, span })
             }
         }
         
@@ -238,21 +262,26 @@ fn parse_option(pair: pest::iterators::Pair<Rule>, line_offset: usize, column_of
         }
         
         Rule::time_option => {
-            let option_text = actual_pair.as_str().to_string();
+            let mut flag = String::new();
             let mut time = String::new();
             let mut time_span = span.clone();
             
             for inner in actual_pair.into_inner() {
-                if inner.as_rule() == Rule::time_value {
-                    time_span = span_from_pest(inner.as_span(), line_offset, column_offset);
-                    time = inner.as_str().to_string();
+                match inner.as_rule() {
+                    Rule::time_flag => flag = inner.as_str().to_string(),
+                    Rule::time_value => {
+                        time_span = span_from_pest(inner.as_span(), line_offset, column_offset);
+                        time = inner.as_str().to_string();
+                    }
+                    _ => {}
                 }
             }
             
-            if option_text.starts_with("-ss") {
-                Some(OptionNode::SeekStart { time, time_span, span })
-            } else {
-                Some(OptionNode::Duration { time, time_span, span })
+            match flag.as_str() {
+                "-ss" => Some(OptionNode::SeekStart { time, time_span, span }),
+                "-sseof" => Some(OptionNode::SeekFromEnd { time, time_span, span }),
+                "-to" => Some(OptionNode::SeekEnd { time, time_span, span }),
+                _ => Some(OptionNode::Duration { time, time_span, span }),
             }
         }
         
@@ -275,6 +304,29 @@ fn parse_option(pair: pest::iterators::Pair<Rule>, line_offset: usize, column_of
             }
         }
         
+        Rule::pix_fmt_option => {
+            let mut format = String::new();
+            let mut format_span = span.clone();
+            
+            for inner in actual_pair.into_inner() {
+                if inner.as_rule() == Rule::pix_fmt_name {
+                    format_span = span_from_pest(inner.as_span(), line_offset, column_offset);
+                    format = inner.as_str().to_string();
+                }
+            }
+            
+            Some(OptionNode::PixelFormat { format, format_span, span })
+        }
+        
+        Rule::bool_flag => {
+            Some(OptionNode::Generic {
+                name: actual_pair.as_str().to_string(),
+                value: None,
+                value_span: None,
+                span,
+            })
+        }
+        
         Rule::flag => {
             let pair_str = actual_pair.as_str().to_string();
             let mut name = String::new();
@@ -308,19 +360,101 @@ fn parse_option(pair: pest::iterators::Pair<Rule>, line_offset: usize, column_of
 fn parse_filter_spec(pair: pest::iterators::Pair<Rule>, line_offset: usize, column_offset: usize) -> FilterSpec {
     let span = span_from_pest(pair.as_span(), line_offset, column_offset);
     let mut raw = String::new();
+    let mut raw_span = span.clone();
+    
+    for inner in pair.into_inner() {
+        if inner.as_rule() == Rule::filter_graph {
+            raw_span = span_from_pest(inner.as_span(), line_offset, column_offset);
+            if inner.as_str().starts_with(['"', '\'']) {
+                raw_span.start_column += 1;
+            }
+            raw = extract_string_value(inner);
+        }
+    }
+    
+    let parsed = parse_filter_graph(&raw, raw_span.start_line, raw_span.start_column);
+    
+    FilterSpec {
+        raw,
+        parsed,
+        span,
+    }
+}
+
+/// Parse filter graph text into chains and filters. `line` and `column` locate the
+/// start of the text in the document. Returns None if the graph is not well-formed.
+pub fn parse_filter_graph(text: &str, line: usize, column: usize) -> Option<FilterGraph> {
+    let graph_pair = FfmpegParser::parse(Rule::filter_graph_text, text).ok()?.next()?;
+    let mut chains = Vec::new();
+    
+    for chain_pair in graph_pair.into_inner() {
+        if chain_pair.as_rule() != Rule::filter_chain {
+            continue;
+        }
+        let filters = chain_pair
+            .into_inner()
+            .map(|filter_pair| parse_filter(filter_pair, line, column))
+            .collect();
+        chains.push(FilterChain { filters });
+    }
+    
+    Some(FilterGraph { chains })
+}
+
+fn parse_filter(pair: pest::iterators::Pair<Rule>, line: usize, column: usize) -> Filter {
+    let span = span_from_pest(pair.as_span(), line, column);
+    let mut name = String::new();
+    let mut name_span = span.clone();
+    let mut params = Vec::new();
+    let mut input_labels = Vec::new();
+    let mut output_labels = Vec::new();
+    let mut input_label_spans = Vec::new();
+    let mut output_label_spans = Vec::new();
     
     for inner in pair.into_inner() {
         match inner.as_rule() {
-            Rule::filter_graph | Rule::unquoted_filter => {
-                raw = extract_string_value(inner);
+            Rule::link_label => {
+                let label = inner.as_str().trim_start_matches('[').trim_end_matches(']').to_string();
+                let label_span = span_from_pest(inner.as_span(), line, column);
+                if name.is_empty() {
+                    input_labels.push(label);
+                    input_label_spans.push(label_span);
+                } else {
+                    output_labels.push(label);
+                    output_label_spans.push(label_span);
+                }
+            }
+            Rule::filter_name => {
+                name_span = span_from_pest(inner.as_span(), line, column);
+                name = inner.as_str().to_string();
+            }
+            Rule::filter_params => {
+                for param_pair in inner.into_inner() {
+                    let param_span = span_from_pest(param_pair.as_span(), line, column);
+                    let mut key = None;
+                    let mut value = String::new();
+                    for part in param_pair.into_inner() {
+                        match part.as_rule() {
+                            Rule::param_key => key = Some(part.as_str().to_string()),
+                            Rule::param_value => value = extract_string_value(part),
+                            _ => {}
+                        }
+                    }
+                    params.push(FilterParam { key, value, span: param_span });
+                }
             }
             _ => {}
         }
     }
     
-    FilterSpec {
-        raw: raw.clone(),
-        parsed: None, // TODO: Implement filter graph parsing
+    Filter {
+        name,
+        name_span,
+        params,
+        input_labels,
+        output_labels,
+        input_label_spans,
+        output_label_spans,
         span,
     }
 }
@@ -368,5 +502,76 @@ mod tests {
         let result = parse_command(input, 0, 0);
         assert!(result.is_ok());
     }
+    
+    #[test]
+    fn test_parse_option_keyword_boundaries() {
+        // `-crf` must not be read as `-c rf`, and `-shortest` must not swallow the output path
+        let input = "ffmpeg -i input.mp4 -c:v libx264 -crf 23 -cpu-used -4 -shortest output.mp4";
+        let cmd = parse_command(input, 0, 0).unwrap();
+        assert_eq!(cmd.outputs.len(), 1);
+        assert_eq!(cmd.outputs[0].file_path, "output.mp4");
+        let names: Vec<_> = cmd.outputs[0].options.iter().filter_map(|o| match o {
+            OptionNode::Generic { name, value, .. } => Some((name.as_str(), value.clone())),
+            _ => None,
+        }).collect();
+        assert_eq!(names, vec![
+            ("-crf", Some("23".to_string())),
+            ("-cpu-used", Some("-4".to_string())),
+            ("-shortest", None),
+        ]);
+    }
+    
+    #[test]
+    fn test_parse_filter_graph() {
+        let input = "ffmpeg -i input.mp4 -vf \"scale=w=1280:h=-2,format=yuv420p\" output.mp4";
+        let cmd = parse_command(input, 1, 0).unwrap();
+        let OptionNode::VideoFilter { filter, .. } = &cmd.outputs[0].options[0] else {
+            panic!("expected video filter");
+        };
+        let filters: Vec<_> = filter.filters().collect();
+        assert_eq!(filters.len(), 2);
+        assert_eq!(filters[0].name, "scale");
+        assert_eq!(filters[0].param("h", 1).unwrap().value, "-2");
+        assert_eq!(filters[1].param("pix_fmts", 0).unwrap().value, "yuv420p");
+        // Spans point into the original document, past the opening quote
+        assert_eq!(filters[0].name_span.start_column, 25);
+        assert_eq!(filters[1].name_span.start_column, 43);
+    }
+    
+    #[test]
+    fn test_parse_filter_graph_labels() {
+        let graph = parse_filter_graph("[0:v][1:v]overlay=10:10[out];[out]scale=640:360", 1, 0).unwrap();
+        assert_eq!(graph.chains.len(), 2);
+        let overlay = &graph.chains[0].filters[0];
+        assert_eq!(overlay.input_labels, vec!["0:v", "1:v"]);
+        assert_eq!(overlay.output_labels, vec!["out"]);
+        assert_eq!(overlay.params.len(), 2);
+        assert_eq!((overlay.input_label_spans[1].start_column, overlay.input_label_spans[1].end_column), (5, 10));
+        assert_eq!(overlay.output_label_spans[0].start_column, 23);
+    }
+    
+    #[test]
+    fn test_parse_time_options() {
+        let input = "ffmpeg -y -ss 00:01:30.5 -i input.mp4 -t -5 -to 90s -sseof -10 output.mp4";
+        let cmd = parse_command(input, 0, 0).unwrap();
+        assert_eq!(cmd.global_options.len(), 1);
+        assert!(matches!(&cmd.inputs[0].options[..], [OptionNode::SeekStart { time, .. }] if time == "00:01:30.5"));
+        assert!(matches!(&cmd.outputs[0].options[..], [
+            OptionNode::Duration { .. },
+            OptionNode::SeekEnd { .. },
+            OptionNode::SeekFromEnd { .. },
+        ]));
+    }
+    
+    #[test]
+    fn test_global_options_after_inputs() {
+        let input = "ffmpeg -i a.mp4 -i logo.png -n -filter_complex \"[0:v][1:v]overlay\" -c:v libx264 output.mp4";
+        let cmd = parse_command(input, 0, 0).unwrap();
+        assert!(matches!(&cmd.global_options[..], [
+            OptionNode::Generic { name, .. },
+            OptionNode::FilterComplex { .. },
+        ] if name == "-n"));
+        assert!(matches!(&cmd.outputs[0].options[..], [OptionNode::VideoCodec { .. }]));
+        assert!(cmd.inputs.iter().all(|input| input.options.is_empty()));
+    }
 }
-
@@ -0,0 +1,121 @@
+//! File path helpers for comparing inputs and outputs and classifying image sequences
+
+/// Still image extensions handled by the image2 demuxer/muxer
+const IMAGE_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "bmp", "tif", "tiff", "webp", "jxl", "dpx", "exr", "tga", "ppm", "pgm", "j2k",
+];
+
+/// What a path on the command line refers to
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathKind {
+    /// A regular media file
+    File,
+    /// A single still image
+    Image,
+    /// A printf-style numbered sequence (`img%04d.png`)
+    ImageSequence,
+    /// A wildcard pattern (`*.jpg`), read with `-pattern_type glob`
+    Glob,
+    /// Pipes, URLs and null sinks
+    NonFile,
+}
+
+/// Normalize a local file path for comparison (`./a.mp4` and `a.mp4` are the same file).
+/// Returns None for paths that are not plain files (pipes, URLs, null sinks).
+pub fn normalize_path(path: &str) -> Option<String> {
+    let path = path.trim_matches(|c| c == '"' || c == '\'');
+    let path = path.strip_prefix("file:").unwrap_or(path);
+    
+    if path.is_empty()
+        || path == "-"
+        || path.starts_with("pipe:")
+        || path.contains("://")
+        || path == "/dev/null"
+        || path.eq_ignore_ascii_case("NUL")
+    {
+        return None;
+    }
+    
+    let is_absolute = path.starts_with('/');
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." if segments.last().is_some_and(|last| *last != "..") => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    
+    let joined = segments.join("/");
+    Some(if is_absolute { format!("/{}", joined) } else { joined })
+}
+
+/// Classify a path the way ffmpeg's image2 format would see it
+pub fn classify_path(path: &str) -> PathKind {
+    let Some(path) = normalize_path(path) else {
+        return PathKind::NonFile;
+    };
+    let ext = path.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase()).unwrap_or_default();
+    if !IMAGE_EXTENSIONS.contains(&ext.as_str()) {
+        return PathKind::File;
+    }
+    
+    if has_sequence_pattern(&path) {
+        PathKind::ImageSequence
+    } else if path.contains(['*', '?', '[']) {
+        PathKind::Glob
+    } else {
+        PathKind::Image
+    }
+}
+
+/// Whether the path contains a `%d` or `%0Nd` frame number placeholder (`%%` is a literal percent)
+fn has_sequence_pattern(path: &str) -> bool {
+    let mut chars = path.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+        if chars.peek() == Some(&'%') {
+            chars.next();
+            continue;
+        }
+        while chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+            chars.next();
+        }
+        if chars.peek() == Some(&'d') {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("./a.mp4"), Some("a.mp4".to_string()));
+        assert_eq!(normalize_path("out//../a.mp4"), Some("a.mp4".to_string()));
+        assert_eq!(normalize_path("'clips/a.mp4'"), Some("clips/a.mp4".to_string()));
+        assert_eq!(normalize_path("file:/tmp/./a.mp4"), Some("/tmp/a.mp4".to_string()));
+        assert_eq!(normalize_path("../a.mp4"), Some("../a.mp4".to_string()));
+        assert_eq!(normalize_path("-"), None);
+        assert_eq!(normalize_path("/dev/null"), None);
+        assert_eq!(normalize_path("rtmp://example.com/live"), None);
+    }
+    
+    #[test]
+    fn test_classify_path() {
+        assert_eq!(classify_path("frames/img%04d.png"), PathKind::ImageSequence);
+        assert_eq!(classify_path("thumb%d.JPG"), PathKind::ImageSequence);
+        assert_eq!(classify_path("100%%.png"), PathKind::Image);
+        assert_eq!(classify_path("*.jpg"), PathKind::Glob);
+        assert_eq!(classify_path("cover.png"), PathKind::Image);
+        assert_eq!(classify_path("segment%03d.mp4"), PathKind::File);
+        assert_eq!(classify_path("pipe:1"), PathKind::NonFile);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::ast::{FfmpegCommand, OptionNode};
 use crate::codec_db::CodecDatabase;
+use crate::messages::{stream_type_name, Locale, Message};
 use crate::stream_tracker::StreamTracker;
 use shared_types::{DiagnosticRich, RichBlock, StreamType};
 
@@ -192,20 +193,19 @@ pub fn explain_codec_format_incompatibility(
     codec_name: &str,
     format_name: &str,
     compatible_formats: &[&str],
+    locale: Locale,
 ) -> String {
-    format!(
-        "## Codec/Container Incompatibility\n\n\
-        The **{}** codec cannot be used with **{}** containers.\n\n\
-        ### Compatible Containers\n{}\n\n\
-        ### Solution\n\
-        Change the output file extension to use a compatible container format.",
-        codec_name,
-        format_name,
-        compatible_formats.iter()
-            .map(|f| format!("- `{}`", f))
-            .collect::<Vec<_>>()
-            .join("\n")
-    )
+    Message::new("rich-codec-format-incompatibility")
+        .arg("codec", codec_name)
+        .arg("format", format_name)
+        .arg(
+            "formats",
+            compatible_formats.iter()
+                .map(|f| format!("- `{}`", f))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+        .render(locale)
 }
 
 /// Generate markdown explanation for missing stream errors
@@ -213,28 +213,28 @@ pub fn explain_missing_stream(
     stream_type: &StreamType,
     operation: &str,
     available_streams: &[StreamType],
+    locale: Locale,
 ) -> String {
-    format!(
-        "## Missing {:?} Stream\n\n\
-        The operation **{}** requires a {:?} stream, but none is available in the inputs.\n\n\
-        ### Available Streams\n{}\n\n\
-        ### Solution\n\
-        - Use an input file that contains a {:?} stream, or\n\
-        - Remove the {:?}-specific option from the command",
-        stream_type,
-        operation,
-        stream_type,
-        if available_streams.is_empty() {
-            "None".to_string()
-        } else {
-            available_streams.iter()
-                .map(|s| format!("- {:?}", s))
-                .collect::<Vec<_>>()
-                .join("\n")
-        },
-        stream_type,
-        stream_type
-    )
+    Message::new("rich-missing-stream")
+        .arg("stream", stream_type_name(stream_type))
+        .arg("operation", operation)
+        .arg(
+            "streams",
+            if available_streams.is_empty() {
+                locale.text("rich-no-streams")
+            } else {
+                available_streams.iter()
+                    .map(|s| format!("- {}", stream_type_name(s).render(locale)))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            },
+        )
+        .render(locale)
+}
+
+/// Explain burned-in subtitles (`subtitles=`/`ass=` filters) versus soft subtitle tracks (`-c:s`)
+pub fn explain_subtitle_modes(locale: Locale) -> String {
+    locale.text("rich-subtitle-modes")
 }
 
 /// Build rich content for a diagnostic
@@ -0,0 +1,322 @@
+use crate::analyzer::{
+    check_audio_parameters, check_codec_streams, check_container_codecs, check_encoder_options, check_frame_parameters,
+    check_input_decoders, check_input_streams, check_output_codecs, check_output_filters, check_output_mapping,
+    check_pipeline_overview, check_pixel_formats, check_rate_control, check_trim_options, check_upscaling,
+    lossy_transcoding, seek_precision_hints, validate_image_sequences, validate_output_paths,
+};
+use crate::ast::FfmpegCommand;
+use crate::codec_db::{CodecDatabase, FfmpegVersion};
+use crate::hwaccel::check_hardware_pipelines;
+use crate::lint::apply_lint;
+use crate::messages::Locale;
+use crate::option_rules::{check_option_conflicts, check_unknown_options};
+use crate::stream_tracker::StreamTracker;
+use crate::versions::check_versions;
+use shared_types::{DiagnosticMessage, LintConfig, Severity};
+use std::collections::HashSet;
+
+/// What a rule can see: the parsed command, the streams resolved from its inputs and
+/// filter graphs, and the codec database
+pub struct RuleContext<'a> {
+    pub command: &'a FfmpegCommand,
+    pub tracker: &'a StreamTracker,
+    pub db: &'a CodecDatabase,
+    /// ffmpeg release the command will run with; None assumes the latest release
+    pub ffmpeg_version: Option<FfmpegVersion>,
+    /// Language of the diagnostic messages
+    pub locale: Locale,
+}
+
+/// Area of the command a rule checks, following the ranges of the diagnostic codes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RuleCategory {
+    Streams,
+    Codecs,
+    Mapping,
+    Options,
+    Filters,
+    Files,
+    Hardware,
+    Quality,
+    Compatibility,
+    Info,
+}
+
+impl RuleCategory {
+    pub const ALL: [RuleCategory; 10] = [
+        RuleCategory::Streams,
+        RuleCategory::Codecs,
+        RuleCategory::Mapping,
+        RuleCategory::Options,
+        RuleCategory::Filters,
+        RuleCategory::Files,
+        RuleCategory::Hardware,
+        RuleCategory::Quality,
+        RuleCategory::Compatibility,
+        RuleCategory::Info,
+    ];
+    
+    /// Name used in lint configuration ("quality")
+    pub fn name(&self) -> &'static str {
+        match self {
+            RuleCategory::Streams => "streams",
+            RuleCategory::Codecs => "codecs",
+            RuleCategory::Mapping => "mapping",
+            RuleCategory::Options => "options",
+            RuleCategory::Filters => "filters",
+            RuleCategory::Files => "files",
+            RuleCategory::Hardware => "hardware",
+            RuleCategory::Quality => "quality",
+            RuleCategory::Compatibility => "compatibility",
+            RuleCategory::Info => "info",
+        }
+    }
+    
+    pub fn from_name(name: &str) -> Option<RuleCategory> {
+        Self::ALL.into_iter().find(|c| c.name() == name)
+    }
+    
+    /// Category of a built-in diagnostic code, from its range; None for parse errors and
+    /// codes of custom rules
+    pub fn of_code(code: &str) -> Option<RuleCategory> {
+        // Release availability is reported with the code of the area it concerns
+        const COMPATIBILITY: [&str; 7] = ["E209", "E210", "W204", "E414", "W414", "E503", "W503"];
+        if COMPATIBILITY.contains(&code) {
+            return Some(RuleCategory::Compatibility);
+        }
+        let mut chars = code.chars();
+        match (chars.next()?, chars.next()?) {
+            ('I', _) => Some(RuleCategory::Info),
+            ('W', '1') => Some(RuleCategory::Quality),
+            // Inputs whose streams are unknown
+            ('W', '2') if code == "W200" => Some(RuleCategory::Streams),
+            ('E', '1') => Some(RuleCategory::Streams),
+            ('E' | 'W', '2') => Some(RuleCategory::Codecs),
+            ('E' | 'W', '3') => Some(RuleCategory::Mapping),
+            ('E' | 'W', '4') => Some(RuleCategory::Options),
+            ('E' | 'W', '5') => Some(RuleCategory::Filters),
+            ('E' | 'W', '6') => Some(RuleCategory::Files),
+            ('E' | 'W', '7') => Some(RuleCategory::Hardware),
+            _ => None,
+        }
+    }
+}
+
+/// A check run over every analyzed command
+pub trait Rule {
+    /// Stable identifier, e.g. "output-paths"
+    fn id(&self) -> &str;
+    fn category(&self) -> RuleCategory;
+    /// Severity of the rule's main diagnostic; individual diagnostics may differ
+    fn default_severity(&self) -> Severity;
+    fn check(&self, context: &RuleContext) -> Vec<DiagnosticMessage>;
+}
+
+/// A rule backed by a plain function
+pub struct FnRule {
+    id: &'static str,
+    category: RuleCategory,
+    default_severity: Severity,
+    check: fn(&RuleContext) -> Vec<DiagnosticMessage>,
+}
+
+impl FnRule {
+    pub fn new(
+        id: &'static str,
+        category: RuleCategory,
+        default_severity: Severity,
+        check: fn(&RuleContext) -> Vec<DiagnosticMessage>,
+    ) -> Self {
+        FnRule { id, category, default_severity, check }
+    }
+}
+
+impl Rule for FnRule {
+    fn id(&self) -> &str {
+        self.id
+    }
+    
+    fn category(&self) -> RuleCategory {
+        self.category
+    }
+    
+    fn default_severity(&self) -> Severity {
+        self.default_severity.clone()
+    }
+    
+    fn check(&self, context: &RuleContext) -> Vec<DiagnosticMessage> {
+        (self.check)(context)
+    }
+}
+
+/// The rules `analyze_command` runs, in order
+pub struct RuleRegistry {
+    rules: Vec<Box<dyn Rule>>,
+    disabled: HashSet<String>,
+}
+
+impl Default for RuleRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl RuleRegistry {
+    /// A registry without any rules
+    pub fn empty() -> Self {
+        RuleRegistry { rules: Vec::new(), disabled: HashSet::new() }
+    }
+    
+    /// The analyzer's own rules
+    pub fn builtin() -> Self {
+        use RuleCategory::*;
+        let builtin = [
+            FnRule::new("input-streams", Streams, Severity::Warning, check_input_streams),
+            FnRule::new("trim-options", Options, Severity::Error, check_trim_options),
+            FnRule::new("input-decoders", Codecs, Severity::Error, check_input_decoders),
+            FnRule::new("seek-precision", Quality, Severity::Hint, |ctx| seek_precision_hints(ctx.command, ctx.locale)),
+            FnRule::new("output-paths", Files, Severity::Error, |ctx| validate_output_paths(ctx.command, ctx.locale)),
+            FnRule::new("option-conflicts", Options, Severity::Warning, |ctx| check_option_conflicts(ctx.command, ctx.locale)),
+            FnRule::new("unknown-options", Options, Severity::Warning, |ctx| check_unknown_options(ctx.command, ctx.locale)),
+            FnRule::new("image-sequences", Files, Severity::Warning, |ctx| validate_image_sequences(ctx.command, ctx.locale)),
+            FnRule::new("hardware-pipelines", Hardware, Severity::Error, |ctx| check_hardware_pipelines(ctx.command, ctx.db, ctx.locale)),
+            FnRule::new("ffmpeg-version", Compatibility, Severity::Error, |ctx| {
+                check_versions(ctx.command, ctx.db, ctx.ffmpeg_version, ctx.locale)
+            }),
+            FnRule::new("output-codecs", Codecs, Severity::Error, check_output_codecs),
+            FnRule::new("codec-streams", Streams, Severity::Error, check_codec_streams),
+            FnRule::new("output-filters", Filters, Severity::Error, check_output_filters),
+            FnRule::new("frame-parameters", Options, Severity::Error, check_frame_parameters),
+            FnRule::new("stream-mapping", Mapping, Severity::Error, check_output_mapping),
+            FnRule::new("container-codecs", Codecs, Severity::Error, check_container_codecs),
+            FnRule::new("encoder-options", Options, Severity::Warning, check_encoder_options),
+            FnRule::new("pixel-formats", Codecs, Severity::Warning, check_pixel_formats),
+            FnRule::new("rate-control", Quality, Severity::Warning, check_rate_control),
+            FnRule::new("upscaling", Quality, Severity::Warning, check_upscaling),
+            FnRule::new("audio-parameters", Codecs, Severity::Error, check_audio_parameters),
+            FnRule::new("lossy-transcoding", Quality, Severity::Hint, lossy_transcoding),
+            FnRule::new("pipeline-overview", Info, Severity::Hint, check_pipeline_overview),
+        ];
+        
+        let mut registry = Self::empty();
+        for rule in builtin {
+            registry.register(rule);
+        }
+        registry
+    }
+    
+    /// Add a rule after the registered ones; a rule with the same id is replaced in place
+    pub fn register(&mut self, rule: impl Rule + 'static) {
+        match self.rules.iter().position(|r| r.id() == rule.id()) {
+            Some(index) => self.rules[index] = Box::new(rule),
+            None => self.rules.push(Box::new(rule)),
+        }
+    }
+    
+    pub fn rules(&self) -> impl Iterator<Item = &dyn Rule> {
+        self.rules.iter().map(|r| r.as_ref())
+    }
+    
+    pub fn get(&self, id: &str) -> Option<&dyn Rule> {
+        self.rules().find(|r| r.id() == id)
+    }
+    
+    pub fn set_enabled(&mut self, id: &str, enabled: bool) {
+        if enabled {
+            self.disabled.remove(id);
+        } else {
+            self.disabled.insert(id.to_string());
+        }
+    }
+    
+    pub fn is_enabled(&self, id: &str) -> bool {
+        !self.disabled.contains(id)
+    }
+    
+    /// Run every enabled rule and collect their diagnostics in rule order, with the
+    /// levels of `lint` applied
+    pub fn run(&self, context: &RuleContext, lint: Option<&LintConfig>) -> Vec<DiagnosticMessage> {
+        self.rules()
+            .filter(|rule| self.is_enabled(rule.id()))
+            .flat_map(|rule| {
+                let diagnostics = rule.check(context);
+                match lint {
+                    Some(lint) => apply_lint(lint, rule.category(), diagnostics),
+                    None => diagnostics,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::{analyze_command_with_rules, AnalysisOptions};
+    use crate::ast::OptionNode;
+    use crate::parser::parse_command;
+    use shared_types::{DiagnosticKind, DiagnosticSpan, SpanRole};
+    
+    /// House policy: deliverables are H.264 only
+    fn no_hevc(context: &RuleContext) -> Vec<DiagnosticMessage> {
+        context
+            .command
+            .outputs
+            .iter()
+            .flat_map(|output| output.options.iter())
+            .filter_map(|option| match option {
+                OptionNode::VideoCodec { codec, codec_span, .. } if context.db.codec_identity(codec) == "hevc" => Some(DiagnosticMessage {
+                    code: "X001".to_string(),
+                    severity: Severity::Error,
+                    kind: DiagnosticKind::ParseError { message: "HEVC is not allowed".to_string() },
+                    message: "Deliverables must be H.264".to_string(),
+                    spans: vec![DiagnosticSpan { span: codec_span.clone(), role: SpanRole::Target, message: "HEVC".to_string() }],
+                    rich: None,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+    
+    fn codes(input: &str, registry: &RuleRegistry) -> Vec<String> {
+        let cmd = parse_command(input, 0, 0).unwrap();
+        analyze_command_with_rules(cmd, AnalysisOptions::default(), &CodecDatabase::new(), registry)
+            .messages
+            .into_iter()
+            .map(|d| d.code)
+            .collect()
+    }
+    
+    #[test]
+    fn test_custom_rule() {
+        let mut registry = RuleRegistry::builtin();
+        registry.register(FnRule::new("house-no-hevc", RuleCategory::Codecs, Severity::Error, no_hevc));
+        assert!(codes("ffmpeg -i input.mp4 -c:v libx265 out.mp4", &registry).contains(&"X001".to_string()));
+        assert!(!codes("ffmpeg -i input.mp4 -c:v libx264 out.mp4", &registry).contains(&"X001".to_string()));
+        assert_eq!(registry.get("house-no-hevc").map(|r| r.category()), Some(RuleCategory::Codecs));
+    }
+    
+    #[test]
+    fn test_disable_rule() {
+        let mut registry = RuleRegistry::builtin();
+        let input = "ffmpeg -i input.mp4 -c:v h264 out.mp4";
+        assert!(codes(input, &registry).contains(&"I001".to_string()));
+        registry.set_enabled("pipeline-overview", false);
+        assert!(!codes(input, &registry).contains(&"I001".to_string()));
+        assert!(codes(input, &registry).contains(&"W205".to_string()));
+        assert!(codes(input, &RuleRegistry::empty()).is_empty());
+    }
+    
+    #[test]
+    fn test_output_checks_are_separate_rules() {
+        let mut registry = RuleRegistry::builtin();
+        let input = "ffmpeg -i input.mp4 -c:v libx264 -b:v 100M -c:a libopus out.mp4";
+        assert!(codes(input, &registry).contains(&"W101".to_string()));
+        registry.set_enabled("rate-control", false);
+        let remaining = codes(input, &registry);
+        assert!(!remaining.contains(&"W101".to_string()));
+        assert!(remaining.contains(&"E201".to_string()));
+        assert_eq!(registry.get("rate-control").map(|r| r.category()), Some(RuleCategory::Quality));
+        assert!(matches!(registry.get("container-codecs").map(|r| r.default_severity()), Some(Severity::Error)));
+    }
+}
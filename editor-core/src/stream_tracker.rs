@@ -1,35 +1,72 @@
-use crate::ast::{InputSpec, OptionNode, StreamInfo};
-use crate::codec_db::CodecDatabase;
-use crate::rich_content::{build_rich_content, generate_codec_compatibility_matrix, explain_codec_format_incompatibility, explain_missing_stream};
-use shared_types::{DiagnosticKind, DiagnosticMessage, Severity, SourceCodeSpan, StreamType, DiagnosticSpan, SpanRole, RichBlock};
+use crate::ast::{parse_time_seconds, FilterSpec, InputSpec, OptionNode, StreamInfo};
+use crate::parser::parse_filter_graph;
+use crate::paths::{classify_path, normalize_path, PathKind};
+use crate::codec_db::{CodecDatabase, STREAM_COPY};
+use crate::messages::{stream_type_name, Locale, Message};
+use crate::suggest::{codec_suggestions, did_you_mean};
+use crate::rich_content::{build_rich_content, generate_codec_compatibility_matrix, explain_codec_format_incompatibility, explain_missing_stream, explain_subtitle_modes};
+use shared_types::{DiagnosticKind, DiagnosticMessage, MediaFile, Severity, SourceCodeSpan, StreamType, DiagnosticSpan, SpanRole, RichBlock};
 use std::collections::HashMap;
 
+/// lavfi source filters producing a video stream
+const VIDEO_SOURCES: &[&str] = &[
+    "allrgb", "allyuv", "cellauto", "color", "colorchart", "colorspectrum", "gradients", "haldclutsrc", "life",
+    "mandelbrot", "mptestsrc", "nullsrc", "pal75bars", "pal100bars", "rgbtestsrc", "smptebars", "smptehdbars",
+    "testsrc", "testsrc2", "yuvtestsrc", "zoneplate",
+];
+
+/// lavfi source filters producing an audio stream
+const AUDIO_SOURCES: &[&str] = &["aevalsrc", "afirsrc", "anoisesrc", "anullsrc", "flite", "hilbert", "sinc", "sine"];
+
 /// Track streams through the FFmpeg pipeline
 pub struct StreamTracker {
     /// Streams available from inputs
     pub input_streams: Vec<StreamInfo>,
     /// Input file spans by input index
     pub input_file_spans: Vec<SourceCodeSpan>,
+    /// Known duration in seconds by input index (None if unknown)
+    pub input_durations: Vec<Option<f64>>,
+    /// Inputs whose stream types could not be determined; they may provide any stream
+    pub unknown_inputs: Vec<usize>,
     /// Named filter outputs (from filter_complex)
     pub filter_outputs: HashMap<String, StreamType>,
+    /// Known stream information for input files, used in place of extension guesses
+    media: Vec<MediaFile>,
     /// Codec database
     db: CodecDatabase,
 }
 
 impl StreamTracker {
     pub fn new() -> Self {
+        Self::with_media(Vec::new())
+    }
+    
+    /// Create a tracker that reads the streams of the given files from `media`
+    pub fn with_media(media: Vec<MediaFile>) -> Self {
+        Self::with_database(CodecDatabase::new(), media)
+    }
+    
+    /// Create a tracker that checks codecs and filters against `db` instead of the built-in database
+    pub fn with_database(db: CodecDatabase, media: Vec<MediaFile>) -> Self {
         StreamTracker {
             input_streams: Vec::new(),
             input_file_spans: Vec::new(),
+            input_durations: Vec::new(),
+            unknown_inputs: Vec::new(),
             filter_outputs: HashMap::new(),
-            db: CodecDatabase::new(),
+            media,
+            db,
         }
     }
     
+    /// Manifest entry for an input path, comparing normalized paths
+    pub(crate) fn media_for(&self, file_path: &str) -> Option<&MediaFile> {
+        let path = normalize_path(file_path)?;
+        self.media.iter().find(|m| normalize_path(&m.path).as_ref() == Some(&path))
+    }
+    
     /// Analyze inputs and determine available streams
-    pub fn analyze_inputs(&mut self, inputs: &[InputSpec]) -> Vec<DiagnosticMessage> {
-        let mut diagnostics = Vec::new();
-        
+    pub fn analyze_inputs(&mut self, inputs: &[InputSpec]) {
         for (input_idx, input) in inputs.iter().enumerate() {
             // Track input file span by index for reference spans
             if self.input_file_spans.len() <= input_idx {
@@ -37,69 +74,143 @@ impl StreamTracker {
             } else {
                 self.input_file_spans[input_idx] = input.file_path_span.clone();
             }
+            let media = self.media_for(&input.file_path);
+            let duration = media.and_then(|m| m.duration).or_else(|| self.infer_input_duration(input));
+            // Take streams from the manifest when the file was probed
+            let probed_streams: Option<Vec<StreamInfo>> = media.map(|media| {
+                media
+                    .streams
+                    .iter()
+                    .enumerate()
+                    .map(|(stream_idx, stream)| StreamInfo {
+                        stream_type: stream.stream_type.clone(),
+                        index: stream_idx,
+                        input_index: input_idx,
+                        codec: stream.codec.clone(),
+                        width: stream.width,
+                        height: stream.height,
+                        frame_rate: stream.frame_rate,
+                        sample_rate: stream.sample_rate,
+                        channels: stream.channels,
+                    })
+                    .collect()
+            });
+            
+            if self.input_durations.len() <= input_idx {
+                self.input_durations.push(duration);
+            } else {
+                self.input_durations[input_idx] = duration;
+            }
+            if let Some(streams) = probed_streams {
+                self.input_streams.extend(streams);
+                continue;
+            }
+            
             // Infer stream types from file extension or format options
             let streams = self.infer_input_streams(input);
+            if streams.is_empty() && !self.unknown_inputs.contains(&input_idx) {
+                self.unknown_inputs.push(input_idx);
+            }
             
             for (stream_idx, stream_type) in streams.iter().enumerate() {
+                let codec = self.db.codec_for_filename(&input.file_path, stream_type);
                 self.input_streams.push(StreamInfo {
                     stream_type: stream_type.clone(),
                     index: stream_idx,
                     input_index: input_idx,
-                });
-            }
-            
-            // If we couldn't infer any streams, add a warning
-            if streams.is_empty() {
-                diagnostics.push(DiagnosticMessage {
-                    code: "W200".to_string(),
-                    severity: Severity::Warning,
-                    kind: DiagnosticKind::ParseError {
-                        message: "Could not determine stream types from input".to_string(),
-                    },
-                    message: format!("Unknown stream types for input: {}", input.file_path),
-                    spans: vec![DiagnosticSpan { span: input.file_path_span.clone(), role: SpanRole::Target, message: "unknown streams".to_string() }],
-                    rich: None,
+                    codec,
+                    width: None,
+                    height: None,
+                    frame_rate: None,
+                    sample_rate: None,
+                    channels: None,
                 });
             }
         }
-        
-        diagnostics
     }
     
     fn infer_input_streams(&self, input: &InputSpec) -> Vec<StreamType> {
         // Check for explicit format option
         for option in &input.options {
-            if let OptionNode::Format { format, .. } = option {
-                return self.infer_streams_from_format(format);
+            match option {
+                OptionNode::Format { format, .. } if format == "lavfi" => return self.infer_lavfi_streams(input),
+                OptionNode::Format { format, .. } => return self.infer_streams_from_format(format),
+                _ => {}
             }
         }
         
+        // Image sequences and globs are read by image2 as a single video stream
+        if matches!(classify_path(&input.file_path), PathKind::ImageSequence | PathKind::Glob) {
+            return vec![StreamType::Video];
+        }
+        
         // Infer from file extension
         self.infer_streams_from_filename(&input.file_path)
     }
     
+    /// Streams of a lavfi graph input from its source filters; empty if any source is unknown
+    fn infer_lavfi_streams(&self, input: &InputSpec) -> Vec<StreamType> {
+        let Some(graph) = parse_filter_graph(&input.file_path, input.file_path_span.start_line, input.file_path_span.start_column) else {
+            return Vec::new();
+        };
+        let mut streams = Vec::new();
+        for source in graph.chains.iter().filter_map(|chain| chain.filters.first()) {
+            let stream_type = if VIDEO_SOURCES.contains(&source.name.as_str()) {
+                StreamType::Video
+            } else if AUDIO_SOURCES.contains(&source.name.as_str()) {
+                StreamType::Audio
+            } else {
+                return Vec::new();
+            };
+            streams.push(stream_type);
+        }
+        streams
+    }
+    
+    /// Duration of an input when it can be known statically (lavfi sources with `d=`/`duration=`)
+    fn infer_input_duration(&self, input: &InputSpec) -> Option<f64> {
+        let is_lavfi = input.options.iter().any(|o| matches!(o, OptionNode::Format { format, .. } if format == "lavfi"));
+        if !is_lavfi {
+            return None;
+        }
+        let graph = parse_filter_graph(&input.file_path, input.file_path_span.start_line, input.file_path_span.start_column)?;
+        graph
+            .chains
+            .iter()
+            .flat_map(|chain| chain.filters.iter())
+            .filter_map(|filter| filter.params.iter().find(|p| matches!(p.key.as_deref(), Some("d" | "duration"))))
+            .filter_map(|param| parse_time_seconds(&param.value))
+            .reduce(f64::min)
+    }
+    
     fn infer_streams_from_filename(&self, filename: &str) -> Vec<StreamType> {
-        let ext = filename.split('.').last().unwrap_or("");
+        let ext = filename.rsplit('.').next().unwrap_or("");
         
         match ext {
             // Video formats (typically have both video and audio)
-            "mp4" | "mkv" | "avi" | "mov" | "webm" | "flv" | "wmv" | "m4v" => {
+            "mp4" | "mkv" | "avi" | "mov" | "webm" | "flv" | "wmv" | "m4v" | "ts" | "m2ts" | "mts" | "mpg" | "mpeg"
+            | "vob" | "3gp" | "mxf" | "ogv" | "f4v" | "asf" | "nut" | "dv" | "matroska" | "mpegts" => {
                 vec![StreamType::Video, StreamType::Audio]
             }
             // Audio-only formats
-            "mp3" | "aac" | "flac" | "wav" | "ogg" | "opus" | "m4a" | "wma" => {
+            "mp3" | "aac" | "flac" | "wav" | "ogg" | "opus" | "m4a" | "wma" | "mka" | "oga" | "ac3" | "eac3" | "dts"
+            | "aiff" | "aif" | "caf" | "amr" | "mp2" | "s16le" | "f32le" | "alsa" | "pulse" => {
                 vec![StreamType::Audio]
             }
             // Image formats (single video frame)
-            "png" | "jpg" | "jpeg" | "bmp" | "gif" => {
+            "png" | "jpg" | "jpeg" | "bmp" | "gif" | "webp" | "tif" | "tiff" | "image2" | "image2pipe" => {
+                vec![StreamType::Video]
+            }
+            // Raw video streams and capture devices
+            "h264" | "264" | "hevc" | "265" | "ivf" | "y4m" | "rawvideo" | "v4l2" | "x11grab" | "gdigrab" | "kmsgrab" => {
                 vec![StreamType::Video]
             }
             // Subtitle formats
-            "srt" | "ass" | "ssa" | "vtt" => {
+            "srt" | "ass" | "ssa" | "vtt" | "sup" => {
                 vec![StreamType::Subtitle]
             }
-            // Unknown - assume video + audio
-            _ => vec![StreamType::Video, StreamType::Audio],
+            // Unknown: the streams are not guessed, and stream checks skip the input
+            _ => Vec::new(),
         }
     }
     
@@ -108,9 +219,9 @@ impl StreamTracker {
         self.infer_streams_from_filename(&format!("file.{}", format))
     }
     
-    /// Check if a stream type is available in inputs
+    /// Check if a stream type is available in inputs; an input with unknown streams may have any
     pub fn has_stream_type(&self, stream_type: &StreamType) -> bool {
-        self.input_streams.iter().any(|s| matches_stream_type(&s.stream_type, stream_type))
+        !self.unknown_inputs.is_empty() || self.input_streams.iter().any(|s| matches_stream_type(&s.stream_type, stream_type))
     }
     
     /// Get streams of a specific type
@@ -139,21 +250,42 @@ impl StreamTracker {
     
     /// Get streams for a specific input file (for pipeline diagram)
     pub fn get_streams_for_input(&self, file_path: &str) -> Vec<StreamType> {
+        if let Some(media) = self.media_for(file_path) {
+            return media.streams.iter().map(|s| s.stream_type.clone()).collect();
+        }
         self.infer_streams_from_filename(file_path)
     }
     
+    /// Register the labelled outputs of a `-filter_complex` graph so `-map [label]` can refer to them
+    pub fn register_filter_graph(&mut self, spec: &FilterSpec) {
+        let Some(graph) = &spec.parsed else { return };
+        for chain in &graph.chains {
+            let Some(last) = chain.filters.last() else { continue };
+            let stream_type = self
+                .db
+                .get_filter(&last.name)
+                .map(|f| f.output_type.clone())
+                .unwrap_or(StreamType::Video);
+            for label in &last.output_labels {
+                self.filter_outputs.insert(label.clone(), stream_type.clone());
+            }
+        }
+    }
+    
     /// Validate filter against available stream types
     pub fn validate_filter(
         &self,
         filter_name: &str,
         expected_type: &StreamType,
         span: &SourceCodeSpan,
+        locale: Locale,
     ) -> Option<DiagnosticMessage> {
         if let Some(filter_info) = self.db.get_filter(filter_name) {
             // Check if we have the required input stream type
             if !self.has_stream_type(&filter_info.input_type) {
                 // Build spans: target on option span, plus a reference to the first input lacking stream
-                let mut spans = vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: "missing required stream".to_string() }];
+                let mut spans = vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-missing-required-stream") }];
+                let no_stream = Message::new("span-no-stream-in-input").arg("stream", stream_type_name(&filter_info.input_type)).render(locale);
                 // Find an input index that lacks the required type
                 let mut ref_added = false;
                 for (idx, input_span) in self.input_file_spans.iter().enumerate() {
@@ -161,7 +293,7 @@ impl StreamTracker {
                         s.input_index == idx && matches_stream_type(&s.stream_type, &filter_info.input_type)
                     );
                     if !has_required {
-                        spans.push(DiagnosticSpan { span: input_span.clone(), role: SpanRole::Reference, message: format!("no {:?} stream in input", filter_info.input_type) });
+                        spans.push(DiagnosticSpan { span: input_span.clone(), role: SpanRole::Reference, message: no_stream.clone() });
                         ref_added = true;
                         break;
                     }
@@ -169,10 +301,23 @@ impl StreamTracker {
                 if !ref_added {
                     // Fallback: reference the first input if none found (shouldn't happen)
                     if let Some(first) = self.input_file_spans.first() {
-                        spans.push(DiagnosticSpan { span: first.clone(), role: SpanRole::Reference, message: format!("no {:?} stream in input", filter_info.input_type) });
+                        spans.push(DiagnosticSpan { span: first.clone(), role: SpanRole::Reference, message: no_stream.clone() });
                     }
                 }
                 let available_streams = self.get_all_stream_types();
+                let mut blocks = vec![
+                    RichBlock::MarkdownGfm {
+                        markdown: explain_missing_stream(
+                            &filter_info.input_type,
+                            &Message::new("operation-filter").arg("filter", filter_name).render(locale),
+                            &available_streams,
+                            locale,
+                        ),
+                    },
+                ];
+                if matches!(filter_name, "subtitles" | "ass") {
+                    blocks.push(RichBlock::MarkdownGfm { markdown: explain_subtitle_modes(locale) });
+                }
                 return Some(DiagnosticMessage {
                     code: "E104".to_string(),
                     severity: Severity::Error,
@@ -180,20 +325,12 @@ impl StreamTracker {
                         stream_type: filter_info.input_type.clone(),
                         operation: format!("filter '{}'", filter_name),
                     },
-                    message: format!(
-                        "Filter '{}' requires {:?} stream, but no {:?} stream is available",
-                        filter_name, filter_info.input_type, filter_info.input_type
-                    ),
+                    message: Message::new("filter-without-stream")
+                        .arg("filter", filter_name)
+                        .arg("stream", stream_type_name(&filter_info.input_type))
+                        .render(locale),
                     spans,
-                    rich: build_rich_content(vec![
-                        RichBlock::MarkdownGfm {
-                            markdown: explain_missing_stream(
-                                &filter_info.input_type,
-                                &format!("filter '{}'", filter_name),
-                                &available_streams,
-                            ),
-                        },
-                    ]),
+                    rich: build_rich_content(blocks),
                 });
             }
             
@@ -207,23 +344,25 @@ impl StreamTracker {
                         expected: expected_type.clone(),
                         found: filter_info.input_type.clone(),
                     },
-                    message: format!(
-                        "Filter '{}' expects {:?} stream but is being used in {:?} context",
-                        filter_name, filter_info.input_type, expected_type
-                    ),
-                    spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: "missing required stream".to_string() }],
+                    message: Message::new("filter-stream-mismatch")
+                        .arg("filter", filter_name)
+                        .arg("stream", stream_type_name(&filter_info.input_type))
+                        .arg("context", stream_type_name(expected_type))
+                        .render(locale),
+                    spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-missing-required-stream") }],
                 rich: None,                });
             }
         } else {
-            // Unknown filter - issue warning
+            // Unknown filter: ffmpeg stops with "No such filter", but the built-in list may lack
+            // filters of the user's build, so only an imported `-filters` listing makes it an error
             return Some(DiagnosticMessage {
                 code: "E502".to_string(),
-                severity: Severity::Warning,
+                severity: if self.db.filters_complete { Severity::Error } else { Severity::Warning },
                 kind: DiagnosticKind::UnknownFilter {
                     filter: filter_name.to_string(),
                 },
-                message: format!("Unknown filter: '{}'", filter_name),
-                spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: "unknown filter".to_string() }],
+                message: Message::new("unknown-filter").arg("filter", filter_name).render(locale),
+                spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-unknown-filter") }],
                 rich: None,
             });
         }
@@ -237,9 +376,10 @@ impl StreamTracker {
         codec_name: &str,
         expected_type: &StreamType,
         span: &SourceCodeSpan,
+        locale: Locale,
     ) -> Option<DiagnosticMessage> {
-        if codec_name == "copy" {
-            // 'copy' is always valid
+        if codec_name == STREAM_COPY {
+            // Stream copy needs neither an encoder nor a decoder
             return None;
         }
         
@@ -252,24 +392,29 @@ impl StreamTracker {
                         codec: codec_name.to_string(),
                         stream_type: expected_type.clone(),
                     },
-                    message: format!(
-                        "Codec '{}' is a {:?} codec but is being used for {:?} stream",
-                        codec_name, codec_info.stream_type, expected_type
-                    ),
-                    spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: "invalid codec for stream".to_string() }],
+                    message: Message::new("codec-stream-mismatch")
+                        .arg("codec", codec_name)
+                        .arg("stream", stream_type_name(&codec_info.stream_type))
+                        .arg("context", stream_type_name(expected_type))
+                        .render(locale),
+                    spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-invalid-codec-for-stream") }],
                     rich: None,
                 });
             }
         } else {
-            // Unknown codec - issue warning
+            // Unknown codec - issue warning, with the encoders of the stream type it may mean
+            let suggestions = codec_suggestions(&self.db, codec_name, expected_type, true);
+            let (suggestion_spans, hint) = did_you_mean(&suggestions, span, locale);
+            let mut spans = vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-unknown-codec") }];
+            spans.extend(suggestion_spans);
             return Some(DiagnosticMessage {
                 code: "W201".to_string(),
                 severity: Severity::Warning,
                 kind: DiagnosticKind::ParseError {
                     message: format!("Unknown codec: '{}'", codec_name),
                 },
-                message: format!("Unknown codec: '{}'", codec_name),
-                spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: "unknown codec".to_string() }],
+                message: Message::new("unknown-codec").arg("codec", codec_name).arg("hint", hint).render(locale),
+                spans,
                 rich: None,
             });
         }
@@ -277,6 +422,78 @@ impl StreamTracker {
         None
     }
     
+    /// Check that an output codec names an encoder. For a codec name such as `h264`, ffmpeg
+    /// falls back to the codec's default encoder (W205); a codec nothing encodes is fatal (E211)
+    pub fn validate_encoder(&self, codec_name: &str, span: &SourceCodeSpan, locale: Locale) -> Option<DiagnosticMessage> {
+        if codec_name == STREAM_COPY {
+            return None;
+        }
+        let codec_info = self.db.get_codec(codec_name).filter(|c| !c.is_encoder)?;
+        let encoders = self.db.encoders_for(self.db.codec_identity(codec_name));
+        let kind = DiagnosticKind::WrongCodecDirection {
+            codec: codec_name.to_string(),
+            direction: "encoder".to_string(),
+            alternatives: encoders.iter().map(|e| e.to_string()).collect(),
+        };
+        if encoders.is_empty() {
+            return Some(DiagnosticMessage {
+                code: "E211".to_string(),
+                severity: Severity::Error,
+                kind,
+                message: Message::new("no-encoder")
+                    .arg("stream", stream_type_name(&codec_info.stream_type))
+                    .arg("codec", codec_name)
+                    .render(locale),
+                spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-no-encoder") }],
+                rich: None,
+            });
+        }
+        
+        let mut spans = vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-decoder-name") }];
+        spans.extend(did_you_mean(&encoders[..encoders.len().min(3)], span, locale).0);
+        Some(DiagnosticMessage {
+            code: "W205".to_string(),
+            severity: Severity::Warning,
+            kind,
+            message: Message::new("decoder-as-encoder")
+                .arg("codec", codec_name)
+                .arg("hint", did_you_mean(&encoders[..1], span, locale).1)
+                .render(locale),
+            spans,
+            rich: None,
+        })
+    }
+    
+    /// Check that an input codec (`-c:v` before `-i`) names a decoder. Codec names fall back to
+    /// the codec's default decoder, but encoder-only names such as `libx264` stop ffmpeg with
+    /// "Unknown decoder" (E212)
+    pub fn validate_decoder(&self, codec_name: &str, span: &SourceCodeSpan, locale: Locale) -> Option<DiagnosticMessage> {
+        if codec_name == STREAM_COPY {
+            return None;
+        }
+        self.db.get_codec(codec_name).filter(|c| !c.is_decoder)?;
+        let identity = self.db.codec_identity(codec_name);
+        let decoders = self.db.decoders_for(identity);
+        if identity == codec_name && !decoders.is_empty() {
+            return None;
+        }
+        let mut spans = vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-encoder-name") }];
+        spans.extend(did_you_mean(&decoders[..decoders.len().min(3)], span, locale).0);
+        let (_, hint) = did_you_mean(&decoders[..decoders.len().min(1)], span, locale);
+        Some(DiagnosticMessage {
+            code: "E212".to_string(),
+            severity: Severity::Error,
+            kind: DiagnosticKind::WrongCodecDirection {
+                codec: codec_name.to_string(),
+                direction: "decoder".to_string(),
+                alternatives: decoders.iter().map(|d| d.to_string()).collect(),
+            },
+            message: Message::new("encoder-as-decoder").arg("codec", codec_name).arg("identity", identity).arg("hint", hint).render(locale),
+            spans,
+            rich: None,
+        })
+    }
+    
     /// Validate codec/format compatibility
     pub fn validate_codec_format_compatibility(
         &self,
@@ -284,8 +501,10 @@ impl StreamTracker {
         format: &str,
         codec_span: &SourceCodeSpan,
         format_span: &SourceCodeSpan,
+        locale: Locale,
     ) -> Option<DiagnosticMessage> {
-        if codec_name == "copy" {
+        // Unknown codecs and formats are reported on their own (W201, W206)
+        if codec_name == STREAM_COPY || self.db.get_codec(codec_name).is_none() || self.db.get_format(format).is_none() {
             return None;
         }
         
@@ -306,10 +525,10 @@ impl StreamTracker {
                     format: format.to_string(),
                     reason: format!("Codec '{}' is not supported in '{}' container", codec_name, format),
                 },
-                message: format!("Codec '{}' is not supported in '{}' container", codec_name, format),
+                message: Message::new("codec-not-in-container").arg("codec", codec_name).arg("format", format).render(locale),
                 spans: vec![
-                    DiagnosticSpan { span: codec_span.clone(), role: SpanRole::Target, message: "codec".to_string() },
-                    DiagnosticSpan { span: format_span.clone(), role: SpanRole::Reference, message: format!("{} container", format) },
+                    DiagnosticSpan { span: codec_span.clone(), role: SpanRole::Target, message: locale.text("span-codec") },
+                    DiagnosticSpan { span: format_span.clone(), role: SpanRole::Reference, message: Message::new("span-container").arg("format", format).render(locale) },
                 ],
                 rich: build_rich_content(vec![
                     RichBlock::MarkdownGfm {
@@ -317,6 +536,7 @@ impl StreamTracker {
                             codec_name,
                             format,
                             &compatible_formats.iter().map(|s| s.as_str()).collect::<Vec<_>>(),
+                            locale,
                         ),
                     },
                     RichBlock::Mermaid {
@@ -334,7 +554,7 @@ impl StreamTracker {
     }
 }
 
-fn matches_stream_type(actual: &StreamType, expected: &StreamType) -> bool {
+pub(crate) fn matches_stream_type(actual: &StreamType, expected: &StreamType) -> bool {
     match (actual, expected) {
         (StreamType::Unknown, _) | (_, StreamType::Unknown) => true,
         (a, b) => std::mem::discriminant(a) == std::mem::discriminant(b),
@@ -368,6 +588,26 @@ mod tests {
         assert!(matches!(streams[0], StreamType::Audio));
     }
     
+    #[test]
+    fn test_unknown_input_streams() {
+        let input = |args: &str| crate::parser::parse_command(&format!("ffmpeg {} out.mp4", args), 1, 0).unwrap().inputs;
+        let mut tracker = StreamTracker::new();
+        tracker.analyze_inputs(&input("-i capture.dat -i rtsp://camera/live"));
+        assert_eq!(tracker.unknown_inputs, vec![0, 1]);
+        assert!(tracker.has_stream_type(&StreamType::Subtitle));
+        
+        let mut tracker = StreamTracker::new();
+        tracker.analyze_inputs(&input("-f mpegts -i capture.dat -f lavfi -i sine=duration=5 -f lavfi -i testsrc2"));
+        assert!(tracker.unknown_inputs.is_empty());
+        let types: Vec<_> = tracker.input_streams.iter().map(|s| (s.input_index, s.stream_type.clone())).collect();
+        assert_eq!(types, vec![(0, StreamType::Video), (0, StreamType::Audio), (1, StreamType::Audio), (2, StreamType::Video)]);
+        assert!(!tracker.has_stream_type(&StreamType::Subtitle));
+        
+        let mut tracker = StreamTracker::new();
+        tracker.analyze_inputs(&input("-f lavfi -i mysterysrc"));
+        assert_eq!(tracker.unknown_inputs, vec![0]);
+    }
+    
     #[test]
     fn test_validate_filter() {
         let mut tracker = StreamTracker::new();
@@ -375,6 +615,12 @@ mod tests {
             stream_type: StreamType::Video,
             index: 0,
             input_index: 0,
+            codec: None,
+            width: None,
+            height: None,
+            frame_rate: None,
+            sample_rate: None,
+            channels: None,
         });
         
         let span = SourceCodeSpan {
@@ -385,8 +631,17 @@ mod tests {
         };
         
         // Video filter on video stream should be ok
-        let result = tracker.validate_filter("scale", &StreamType::Video, &span);
+        let result = tracker.validate_filter("scale", &StreamType::Video, &span, Locale::English);
         assert!(result.is_none());
+        
+        // An unknown filter may come from a custom build unless the build's filters were imported
+        let result = tracker.validate_filter("scael", &StreamType::Video, &span, Locale::English).unwrap();
+        assert!(result.code == "E502" && matches!(result.severity, Severity::Warning));
+        let mut db = CodecDatabase::new();
+        db.import_filters(" T.C scale             V->V       Scale the input video size").unwrap();
+        let tracker = StreamTracker::with_database(db, Vec::new());
+        let result = tracker.validate_filter("scael", &StreamType::Video, &span, Locale::English).unwrap();
+        assert!(matches!(result.severity, Severity::Error));
     }
 }
 
@@ -0,0 +1,197 @@
+//! "Did you mean" suggestions for misspelled codecs, filters, formats and options
+
+use crate::codec_db::CodecDatabase;
+use crate::messages::{Arg, Locale, Message};
+use crate::stream_tracker::matches_stream_type;
+use shared_types::{DiagnosticSpan, SourceCodeSpan, SpanRole, StreamType};
+
+/// Most suggestions offered for one name
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Other names for a codec, after normalization ("H.264" -> "h264")
+const CODEC_ALIASES: &[(&str, &str)] = &[
+    ("avc", "h264"),
+    ("x264", "h264"),
+    ("h265", "hevc"),
+    ("x265", "hevc"),
+    ("vp09", "vp9"),
+    ("lame", "mp3"),
+    ("mp3lame", "mp3"),
+    ("mpeg3", "mp3"),
+    ("ogg", "vorbis"),
+    ("srt", "subrip"),
+];
+
+/// ffmpeg muxers the database has no codec lists for; valid `-f` values that are never corrected
+const UNLISTED_MUXERS: &[&str] = &[
+    "3g2", "3gp", "ac3", "adts", "aiff", "apng", "asf", "caf", "concat", "dash", "eac3", "f4v", "ffmetadata",
+    "fifo", "flac", "flv", "framemd5", "gif", "h264", "hash", "hevc", "hls", "image2", "image2pipe", "ipod",
+    "ismv", "lavfi", "md5", "mjpeg", "mp2", "mp3", "mpeg", "mpegts", "mxf", "null", "nut", "oga", "ogg",
+    "opus", "rawvideo", "rtp", "rtp_mpegts", "rtsp", "segment", "stream_segment", "tee", "vob", "wav", "webp",
+    "yuv4mpegpipe",
+];
+
+/// Lowercase and drop dots, so "H.264" compares equal to "h264"
+fn normalize(name: &str) -> String {
+    name.chars().filter(|c| *c != '.').flat_map(|c| c.to_lowercase()).collect()
+}
+
+/// Edit distance counting insertions, deletions, substitutions and swaps of adjacent characters
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    rows[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j] + 1).min(rows[i][j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+/// Largest distance still taken for a typo rather than a different name
+fn max_distance(name: &str) -> usize {
+    match name.chars().count() {
+        0..=4 => 1,
+        5..=8 => 2,
+        _ => 3,
+    }
+}
+
+/// The candidates closest to a name, ranked by `(distance, tie breaker)`; candidates further
+/// than the closest one are left out
+fn rank<'a, K: Ord>(mut scored: Vec<(usize, K, &'a str)>, name: &str) -> Vec<&'a str> {
+    scored.retain(|(distance, _, _)| *distance <= max_distance(name));
+    scored.sort_by(|a, b| (a.0, &a.1, a.2).cmp(&(b.0, &b.1, b.2)));
+    let best = scored.first().map(|(distance, _, _)| *distance);
+    let mut names: Vec<&str> = Vec::new();
+    for (_, _, candidate) in scored.into_iter().take_while(|(distance, _, _)| Some(*distance) == best) {
+        if !names.contains(&candidate) && names.len() < MAX_SUGGESTIONS {
+            names.push(candidate);
+        }
+    }
+    names
+}
+
+/// Encoders (or decoders) of a stream type that a misspelled codec may have meant. Naming the
+/// codec itself ("h.264", "x265") suggests its encoders, software first
+pub fn codec_suggestions<'a>(db: &'a CodecDatabase, name: &str, stream_type: &StreamType, encoder: bool) -> Vec<&'a str> {
+    let typed = normalize(name);
+    let alias = CODEC_ALIASES.iter().find(|(a, _)| *a == typed).map(|(_, codec)| *codec);
+    let scored = db
+        .codecs
+        .values()
+        .filter(|c| matches_stream_type(&c.stream_type, stream_type) && if encoder { c.is_encoder } else { c.is_decoder })
+        .filter(|c| c.availability.is_current())
+        .map(|c| {
+            let identity = db.codec_identity(&c.name);
+            let distance = if alias == Some(identity) || typed == identity {
+                0
+            } else {
+                edit_distance(&typed, &normalize(&c.name))
+            };
+            (distance, db.hardware_family_of(&c.name).is_some(), c.name.as_str())
+        })
+        .collect();
+    rank(scored, &typed)
+}
+
+/// Filters taking a stream type that a misspelled filter may have meant
+pub fn filter_suggestions<'a>(db: &'a CodecDatabase, name: &str, stream_type: &StreamType) -> Vec<&'a str> {
+    let scored = db
+        .filters
+        .values()
+        .filter(|f| matches_stream_type(&f.input_type, stream_type) && f.availability.is_current())
+        .map(|f| (edit_distance(name, &f.name), db.hardware_family_of(&f.name).is_some(), f.name.as_str()))
+        .collect();
+    rank(scored, name)
+}
+
+/// Output formats a `-f` value may have meant; empty for known formats. A file extension
+/// suggests its format ("mkv" -> matroska)
+pub fn format_suggestions<'a>(db: &'a CodecDatabase, name: &str) -> Vec<&'a str> {
+    if db.get_format(name).is_some() || UNLISTED_MUXERS.contains(&name) {
+        return Vec::new();
+    }
+    let typed = normalize(name);
+    let scored = db
+        .formats
+        .values()
+        .map(|f| {
+            let distance = if f.extensions.contains(&typed) { 0 } else { edit_distance(&typed, &f.name) };
+            (distance, (), f.name.as_str())
+        })
+        .collect();
+    rank(scored, &typed)
+}
+
+/// Options from a table that a misspelled option may have meant
+pub fn option_suggestions<'a>(name: &str, options: &[&'a str]) -> Vec<&'a str> {
+    let scored = options.iter().map(|option| (edit_distance(name, option), (), *option)).collect();
+    rank(scored, name.trim_start_matches('-'))
+}
+
+/// Suggestion spans replacing `span` with each name, and the "did you mean" sentence for the message
+pub fn did_you_mean(names: &[&str], span: &SourceCodeSpan, locale: Locale) -> (Vec<DiagnosticSpan>, String) {
+    let spans = names
+        .iter()
+        .map(|name| DiagnosticSpan {
+            span: span.clone(),
+            role: SpanRole::Suggestion { replacement: name.to_string() },
+            message: Message::new("fix-did-you-mean").arg("name", *name).render(locale),
+        })
+        .collect();
+    let hint = if names.is_empty() {
+        String::new()
+    } else {
+        let choices = Arg::Choices(names.iter().map(|n| n.to_string()).collect());
+        Message::new("hint-did-you-mean").arg("choices", choices).render(locale)
+    };
+    (spans, hint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("libx246", "libx264"), 1);
+        assert_eq!(edit_distance("scael", "scale"), 1);
+        assert_eq!(edit_distance("-vcodc", "-vcodec"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("volume", "volume"), 0);
+    }
+    
+    #[test]
+    fn test_codec_suggestions() {
+        let db = CodecDatabase::new();
+        assert_eq!(codec_suggestions(&db, "libx246", &StreamType::Video, true), vec!["libx264"]);
+        assert_eq!(codec_suggestions(&db, "h.264", &StreamType::Video, true)[0], "libx264");
+        assert_eq!(codec_suggestions(&db, "X265", &StreamType::Video, true)[0], "libx265");
+        // Only codecs of the stream being encoded
+        assert!(codec_suggestions(&db, "libx246", &StreamType::Audio, true).is_empty());
+        assert_eq!(codec_suggestions(&db, "acc", &StreamType::Audio, true)[0], "aac");
+    }
+    
+    #[test]
+    fn test_filter_and_format_suggestions() {
+        let db = CodecDatabase::new();
+        assert_eq!(filter_suggestions(&db, "scael", &StreamType::Video), vec!["scale"]);
+        assert!(filter_suggestions(&db, "scael", &StreamType::Audio).is_empty());
+        assert_eq!(filter_suggestions(&db, "volme", &StreamType::Audio), vec!["volume"]);
+        assert_eq!(format_suggestions(&db, "mkv"), vec!["matroska"]);
+        assert_eq!(format_suggestions(&db, "mpp4"), vec!["mp4"]);
+        assert!(format_suggestions(&db, "mp3").is_empty());
+        assert!(format_suggestions(&db, "webm").is_empty());
+    }
+}
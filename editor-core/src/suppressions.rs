@@ -0,0 +1,178 @@
+use crate::handler::{continues_command, is_command_line};
+use crate::messages::{Locale, Message};
+use shared_types::{DiagnosticKind, DiagnosticMessage, DiagnosticSpan, Severity, SourceCodeSpan, SpanRole};
+
+const PRAGMA_PREFIX: &str = "ffmpeg-lint:";
+
+/// Lines a suppression pragma covers
+#[derive(Debug, Clone, PartialEq)]
+pub enum SuppressionScope {
+    /// `disable-next-line`: the command on the line after the pragma, through the last line
+    /// it is wrapped over
+    NextLine { first: usize, last: usize },
+    /// `disable`: every line after the pragma
+    FromLine(usize),
+}
+
+/// A `# ffmpeg-lint: disable...` comment; no codes means every code
+#[derive(Debug, Clone)]
+pub struct Suppression {
+    pub codes: Vec<String>,
+    pub scope: SuppressionScope,
+    pub span: SourceCodeSpan,
+}
+
+impl Suppression {
+    fn covers_line(&self, line: usize) -> bool {
+        match self.scope {
+            SuppressionScope::NextLine { first, last } => (first..=last).contains(&line),
+            SuppressionScope::FromLine(pragma_line) => line > pragma_line,
+        }
+    }
+    
+    fn covers(&self, diagnostic: &DiagnosticMessage) -> bool {
+        let Some(target) = diagnostic.spans.iter().find(|s| matches!(s.role, SpanRole::Target)) else { return false };
+        self.covers_line(target.span.start_line) && (self.codes.is_empty() || self.codes.contains(&diagnostic.code))
+    }
+}
+
+/// Find the suppression pragmas in comment lines, numbering lines and columns like the parser
+pub fn parse_suppressions(content: &str, line_offset: usize, column_offset: usize) -> Vec<Suppression> {
+    let mut suppressions = Vec::new();
+    let lines: Vec<&str> = content.lines().collect();
+    for (idx, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+        let Some(comment) = trimmed.strip_prefix('#') else { continue };
+        let Some(directive) = comment.trim_start().strip_prefix(PRAGMA_PREFIX) else { continue };
+        let (name, codes) = match directive.trim().split_once('=') {
+            Some((name, codes)) => (name.trim(), codes.split(',').map(|c| c.trim().to_string()).filter(|c| !c.is_empty()).collect()),
+            None => (directive.trim(), Vec::new()),
+        };
+        
+        let line_number = line_offset + idx;
+        let scope = match name {
+            "disable-next-line" => {
+                let wrapped = match lines.get(idx + 1) {
+                    Some(next) if is_command_line(next) => lines[idx + 2..].iter().take_while(|l| continues_command(l)).count(),
+                    _ => 0,
+                };
+                SuppressionScope::NextLine { first: line_number + 1, last: line_number + 1 + wrapped }
+            }
+            "disable" => SuppressionScope::FromLine(line_number),
+            _ => continue,
+        };
+        let start_column = line.len() - trimmed.len() + if idx == 0 { column_offset } else { 0 };
+        suppressions.push(Suppression {
+            codes,
+            scope,
+            span: SourceCodeSpan {
+                start_line: line_number,
+                start_column,
+                end_line: line_number,
+                end_column: start_column + trimmed.trim_end().len(),
+            },
+        });
+    }
+    suppressions
+}
+
+/// Drop suppressed diagnostics and add a hint (I003) for every suppressed code that matched nothing
+pub fn apply_suppressions(diagnostics: Vec<DiagnosticMessage>, suppressions: &[Suppression], locale: Locale) -> Vec<DiagnosticMessage> {
+    if suppressions.is_empty() {
+        return diagnostics;
+    }
+    
+    let mut used: Vec<Vec<String>> = vec![Vec::new(); suppressions.len()];
+    let mut kept = Vec::new();
+    for diagnostic in diagnostics {
+        match suppressions.iter().position(|s| s.covers(&diagnostic)) {
+            Some(idx) => used[idx].push(diagnostic.code),
+            None => kept.push(diagnostic),
+        }
+    }
+    
+    for (suppression, used_codes) in suppressions.iter().zip(&used) {
+        let unused: Vec<&str> = if suppression.codes.is_empty() {
+            if used_codes.is_empty() { vec!["all"] } else { vec![] }
+        } else {
+            suppression.codes.iter().filter(|c| !used_codes.contains(c)).map(|c| c.as_str()).collect()
+        };
+        if unused.is_empty() {
+            continue;
+        }
+        
+        let codes = unused.join(", ");
+        kept.push(DiagnosticMessage {
+            code: "I003".to_string(),
+            severity: Severity::Hint,
+            kind: DiagnosticKind::UnusedSuppression { codes: unused.iter().map(|c| c.to_string()).collect() },
+            message: Message::new("unused-suppression").arg("codes", codes).render(locale),
+            spans: vec![
+                DiagnosticSpan { span: suppression.span.clone(), role: SpanRole::Target, message: locale.text("span-unused-suppression") },
+                DiagnosticSpan {
+                    span: suppression.span.clone(),
+                    role: SpanRole::Suggestion { replacement: String::new() },
+                    message: locale.text("fix-remove-pragma"),
+                },
+            ],
+            rich: None,
+        });
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::analyze_command;
+    use crate::parser::parse_command;
+    
+    fn analyze(content: &str) -> Vec<DiagnosticMessage> {
+        let diagnostics = analyze_command(parse_command(content, 1, 0).unwrap()).messages;
+        apply_suppressions(diagnostics, &parse_suppressions(content, 1, 0), Locale::English)
+    }
+    
+    fn codes(diagnostics: &[DiagnosticMessage]) -> Vec<&str> {
+        diagnostics.iter().map(|d| d.code.as_str()).collect()
+    }
+    
+    #[test]
+    fn test_parse_suppressions() {
+        let content = "# ffmpeg-lint: disable-next-line=W101, E502\n  #ffmpeg-lint: disable\n# a comment\nffmpeg -i a.mp4 b.mp4";
+        let suppressions = parse_suppressions(content, 1, 0);
+        assert_eq!(suppressions.len(), 2);
+        assert_eq!(suppressions[0].codes, vec!["W101", "E502"]);
+        assert_eq!(suppressions[0].scope, SuppressionScope::NextLine { first: 2, last: 2 });
+        assert!(suppressions[1].codes.is_empty());
+        assert_eq!(suppressions[1].scope, SuppressionScope::FromLine(2));
+        assert_eq!((suppressions[1].span.start_column, suppressions[1].span.end_column), (2, 23));
+    }
+    
+    #[test]
+    fn test_disable_next_line() {
+        let command = "ffmpeg -i input.mp4 -c:v libx264 -b:v 80M out.mp4";
+        assert!(codes(&analyze(command)).contains(&"W101"));
+        
+        let suppressed = analyze(&format!("# ffmpeg-lint: disable-next-line=W101\n{}", command));
+        assert!(!codes(&suppressed).contains(&"W101"));
+        assert!(!codes(&suppressed).contains(&"I003"));
+        
+        // Only the next line is covered
+        let too_far = analyze(&format!("# ffmpeg-lint: disable-next-line=W101\n\n{}", command));
+        assert!(codes(&too_far).contains(&"W101"));
+        
+        // ...with the lines the command there is wrapped over
+        let wrapped = "# ffmpeg-lint: disable-next-line=W101\nffmpeg -i input.mp4\n  -c:v libx264\n  -b:v 80M out.mp4";
+        assert!(!codes(&analyze(wrapped)).contains(&"W101"));
+    }
+    
+    #[test]
+    fn test_unused_suppression() {
+        let content = "# ffmpeg-lint: disable=W201,W101\nffmpeg -i input.mp4 -c:a mystery out.mkv";
+        let diagnostics = analyze(content);
+        assert!(!codes(&diagnostics).contains(&"W201"));
+        let hint = diagnostics.iter().find(|d| d.code == "I003").expect("expected I003");
+        assert!(matches!(&hint.kind, DiagnosticKind::UnusedSuppression { codes } if codes == &vec!["W101".to_string()]));
+        assert_eq!(hint.spans[0].span.start_line, 1);
+    }
+}
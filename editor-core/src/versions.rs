@@ -0,0 +1,341 @@
+//! Check options, codecs and filters against the ffmpeg release a command targets
+
+use crate::analyzer::end_of;
+use crate::ast::{FfmpegCommand, FilterSpec, OptionNode};
+use crate::codec_db::{Availability, CodecDatabase, FfmpegVersion};
+use crate::messages::{Locale, Message};
+use shared_types::{DiagnosticKind, DiagnosticMessage, DiagnosticSpan, Severity, SourceCodeSpan, SpanRole};
+
+/// Releases that provide a command-line option
+pub struct OptionAvailability {
+    pub name: &'static str,
+    pub availability: Availability,
+    /// Whether the replacement takes the same value, so the option can be rewritten in place
+    pub rewrite_value: bool,
+}
+
+const fn deprecated(since: FfmpegVersion, removed_in: Option<FfmpegVersion>, replacement: &'static str) -> Availability {
+    Availability { since: None, deprecated_in: Some(since), removed_in, replacement: Some(replacement) }
+}
+
+/// Options added, deprecated or removed in a known release
+pub static OPTION_AVAILABILITY: &[OptionAvailability] = &[
+    OptionAvailability {
+        name: "-vsync",
+        availability: deprecated(FfmpegVersion::new(5, 1), None, "-fps_mode"),
+        rewrite_value: true,
+    },
+    OptionAvailability {
+        name: "-fps_mode",
+        availability: Availability { since: Some(FfmpegVersion::new(5, 1)), deprecated_in: None, removed_in: None, replacement: None },
+        rewrite_value: false,
+    },
+    // Legacy spellings from before stream specifiers (0.9)
+    OptionAvailability {
+        name: "-ab",
+        availability: deprecated(FfmpegVersion::new(0, 9), None, "-b:a"),
+        rewrite_value: true,
+    },
+    OptionAvailability {
+        name: "-vb",
+        availability: deprecated(FfmpegVersion::new(0, 9), None, "-b:v"),
+        rewrite_value: true,
+    },
+    OptionAvailability {
+        name: "-map_channel",
+        availability: deprecated(FfmpegVersion::new(6, 0), Some(FfmpegVersion::new(7, 0)), "the pan filter (-af pan=...)"),
+        rewrite_value: false,
+    },
+];
+
+/// `-vsync` numbers and the `-fps_mode` names they stand for
+const VSYNC_MODES: &[(&str, &str)] = &[("-1", "auto"), ("0", "passthrough"), ("1", "cfr"), ("2", "vfr")];
+
+/// Report options, codecs and filters that `target` does not provide, deprecated ones, and
+/// experimental encoders used without `-strict experimental`. Without a target version the
+/// latest release is assumed, so deprecations and removals are reported.
+pub fn check_versions(
+    command: &FfmpegCommand,
+    db: &CodecDatabase,
+    target: Option<FfmpegVersion>,
+    locale: Locale,
+) -> Vec<DiagnosticMessage> {
+    let mut diagnostics = Vec::new();
+    
+    let all_options = command
+        .global_options
+        .iter()
+        .chain(command.inputs.iter().flat_map(|i| i.options.iter()))
+        .chain(command.outputs.iter().flat_map(|o| o.options.iter()));
+    for option in all_options {
+        diagnostics.extend(check_option(option, target, locale));
+        
+        match option {
+            OptionNode::VideoCodec { codec, codec_span, .. }
+            | OptionNode::AudioCodec { codec, codec_span, .. }
+            | OptionNode::SubtitleCodec { codec, codec_span, .. }
+            | OptionNode::Codec { codec, codec_span, .. } => {
+                if let Some(info) = db.get_codec(codec) {
+                    diagnostics.extend(check_availability(codec, "kind-codec", &info.availability, codec_span, target, ("E209", "W204"), locale));
+                }
+            }
+            OptionNode::VideoFilter { filter, .. } | OptionNode::AudioFilter { filter, .. } | OptionNode::FilterComplex { filter, .. } => {
+                diagnostics.extend(check_filters(filter, db, target, locale));
+            }
+            _ => {}
+        }
+    }
+    
+    for output in &command.outputs {
+        let allows_experimental = output.options.iter().any(|o| match o {
+            OptionNode::Generic { name, value: Some(value), .. } if name == "-strict" || name.starts_with("-strict:") => {
+                value == "experimental" || value.parse::<i32>().is_ok_and(|level| level <= -2)
+            }
+            _ => false,
+        });
+        if allows_experimental {
+            continue;
+        }
+        for option in &output.options {
+            let (OptionNode::VideoCodec { codec, codec_span, .. }
+            | OptionNode::AudioCodec { codec, codec_span, .. }
+            | OptionNode::SubtitleCodec { codec, codec_span, .. }
+            | OptionNode::Codec { codec, codec_span, .. }) = option else { continue };
+            let Some(info) = db.get_codec(codec).filter(|c| c.is_encoder) else { continue };
+            let experimental_in_target = match (info.experimental_until, target) {
+                (Some(until), Some(target)) => target < until,
+                _ => false,
+            };
+            if !info.experimental && !experimental_in_target {
+                continue;
+            }
+            
+            let release = match target {
+                Some(target) => Message::new("release").arg("version", target.to_string()),
+                None => Message::new("current-releases"),
+            };
+            diagnostics.push(DiagnosticMessage {
+                code: "E210".to_string(),
+                severity: Severity::Error,
+                kind: DiagnosticKind::MissingRequiredOption {
+                    option: "-strict experimental".to_string(),
+                    context: codec.clone(),
+                },
+                message: Message::new("experimental-encoder").arg("encoder", codec).arg("release", release).render(locale),
+                spans: vec![
+                    DiagnosticSpan { span: codec_span.clone(), role: SpanRole::Target, message: locale.text("span-experimental-encoder") },
+                    DiagnosticSpan {
+                        span: end_of(codec_span),
+                        role: SpanRole::Suggestion { replacement: " -strict experimental".to_string() },
+                        message: locale.text("fix-allow-experimental"),
+                    },
+                ],
+                rich: None,
+            });
+        }
+    }
+    
+    diagnostics
+}
+
+fn check_option(option: &OptionNode, target: Option<FfmpegVersion>, locale: Locale) -> Option<DiagnosticMessage> {
+    // Bitrate aliases are parsed as `-b:a`/`-b:v`; check the spelling that was written
+    let written = match option {
+        OptionNode::AudioBitrate { flag, .. } | OptionNode::VideoBitrate { flag, .. } => flag.as_str(),
+        _ => option.name(),
+    };
+    let base = written.split(':').next().unwrap_or(written);
+    let entry = OPTION_AVAILABILITY.iter().find(|entry| entry.name == base)?;
+    let mut diagnostic = check_availability(written, "kind-option", &entry.availability, option.span(), target, ("E414", "W414"), locale)?;
+    
+    // Offer the rewritten option when it takes the same value
+    let Some(replacement) = entry.availability.replacement.filter(|_| entry.rewrite_value) else { return Some(diagnostic) };
+    let value = match option {
+        OptionNode::AudioBitrate { bitrate, .. } | OptionNode::VideoBitrate { bitrate, .. } => bitrate.as_str(),
+        OptionNode::Generic { value: Some(value), .. } => value.as_str(),
+        _ => return Some(diagnostic),
+    };
+    let value = VSYNC_MODES
+        .iter()
+        .find(|(number, _)| base == "-vsync" && *number == value)
+        .map(|(_, mode)| *mode)
+        .unwrap_or(value);
+    diagnostic.spans.push(DiagnosticSpan {
+        span: option.span().clone(),
+        role: SpanRole::Suggestion { replacement: format!("{} {}", replacement, value) },
+        message: Message::new("fix-use").arg("replacement", replacement).render(locale),
+    });
+    Some(diagnostic)
+}
+
+fn check_filters(filter: &FilterSpec, db: &CodecDatabase, target: Option<FfmpegVersion>, locale: Locale) -> Vec<DiagnosticMessage> {
+    filter
+        .filters()
+        .filter_map(|f| {
+            let info = db.get_filter(&f.name)?;
+            check_availability(&f.name, "kind-filter", &info.availability, &f.name_span, target, ("E503", "W503"), locale)
+        })
+        .collect()
+}
+
+/// Error when `target` lacks the feature, warning when it is deprecated there. `what` is the
+/// message ID of the kind of feature
+fn check_availability(
+    name: &str,
+    what: &'static str,
+    availability: &Availability,
+    span: &SourceCodeSpan,
+    target: Option<FfmpegVersion>,
+    (error_code, warning_code): (&str, &str),
+    locale: Locale,
+) -> Option<DiagnosticMessage> {
+    let instead = |locale| match availability.replacement {
+        Some(replacement) => Message::new("use-instead").arg("replacement", replacement).render(locale),
+        None => String::new(),
+    };
+    
+    // The latest release lacks whatever some release removed
+    if let Some(target) = target.or(availability.removed_in).filter(|t| !availability.is_available_in(*t)) {
+        let reason = match (availability.since, availability.removed_in) {
+            (Some(since), _) if target < since => Message::new("added-in").arg("version", since.to_string()),
+            (_, Some(removed)) => Message::new("removed-in").arg("version", removed.to_string()),
+            _ => Message::new("not-available"),
+        };
+        let reason = |locale| reason.clone().arg("instead", instead(locale)).render(locale);
+        return Some(DiagnosticMessage {
+            code: error_code.to_string(),
+            severity: Severity::Error,
+            kind: DiagnosticKind::UnavailableInVersion {
+                name: name.to_string(),
+                version: target.to_string(),
+                reason: reason(Locale::English),
+            },
+            message: Message::new("unavailable-in-version")
+                .arg("what", Message::new(what))
+                .arg("name", name)
+                .arg("version", target.to_string())
+                .arg("reason", reason(locale))
+                .render(locale),
+            spans: vec![DiagnosticSpan {
+                span: span.clone(),
+                role: SpanRole::Target,
+                message: Message::new("span-not-in-version").arg("version", target.to_string()).render(locale),
+            }],
+            rich: None,
+        });
+    }
+    
+    if !availability.is_deprecated_in(target) {
+        return None;
+    }
+    let since = availability.deprecated_in.or(availability.removed_in)?;
+    let removal = match availability.removed_in {
+        Some(removed) if target.is_none_or(|t| t < removed) => {
+            Message::new("and-removed-in").arg("version", removed.to_string()).render(locale)
+        }
+        _ => String::new(),
+    };
+    Some(DiagnosticMessage {
+        code: warning_code.to_string(),
+        severity: Severity::Warning,
+        kind: DiagnosticKind::Deprecated {
+            name: name.to_string(),
+            since: since.to_string(),
+            replacement: availability.replacement.map(String::from),
+        },
+        message: Message::new("deprecated")
+            .arg("what", Message::new(what))
+            .arg("name", name)
+            .arg("version", since.to_string())
+            .arg("removal", removal)
+            .arg("instead", instead(locale))
+            .render(locale),
+        spans: vec![DiagnosticSpan { span: span.clone(), role: SpanRole::Target, message: locale.text("span-deprecated") }],
+        rich: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_command;
+    
+    fn codes(input: &str, target: Option<&str>) -> Vec<String> {
+        let cmd = parse_command(input, 0, 0).unwrap();
+        let target = target.and_then(FfmpegVersion::parse);
+        check_versions(&cmd, &CodecDatabase::new(), target, Locale::English).into_iter().map(|d| d.code).collect()
+    }
+    
+    fn suggestion(input: &str) -> Option<String> {
+        let cmd = parse_command(input, 0, 0).unwrap();
+        check_versions(&cmd, &CodecDatabase::new(), None, Locale::English).into_iter().flat_map(|d| d.spans).find_map(|s| match s.role {
+            SpanRole::Suggestion { replacement } => Some(replacement),
+            _ => None,
+        })
+    }
+    
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(FfmpegVersion::parse("6.1"), Some(FfmpegVersion::new(6, 1)));
+        assert_eq!(FfmpegVersion::parse("7"), Some(FfmpegVersion::new(7, 0)));
+        assert_eq!(FfmpegVersion::parse("n5.1.4"), Some(FfmpegVersion::new(5, 1)));
+        assert_eq!(FfmpegVersion::parse("ffmpeg version 6.1.1-3ubuntu5 Copyright"), Some(FfmpegVersion::new(6, 1)));
+        assert_eq!(FfmpegVersion::parse("latest"), None);
+    }
+    
+    #[test]
+    fn test_deprecated_options() {
+        assert_eq!(codes("ffmpeg -i in.mp4 -vsync 1 out.mp4", None), vec!["W414"]);
+        assert_eq!(suggestion("ffmpeg -i in.mp4 -vsync 1 out.mp4").as_deref(), Some("-fps_mode cfr"));
+        assert_eq!(suggestion("ffmpeg -i in.mp4 -ab 128k out.mp4").as_deref(), Some("-b:a 128k"));
+        assert!(codes("ffmpeg -i in.mp4 -b:a 128k out.mp4", None).is_empty());
+        // -vsync was not yet deprecated, and -fps_mode did not exist
+        assert!(codes("ffmpeg -i in.mp4 -vsync 1 out.mp4", Some("5.0")).is_empty());
+        assert_eq!(codes("ffmpeg -i in.mp4 -fps_mode cfr out.mp4", Some("5.0")), vec!["E414"]);
+        assert!(codes("ffmpeg -i in.mp4 -fps_mode cfr out.mp4", Some("6.1")).is_empty());
+    }
+    
+    #[test]
+    fn test_removed_option() {
+        let input = "ffmpeg -i in.mov -map_channel 0.1.0 out.wav";
+        assert_eq!(codes(input, Some("5.1")), Vec::<String>::new());
+        assert_eq!(codes(input, Some("6.1")), vec!["W414"]);
+        assert_eq!(codes(input, Some("7.0")), vec!["E414"]);
+        assert_eq!(codes(input, None), vec!["E414"]);
+    }
+    
+    #[test]
+    fn test_codec_and_filter_versions() {
+        assert_eq!(codes("ffmpeg -i in.mp4 -c:v av1_vaapi out.mkv", Some("6.0")), vec!["E209"]);
+        assert!(codes("ffmpeg -i in.mp4 -c:v av1_vaapi out.mkv", Some("6.1")).is_empty());
+        assert_eq!(codes("ffmpeg -i in.mp4 -af loudnorm out.mp4", Some("3.0")), vec!["E503"]);
+        assert!(codes("ffmpeg -i in.mp4 -af loudnorm out.mp4", None).is_empty());
+    }
+    
+    #[test]
+    fn test_retired_codecs_and_filters() {
+        let nvenc = "ffmpeg -i in.mp4 -c:v nvenc_h264 out.mp4";
+        assert!(codes(nvenc, Some("2.8")).is_empty());
+        assert_eq!(codes(nvenc, Some("4.4")), vec!["W204"]);
+        assert_eq!(codes(nvenc, Some("5.0")), vec!["E209"]);
+        assert_eq!(codes(nvenc, None), vec!["E209"]);
+        assert_eq!(codes("ffmpeg -i in.wav -c:a libfaac out.m4a", Some("3.1")), Vec::<String>::new());
+        assert_eq!(codes("ffmpeg -i in.wav -c:a libfaac out.m4a", None), vec!["E209"]);
+        
+        let scale2ref = "ffmpeg -i in.mp4 -i logo.png -filter_complex \"[1:v][0:v]scale2ref[logo][base]\" -map \"[base]\" out.mp4";
+        assert!(codes(scale2ref, Some("7.0")).is_empty());
+        assert_eq!(codes(scale2ref, None), vec!["W503"]);
+        assert_eq!(codes("ffmpeg -i in.mp4 -vf pp=hb/vb out.mp4", Some("7.1")), Vec::<String>::new());
+        assert_eq!(codes("ffmpeg -i in.mp4 -vf pp=hb/vb out.mp4", None), vec!["E503"]);
+    }
+    
+    #[test]
+    fn test_experimental_encoders() {
+        assert_eq!(codes("ffmpeg -i in.wav -c:a opus out.ogg", None), vec!["E210"]);
+        assert!(codes("ffmpeg -i in.wav -c:a opus -strict experimental out.ogg", None).is_empty());
+        assert_eq!(suggestion("ffmpeg -i in.wav -c:a opus out.ogg").as_deref(), Some(" -strict experimental"));
+        assert!(codes("ffmpeg -i in.wav -c:a opus -strict -2 out.ogg", None).is_empty());
+        assert_eq!(codes("ffmpeg -i in.wav -c:a aac out.m4a", Some("2.8")), vec!["E210"]);
+        assert!(codes("ffmpeg -i in.wav -c:a aac out.m4a", Some("3.0")).is_empty());
+    }
+}
@@ -3,8 +3,9 @@ use shared_types::context::Context;
 use shared_types::router::{CallHandler, ObserverImpl};
 use shared_types::storage::Storage;
 use shared_types::{
-    AnalyzeCodeParams, AnalyzerDiagnostics, GraphMetrics, GraphMetricsParams, PathResult,
-    ShortestPathParams,
+    AnalyzeCodeParams, AnalyzerDiagnostics, CodeActionParams, CodeActions, CodeExplanation, CompletionList,
+    CompletionParams, ExplainCodeParams, GraphMetrics, GraphMetricsParams, HoverParams, HoverResult, LintConfig,
+    PathResult, SetLintConfigParams, ShortestPathParams,
 };
 use std::sync::Arc;
 
@@ -107,6 +108,51 @@ impl<S: Storage> CallHandler for PathfinderHandler<S> {
     ) {
         tx.error("Code analysis not supported in pathfinder handler".to_string());
     }
+
+    fn set_lint_config(
+        &self,
+        _ctx: &Context,
+        _params: SetLintConfigParams,
+        tx: ObserverImpl<LintConfig>,
+    ) {
+        tx.error("Lint configuration not supported in pathfinder handler".to_string());
+    }
+
+    fn code_actions(
+        &self,
+        _ctx: &Context,
+        _params: CodeActionParams,
+        tx: ObserverImpl<CodeActions>,
+    ) {
+        tx.error("Code actions not supported in pathfinder handler".to_string());
+    }
+
+    fn explain_code(
+        &self,
+        _ctx: &Context,
+        _params: ExplainCodeParams,
+        tx: ObserverImpl<CodeExplanation>,
+    ) {
+        tx.error("Code explanations not supported in pathfinder handler".to_string());
+    }
+
+    fn hover(
+        &self,
+        _ctx: &Context,
+        _params: HoverParams,
+        tx: ObserverImpl<HoverResult>,
+    ) {
+        tx.error("Hover not supported in pathfinder handler".to_string());
+    }
+
+    fn complete(
+        &self,
+        _ctx: &Context,
+        _params: CompletionParams,
+        tx: ObserverImpl<CompletionList>,
+    ) {
+        tx.error("Completion not supported in pathfinder handler".to_string());
+    }
 }
 
 #[cfg(test)]
@@ -117,6 +163,7 @@ mod tests {
 
     // Mock WireResponseSender for testing
     struct MockSender {
+        #[allow(dead_code)]
         responses: Arc<std::sync::Mutex<Vec<String>>>,
     }
 
@@ -1,5 +1,4 @@
 use futures_util::SinkExt;
-use serde_json;
 use shared_types::router::{WireResponse, WireResponseSender};
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -127,6 +127,9 @@ pub enum DiagnosticKind {
     InvalidCodecForStream { codec: String, stream_type: StreamType },
     UnsupportedPixelFormat { format: String, codec: String },
     UnsupportedSampleRate { rate: String, codec: String },
+    /// `direction` is "encoder" or "decoder"; `alternatives` implement the same codec in that direction
+    WrongCodecDirection { codec: String, direction: String, alternatives: Vec<String> },
+    UnknownFormat { format: String },
     
     // E300-E399: Stream Mapping Errors
     StreamMappingError { mapping: String, reason: String },
@@ -150,12 +153,30 @@ pub enum DiagnosticKind {
     InvalidFilterParameter { filter: String, parameter: String, value: String },
     FilterChainTypeMismatch { from_type: StreamType, to_type: StreamType },
     
+    // E600-E699: Input/Output File Errors
+    OutputOverwritesInput { path: String },
+    DuplicateOutput { path: String },
+    ImageSequenceWithoutPattern { path: String },
+    
+    // E700-E799: Hardware Acceleration Errors
+    HardwareFrameMismatch { operation: String, expected: String, found: String },
+    
     // W100-W199: Performance/Quality Warnings
     HighBitrateWarning { bitrate: String },
     ResolutionUpscaling { from_res: String, to_res: String },
     LossyTranscoding { message: String },
     NoQualitySetting { codec: String },
     
+    // Version compatibility (E209, E414, E503 and their deprecation warnings)
+    UnavailableInVersion { name: String, version: String, reason: String },
+    Deprecated { name: String, since: String, replacement: Option<String> },
+    
+    // Seeking with stream copy (I002); `position` is "input" or "output"
+    SeekWithStreamCopy { seek: String, position: String, copy_option: String },
+    
+    // Suppression pragmas (I003)
+    UnusedSuppression { codes: Vec<String> },
+    
     // General errors
     ParseError { message: String },
     UnknownOption { option: String },
@@ -186,6 +207,65 @@ pub struct AnalyzerDiagnostics {
     pub messages: Vec<DiagnosticMessage>,
 }
 
+/// A stream of an input file, as reported by a probe of the real media
+#[protocol("wasm")]
+pub struct MediaStream {
+    pub stream_type: StreamType,
+    /// Codec name (e.g. "h264", "aac")
+    pub codec: Option<String>,
+    pub width: Option<usize>,
+    pub height: Option<usize>,
+    /// Frames per second
+    pub frame_rate: Option<f64>,
+    /// Audio sample rate in Hz
+    pub sample_rate: Option<usize>,
+    pub channels: Option<usize>,
+}
+
+/// Known contents of an input file, used instead of guessing streams from its extension
+#[protocol("wasm")]
+pub struct MediaFile {
+    /// Path as written in the command
+    pub path: String,
+    /// Duration in seconds
+    pub duration: Option<f64>,
+    pub streams: Vec<MediaStream>,
+}
+
+/// How diagnostics of a code or rule category are reported
+#[protocol("wasm")]
+pub enum LintLevel {
+    /// Not reported
+    Allow,
+    /// Reported as a warning
+    Warn,
+    /// Reported as an error
+    Deny,
+}
+
+/// Level for one diagnostic code (e.g. "W101") or rule category (e.g. "quality")
+#[protocol("wasm")]
+pub struct LintSetting {
+    pub name: String,
+    pub level: LintLevel,
+}
+
+/// Which diagnostics are reported and at what severity. A code's setting takes
+/// precedence over its category's; diagnostics without a setting keep their severity
+#[protocol("wasm")]
+pub struct LintConfig {
+    pub codes: Vec<LintSetting>,
+    pub categories: Vec<LintSetting>,
+}
+
+/// Parameters for storing the lint configuration of the session
+#[protocol("wasm")]
+#[codegen(fn = "set_lint_config() -> LintConfig")]
+pub struct SetLintConfigParams {
+    /// Configuration for later `analyze_code` calls; None clears it
+    pub config: Option<LintConfig>,
+}
+
 /// Parameters for code analysis
 #[protocol("wasm")]
 #[codegen(fn = "analyze_code() -> AnalyzerDiagnostics")]
@@ -198,6 +278,141 @@ pub struct AnalyzeCodeParams {
     pub line_offset: usize,
     /// Column offset for error reporting (0-based)
     pub column_offset: usize,
+    /// Optional stream information for the input files of the command
+    pub media: Option<Vec<MediaFile>>,
+    /// ffmpeg release the command will run with (e.g. "6.1"); options, codecs and filters
+    /// missing from that release are reported. Defaults to the latest release
+    pub ffmpeg_version: Option<String>,
+    /// Lint configuration for this request; defaults to the one stored for the session
+    pub lint: Option<LintConfig>,
+    /// Language tag of the diagnostic messages (e.g. "de" or "fr-CA"); unsupported languages
+    /// fall back to English
+    pub locale: Option<String>,
+}
+
+/// Replacement of the text in a span; an empty span inserts and an empty text deletes
+#[protocol("wasm")]
+pub struct TextEdit {
+    pub span: SourceCodeSpan,
+    pub new_text: String,
+}
+
+/// A fix for a diagnostic, built from one of its suggestion spans
+#[protocol("wasm")]
+pub struct CodeAction {
+    /// Human-readable description (e.g. "Replace `vp9` with `libx264`")
+    pub title: String,
+    /// Code of the diagnostic the fix clears
+    pub diagnostic_code: String,
+    pub edits: Vec<TextEdit>,
+    /// The diagnostic's first suggestion, applied by "fix all"
+    pub is_preferred: bool,
+}
+
+/// Fixes available for a range of the document
+#[protocol("wasm")]
+pub struct CodeActions {
+    pub actions: Vec<CodeAction>,
+}
+
+/// Parameters for finding the fixes of the diagnostics in a range
+#[protocol("wasm")]
+#[codegen(fn = "code_actions() -> CodeActions")]
+pub struct CodeActionParams {
+    /// The document and analysis settings, as sent to `analyze_code`
+    pub analysis: AnalyzeCodeParams,
+    /// Fixes are returned for diagnostics whose target overlaps this range
+    pub range: SourceCodeSpan,
+    /// Only return fixes for this diagnostic code
+    pub code: Option<String>,
+}
+
+/// Documentation of a diagnostic code
+#[protocol("wasm")]
+pub struct CodeExplanation {
+    pub code: String,
+    /// Severity the code is reported with unless the lint configuration changes it
+    pub severity: Severity,
+    /// English, like the explanation; the catalog is not localized
+    pub title: String,
+    /// Long-form explanation with a command that reports the code and its fixed form
+    pub rich: DiagnosticRich,
+}
+
+/// Parameters for explaining a diagnostic code
+#[protocol("wasm")]
+#[codegen(fn = "explain_code() -> CodeExplanation")]
+pub struct ExplainCodeParams {
+    /// Diagnostic code (e.g. "E201")
+    pub code: String,
+}
+
+/// Documentation of the token under the cursor
+#[protocol("wasm")]
+pub struct Hover {
+    /// Span of the option, codec, filter, path or label the documentation is about
+    pub span: SourceCodeSpan,
+    pub rich: DiagnosticRich,
+}
+
+/// Hover documentation at a position; None when nothing documented is under the cursor
+#[protocol("wasm")]
+pub struct HoverResult {
+    pub hover: Option<Hover>,
+}
+
+/// Parameters for documenting the token under the cursor
+#[protocol("wasm")]
+#[codegen(fn = "hover() -> HoverResult")]
+pub struct HoverParams {
+    /// The document and analysis settings, as sent to `analyze_code`
+    pub analysis: AnalyzeCodeParams,
+    /// Cursor position, numbered like diagnostic spans
+    pub line: usize,
+    pub column: usize,
+}
+
+/// What a completion item inserts
+#[protocol("wasm")]
+pub enum CompletionKind {
+    Option,
+    Codec,
+    Filter,
+    FilterParameter,
+    /// An input stream specifier such as `0:v`
+    StreamSpecifier,
+    /// A `[label]` of a filter graph output
+    Label,
+}
+
+/// A completion candidate
+#[protocol("wasm")]
+pub struct CompletionItem {
+    pub label: String,
+    pub kind: CompletionKind,
+    /// Short description shown next to the label
+    pub detail: Option<String>,
+    /// Text replacing the completion range
+    pub insert_text: String,
+}
+
+/// Completions at a position
+#[protocol("wasm")]
+pub struct CompletionList {
+    /// Span of the partly typed word the items replace; empty at the start of a word
+    pub range: SourceCodeSpan,
+    pub items: Vec<CompletionItem>,
+}
+
+/// Parameters for completing the word at a position
+#[protocol("wasm")]
+#[codegen(fn = "complete() -> CompletionList")]
+pub struct CompletionParams {
+    /// The document and analysis settings, as sent to `analyze_code`
+    pub analysis: AnalyzeCodeParams,
+    /// Cursor position, numbered like diagnostic spans
+    pub line: usize,
+    pub column: usize,
 }
 
 #[cfg(test)]
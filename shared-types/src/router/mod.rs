@@ -8,7 +8,7 @@ pub use router_gen::CallHandler;
 #[protocol("router", externally_tagged)]
 pub enum RequestEnum {
     Abort(usize, String),
-    Call(usize, router_gen::CallGen),
+    Call(usize, Box<router_gen::CallGen>),
 }
 
 #[protocol("router")]
@@ -151,7 +151,7 @@ pub fn handle_request(
             sender.send_response(WireResponse(id, ResponseEnum::Aborted(reason)));
         }
         RequestEnum::Call(id, call) => {
-            router_gen::gen_call(ctx, id, call, handler, sender);
+            router_gen::gen_call(ctx, id, *call, handler, sender);
         }
     }
 }
@@ -22,6 +22,36 @@ pub trait CallHandler {
         params: AnalyzeCodeParams,
         tx: ObserverImpl<AnalyzerDiagnostics>,
     );
+    fn set_lint_config(
+        &self,
+        ctx: &Context,
+        params: SetLintConfigParams,
+        tx: ObserverImpl<LintConfig>,
+    );
+    fn code_actions(
+        &self,
+        ctx: &Context,
+        params: CodeActionParams,
+        tx: ObserverImpl<CodeActions>,
+    );
+    fn explain_code(
+        &self,
+        ctx: &Context,
+        params: ExplainCodeParams,
+        tx: ObserverImpl<CodeExplanation>,
+    );
+    fn hover(
+        &self,
+        ctx: &Context,
+        params: HoverParams,
+        tx: ObserverImpl<HoverResult>,
+    );
+    fn complete(
+        &self,
+        ctx: &Context,
+        params: CompletionParams,
+        tx: ObserverImpl<CompletionList>,
+    );
 }
 
 #[allow(non_camel_case_types)]
@@ -30,6 +60,11 @@ pub enum CallGen {
     find_shortest_path(ShortestPathParams),
     compute_graph_metrics(GraphMetricsParams),
     analyze_code(AnalyzeCodeParams),
+    set_lint_config(SetLintConfigParams),
+    code_actions(CodeActionParams),
+    explain_code(ExplainCodeParams),
+    hover(HoverParams),
+    complete(CompletionParams),
 }
 
 #[allow(non_camel_case_types)]
@@ -38,6 +73,11 @@ pub enum ResponseNextGen {
     find_shortest_path(PathResult),
     compute_graph_metrics(GraphMetrics),
     analyze_code(AnalyzerDiagnostics),
+    set_lint_config(LintConfig),
+    code_actions(CodeActions),
+    explain_code(CodeExplanation),
+    hover(HoverResult),
+    complete(CompletionList),
 }
 
 pub(crate) fn gen_call(
@@ -63,6 +103,31 @@ pub(crate) fn gen_call(
             params,
             ObserverImpl::new(id, sender),
         ),
+        CallGen::set_lint_config(params) => handler.set_lint_config(
+            ctx,
+            params,
+            ObserverImpl::new(id, sender),
+        ),
+        CallGen::code_actions(params) => handler.code_actions(
+            ctx,
+            params,
+            ObserverImpl::new(id, sender),
+        ),
+        CallGen::explain_code(params) => handler.explain_code(
+            ctx,
+            params,
+            ObserverImpl::new(id, sender),
+        ),
+        CallGen::hover(params) => handler.hover(
+            ctx,
+            params,
+            ObserverImpl::new(id, sender),
+        ),
+        CallGen::complete(params) => handler.complete(
+            ctx,
+            params,
+            ObserverImpl::new(id, sender),
+        ),
     }
 }
 
@@ -84,3 +149,33 @@ impl super::ToResponseNextGen for AnalyzerDiagnostics {
         ResponseNextGen::analyze_code(self)
     }
 }
+
+impl super::ToResponseNextGen for LintConfig {
+    fn to_response_next_gen(self) -> ResponseNextGen {
+        ResponseNextGen::set_lint_config(self)
+    }
+}
+
+impl super::ToResponseNextGen for CodeActions {
+    fn to_response_next_gen(self) -> ResponseNextGen {
+        ResponseNextGen::code_actions(self)
+    }
+}
+
+impl super::ToResponseNextGen for CodeExplanation {
+    fn to_response_next_gen(self) -> ResponseNextGen {
+        ResponseNextGen::explain_code(self)
+    }
+}
+
+impl super::ToResponseNextGen for HoverResult {
+    fn to_response_next_gen(self) -> ResponseNextGen {
+        ResponseNextGen::hover(self)
+    }
+}
+
+impl super::ToResponseNextGen for CompletionList {
+    fn to_response_next_gen(self) -> ResponseNextGen {
+        ResponseNextGen::complete(self)
+    }
+}